@@ -0,0 +1,173 @@
+# English strings. Every other language falls back to these for anything it leaves out.
+# Lines are `key = text`, with `{ $name }` where a value gets filled in.
+
+title-start = Press SPACE to start
+title-last-score = Last Score: { $score }
+title-points = Points: { $points }  Best Combo: x{ $combo }
+title-profile = Profile: { $name }
+title-guide = Press H to show the controls guide again  -  S for statistics  -  V for online versus  -  TAB to switch profile
+title-difficulty = Difficulty: { $difficulty }  (press D to change)
+title-controls = Controls: { $controls }  (press O)  -  Open Walls: { $walls }  (press W)  -  Camera: { $camera }  (press F)
+title-swipe = Swipe: { $sensitivity }  (tap to change)
+
+title-time-attack = T - Time Attack
+hud-invincible = INVINCIBLE { $seconds }s
+hud-time = TIME: { $time }
+
+title-checkpoint = ENTER - Continue from Level { $level } ({ $left } continues left)
+continue-offer = C - Continue from Level { $level } ({ $left } left)
+continue-none-left = No continues left
+game-over-title = GAME OVER
+game-over-continues = Continues used: { $used }
+game-over-dismiss = Press SPACE to return to the title
+
+title-high-scores = K - High Scores
+high-scores-title = HIGH SCORES
+high-scores-empty = No high scores yet
+high-scores-rank = #
+high-scores-name = NAME
+high-scores-score = SCORE
+high-scores-level = LEVEL
+high-scores-date = DATE
+high-scores-dismiss = Press SPACE to continue
+high-scores-runs = Whole Runs
+high-scores-level-tab = Level { $level }
+high-scores-length = LENGTH
+high-scores-tabs-help = LEFT/RIGHT - Switch table   SPACE - Continue
+
+title-history = Y - Run History
+history-title = RUN HISTORY
+history-filters = Mode: { $mode }  (M)     Difficulty: { $difficulty }  (D)
+history-all = All
+history-empty = No finished runs yet
+history-date = DATE
+history-mode = MODE
+history-level = LEVEL
+history-score = SCORE
+history-time = TIME
+history-ending = ENDED BY
+history-mode-campaign = Campaign
+history-mode-time_attack = Time Attack
+history-ending-wall = Wall
+history-ending-tail = Own tail
+history-ending-obstacle = Obstacle
+history-ending-ice = Ice
+history-ending-cleared = Cleared
+history-help = UP/DOWN - Scroll   M/D - Filter   SPACE - Continue
+name-entry-title = NEW HIGH SCORE!
+name-entry-score = Score: { $score }
+name-entry-help = ARROWS - Move   ENTER - Press key   Type to enter letters   END - Done
+name-entry-too-short = Names need at least { $min } characters
+
+time-attack-title = TIME ATTACK
+time-attack-level = < Level { $level } >
+time-attack-best = Best: { $time }s
+time-attack-no-best = No time set yet
+time-attack-menu-help = LEFT/RIGHT - Level   ENTER - Start   ESC - Back
+time-attack-result-title = LEVEL { $level } - TIME ATTACK
+time-attack-time = { $time }s
+time-attack-new-best = New best! ({ $delta }s)
+time-attack-versus-best = Best { $best }s ({ $delta }s)
+time-attack-first-time = First time set!
+time-attack-crashed = Crashed - no time
+time-attack-continue = Press SPACE to continue
+
+medal-gold = Gold
+medal-silver = Silver
+medal-bronze = Bronze
+medal-none = No medal
+
+setting-latency = Audio Latency: { $ms } ms  (press L)
+setting-ducking = Music Ducking: { $ducking }  (press M)
+setting-music = Game Music: { $music }  (press G)
+setting-volume = Volume: { $percent }%  (press A)
+setting-colors = Colors: { $palette }  (press C)
+setting-motion = Reduced Motion: { $state }  (press R)
+setting-cues = Audio Cues: { $state }  (press U)
+setting-language = Language: { $language }  (press N)
+setting-window = Window: { $size }  (press X)
+setting-fullscreen = Fullscreen: { $state }  (F11 or Alt+Enter)
+setting-body = Snake Body: { $style }  (press B)
+setting-hud = HUD Size: { $size }  (press I)
+setting-preview = Path Preview: { $state }  (press P)
+setting-effects = Effects Limit: { $limit }  (press J)
+
+on = ON
+off = OFF
+camera-follow = Follow
+camera-fixed = Whole Level
+music-generated = Per Level
+music-classic = Classic
+body-smooth = Smooth
+body-classic = Classic
+hud-normal = Normal
+hud-large = Large
+effects-low = Low
+effects-medium = Medium
+effects-high = High
+
+difficulty-easy = EASY
+difficulty-normal = NORMAL
+difficulty-hard = HARD
+difficulty-insane = INSANE
+
+controls-arrows = Arrows + WASD
+controls-arrows_only = Arrow Keys
+controls-wasd = WASD
+controls-vim = Vim (HJKL)
+controls-one_button = One Button
+controls-one_button_straighten = One Button + Auto-Straighten
+
+ducking-off = Off
+ducking-light = Light
+ducking-strong = Strong
+
+palette-standard = Standard
+palette-colorblind = Colorblind
+palette-high_contrast = High Contrast
+
+swipe-low = Low
+swipe-medium = Medium
+swipe-high = High
+
+hud-level = LEVEL { $level }
+hud-tails = TAILS: { $tails }
+hud-speed = SPEED: { $speed }x
+hud-next-turn-right = NEXT TURN: RIGHT
+hud-next-turn-left = NEXT TURN: LEFT
+
+intro-get-ready = Get ready!
+mechanic-moving_food = Moving food
+mechanic-multiple_foods = Extra food
+mechanic-gravity_wells = Gravity wells
+mechanic-speed_zones = Speed zones
+mechanic-moving_walls = Moving walls
+mechanic-teleporters = Teleporters
+mechanic-wrap_around = Open walls
+mechanic-trail = Ice trail
+mechanic-boss = Boss fight
+
+objective-food = Food { $eaten }/{ $target }
+objective-length = Length { $length }/{ $target }
+objective-survive = Survive { $seconds }s
+objective-golden = Golden Food { $eaten }/{ $target }
+
+tutorial-steer-arrows-wasd = Use the arrow keys or WASD to steer
+tutorial-steer-arrows = Use the arrow keys to steer
+tutorial-steer-wasd = Use WASD to steer
+tutorial-steer-vim = Use H J K L to steer
+tutorial-steer-one-button = Press SPACE to turn
+tutorial-eat = Eat the food to grow
+tutorial-tail = Don't run into the walls or your own tail
+tutorial-dismiss = ENTER to dismiss
+
+pause-title = PAUSED
+pause-resume = Resume (P)
+pause-restart = Restart Level (R)
+pause-look = Look Around (L)
+pause-seed = Seed: { $seed }  (replay it with --seed { $seed })
+pause-look-help = ARROWS/WASD or drag - Pan   WHEEL - Zoom   L - Menu   P - Resume
+pause-controls = Controls: { $controls } (C)
+pause-feedback = Send Feedback (F)
+pause-quit = Quit to Menu (Q)
+pause-continue = Press P to continue
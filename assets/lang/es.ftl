@@ -0,0 +1,172 @@
+# Spanish strings. Anything missing here shows in English.
+
+title-start = Pulsa ESPACIO para empezar
+title-last-score = Última puntuación: { $score }
+title-points = Puntos: { $points }  Mejor combo: x{ $combo }
+title-profile = Perfil: { $name }
+title-guide = H para ver la guía de controles  -  S para estadísticas  -  V para versus en línea  -  TAB para cambiar de perfil
+title-difficulty = Dificultad: { $difficulty }  (pulsa D para cambiar)
+title-controls = Controles: { $controls }  (pulsa O)  -  Paredes abiertas: { $walls }  (pulsa W)  -  Cámara: { $camera }  (pulsa F)
+title-swipe = Deslizar: { $sensitivity }  (toca para cambiar)
+
+title-time-attack = T - Contrarreloj
+hud-invincible = INVENCIBLE { $seconds } s
+hud-time = TIEMPO: { $time }
+
+title-checkpoint = ENTER - Continuar desde el nivel { $level } (quedan { $left } continuaciones)
+continue-offer = C - Continuar desde el nivel { $level } (quedan { $left })
+continue-none-left = No quedan continuaciones
+game-over-title = FIN DE LA PARTIDA
+game-over-continues = Continuaciones usadas: { $used }
+game-over-dismiss = Pulsa ESPACIO para volver al título
+
+title-high-scores = K - Récords
+high-scores-title = RÉCORDS
+high-scores-empty = Aún no hay récords
+high-scores-rank = #
+high-scores-name = NOMBRE
+high-scores-score = PUNTOS
+high-scores-level = NIVEL
+high-scores-date = FECHA
+high-scores-dismiss = Pulsa ESPACIO para continuar
+high-scores-runs = Partidas completas
+high-scores-level-tab = Nivel { $level }
+high-scores-length = LARGO
+high-scores-tabs-help = IZQ./DER. - Cambiar tabla   ESPACIO - Continuar
+
+title-history = Y - Historial
+history-title = HISTORIAL DE PARTIDAS
+history-filters = Modo: { $mode }  (M)     Dificultad: { $difficulty }  (D)
+history-all = Todos
+history-empty = Aún no hay partidas terminadas
+history-date = FECHA
+history-mode = MODO
+history-level = NIVEL
+history-score = PUNTOS
+history-time = TIEMPO
+history-ending = FIN
+history-mode-campaign = Campaña
+history-mode-time_attack = Contrarreloj
+history-ending-wall = Muro
+history-ending-tail = Propia cola
+history-ending-obstacle = Obstáculo
+history-ending-ice = Hielo
+history-ending-cleared = Superado
+history-help = ARRIBA/ABAJO - Desplazar   M/D - Filtrar   ESPACIO - Continuar
+name-entry-title = ¡NUEVO RÉCORD!
+name-entry-score = Puntos: { $score }
+name-entry-help = FLECHAS - Mover   ENTER - Pulsar tecla   Escribe para añadir letras   END - Terminar
+name-entry-too-short = El nombre necesita al menos { $min } caracteres
+
+time-attack-title = CONTRARRELOJ
+time-attack-level = < Nivel { $level } >
+time-attack-best = Mejor: { $time } s
+time-attack-no-best = Aún sin tiempo
+time-attack-menu-help = IZQ./DER. - Nivel   ENTER - Empezar   ESC - Volver
+time-attack-result-title = NIVEL { $level } - CONTRARRELOJ
+time-attack-time = { $time } s
+time-attack-new-best = ¡Nuevo récord! ({ $delta } s)
+time-attack-versus-best = Récord { $best } s ({ $delta } s)
+time-attack-first-time = ¡Primer tiempo!
+time-attack-crashed = Choque - sin tiempo
+time-attack-continue = Pulsa ESPACIO para continuar
+
+medal-gold = Oro
+medal-silver = Plata
+medal-bronze = Bronce
+medal-none = Sin medalla
+
+setting-latency = Latencia de audio: { $ms } ms  (pulsa L)
+setting-ducking = Atenuar música: { $ducking }  (pulsa M)
+setting-music = Música: { $music }  (pulsa G)
+setting-volume = Volumen: { $percent }%  (pulsa A)
+setting-colors = Colores: { $palette }  (pulsa C)
+setting-motion = Movimiento reducido: { $state }  (pulsa R)
+setting-cues = Pistas de audio: { $state }  (pulsa U)
+setting-language = Idioma: { $language }  (pulsa N)
+setting-window = Ventana: { $size }  (pulsa X)
+setting-fullscreen = Pantalla completa: { $state }  (F11 o Alt+Enter)
+setting-body = Cuerpo: { $style }  (pulsa B)
+setting-hud = Tamaño del HUD: { $size }  (pulsa I)
+setting-preview = Vista de la ruta: { $state }  (pulsa P)
+setting-effects = Límite de efectos: { $limit }  (pulsa J)
+
+on = SÍ
+off = NO
+camera-follow = Seguir
+camera-fixed = Nivel entero
+music-generated = Por nivel
+music-classic = Clásica
+body-smooth = Suave
+body-classic = Clásico
+hud-normal = Normal
+hud-large = Grande
+effects-low = Bajo
+effects-medium = Medio
+effects-high = Alto
+
+difficulty-easy = FÁCIL
+difficulty-normal = NORMAL
+difficulty-hard = DIFÍCIL
+difficulty-insane = LOCURA
+
+controls-arrows = Flechas + WASD
+controls-arrows_only = Flechas
+controls-wasd = WASD
+controls-vim = Vim (HJKL)
+controls-one_button = Un botón
+controls-one_button_straighten = Un botón + enderezar
+
+ducking-off = No
+ducking-light = Suave
+ducking-strong = Fuerte
+
+palette-standard = Estándar
+palette-colorblind = Daltonismo
+palette-high_contrast = Alto contraste
+
+swipe-low = Baja
+swipe-medium = Media
+swipe-high = Alta
+
+hud-level = NIVEL { $level }
+hud-tails = COLAS: { $tails }
+hud-speed = VELOCIDAD: { $speed }x
+hud-next-turn-right = PRÓXIMO GIRO: DERECHA
+hud-next-turn-left = PRÓXIMO GIRO: IZQUIERDA
+
+intro-get-ready = ¡Prepárate!
+mechanic-moving_food = Comida móvil
+mechanic-multiple_foods = Comida extra
+mechanic-gravity_wells = Pozos de gravedad
+mechanic-speed_zones = Zonas de velocidad
+mechanic-moving_walls = Muros móviles
+mechanic-teleporters = Teletransportadores
+mechanic-wrap_around = Muros abiertos
+mechanic-trail = Rastro de hielo
+mechanic-boss = Jefe final
+
+objective-food = Comida { $eaten }/{ $target }
+objective-length = Longitud { $length }/{ $target }
+objective-survive = Aguanta { $seconds } s
+objective-golden = Comida dorada { $eaten }/{ $target }
+
+tutorial-steer-arrows-wasd = Usa las flechas o WASD para girar
+tutorial-steer-arrows = Usa las flechas para girar
+tutorial-steer-wasd = Usa WASD para girar
+tutorial-steer-vim = Usa H J K L para girar
+tutorial-steer-one-button = Pulsa ESPACIO para girar
+tutorial-eat = Come la comida para crecer
+tutorial-tail = No choques con las paredes ni con tu cola
+tutorial-dismiss = ENTER para cerrar
+
+pause-title = PAUSA
+pause-resume = Continuar (P)
+pause-restart = Reiniciar nivel (R)
+pause-look = Mirar alrededor (L)
+pause-seed = Semilla: { $seed }  (repítela con --seed { $seed })
+pause-look-help = FLECHAS/WASD o arrastrar - Mover   RUEDA - Zoom   L - Menú   P - Continuar
+pause-controls = Controles: { $controls } (C)
+pause-feedback = Enviar comentarios (F)
+pause-quit = Salir al menú (Q)
+pause-continue = Pulsa P para continuar
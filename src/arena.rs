@@ -0,0 +1,140 @@
+use crate::boss::Boss;
+use crate::food::ExtraFood;
+use crate::garden::Garden;
+use crate::gravity::GravityField;
+use crate::layouts::WallLayout;
+use crate::level::{special_mechanics, SpecialMechanic};
+use crate::snake::{Segment, Snake};
+use crate::teleporters::Teleporters;
+use crate::themes::Theme;
+use crate::trail::IceTrail;
+use crate::walls::MovingWalls;
+use crate::zones::SpeedZones;
+
+// Everything on the board besides the snakes and the main food
+pub struct Arena {
+    pub layout: WallLayout,
+    pub garden: Garden,
+    pub gravity: GravityField,
+    pub speed_zones: SpeedZones,
+    pub moving_walls: MovingWalls,
+    pub teleporters: Teleporters,
+    pub trail: IceTrail,
+    pub extra_food: ExtraFood,
+    pub boss: Option<Boss>,
+}
+
+impl Arena {
+    pub fn new() -> Self {
+        Self {
+            layout: WallLayout::new(),
+            garden: Garden::new(),
+            gravity: GravityField::new(),
+            speed_zones: SpeedZones::new(),
+            moving_walls: MovingWalls::new(),
+            teleporters: Teleporters::new(),
+            trail: IceTrail::new(),
+            extra_food: ExtraFood::new(),
+            boss: None,
+        }
+    }
+
+    // Swap in the mechanics for a new level. The garden carries over for the whole run.
+    pub fn setup(&mut self, level: usize, snake: &Snake) {
+        self.layout.setup(level, snake);
+        self.gravity.setup(level);
+        self.speed_zones.setup(level);
+        self.moving_walls.setup(level, snake);
+        self.trail.setup(level);
+        self.extra_food.setup(level);
+
+        if special_mechanics(level).contains(&SpecialMechanic::Teleporters) {
+            let (layout, garden, moving_walls) = (&self.layout, &self.garden, &self.moving_walls);
+            self.teleporters.place(level, |pos| {
+                !snake.is_at(pos)
+                    && !layout.is_wall(pos)
+                    && !garden.is_occupied(pos)
+                    && !moving_walls.is_on_route(pos)
+            });
+        } else {
+            self.teleporters.clear();
+        }
+
+        self.boss = if special_mechanics(level).contains(&SpecialMechanic::Boss) {
+            Some(Boss::new(snake))
+        } else {
+            None
+        };
+    }
+
+    pub fn update(&mut self, delta_time: f32, snake: &Snake) {
+        self.layout.update(delta_time, snake);
+        self.garden.update(delta_time, snake);
+        self.moving_walls.update(delta_time);
+        self.teleporters.update(delta_time);
+        self.trail.update(delta_time, snake);
+        let (layout, garden, moving_walls, teleporters) =
+            (&self.layout, &self.garden, &self.moving_walls, &self.teleporters);
+        self.extra_food.update(delta_time, snake, |pos| {
+            layout.is_wall(pos)
+                || garden.is_occupied(pos) || moving_walls.is_occupied(pos) || teleporters.is_at(pos)
+        });
+        if let Some(boss) = &mut self.boss {
+            boss.update(delta_time, snake, &mut self.garden);
+        }
+    }
+
+    // Extra food placed on demand by the debug console and test hooks
+    #[cfg(any(feature = "console", debug_assertions))]
+    pub fn spawn_food(&mut self, kind: crate::food::FoodKind, snake: &Snake) -> bool {
+        let (layout, garden, moving_walls, teleporters) =
+            (&self.layout, &self.garden, &self.moving_walls, &self.teleporters);
+        self.extra_food.spawn(kind, snake, |pos| {
+            layout.is_wall(pos)
+                || garden.is_occupied(pos) || moving_walls.is_occupied(pos) || teleporters.is_at(pos)
+        })
+    }
+
+    // How fast the snake moves from here, from speed zones and ice
+    pub fn speed_multiplier_at(&self, position: Segment) -> f32 {
+        self.speed_zones.multiplier_at(position) * self.trail.multiplier_at(position)
+    }
+
+    // Cells that kill the snake
+    pub fn is_blocked(&self, position: Segment) -> bool {
+        self.layout.is_wall(position)
+            || self.garden.is_blocked(position)
+            || self.moving_walls.is_occupied(position)
+            || self.boss.as_ref().is_some_and(|boss| boss.hits(position))
+    }
+
+    // Cells that something large is sliding over (or a reloaded layout put a wall on), food there has to move
+    pub fn is_covered(&self, position: Segment) -> bool {
+        self.layout.is_wall(position)
+            || self.moving_walls.is_occupied(position)
+            || self.boss.as_ref().is_some_and(|boss| boss.cells().contains(&position))
+    }
+
+    // Cells food shouldn't be placed on
+    pub fn is_occupied(&self, position: Segment) -> bool {
+        self.garden.is_occupied(position)
+            || self.moving_walls.is_occupied(position)
+            || self.teleporters.is_at(position)
+            || self.extra_food.is_at(position)
+            || self.is_covered(position)
+    }
+
+    pub fn draw(&self, theme: &Theme) {
+        self.speed_zones.draw();
+        self.layout.draw();
+        self.trail.draw(theme);
+        self.gravity.draw();
+        self.garden.draw();
+        self.teleporters.draw();
+        self.extra_food.draw(theme);
+        self.moving_walls.draw();
+        if let Some(boss) = &self.boss {
+            boss.draw();
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use macroquad::prelude::*;
+use crate::grid::{CELL_SIZE, get_offset, is_within_grid};
+use crate::snake::{Segment, Snake};
+
+// Moves ahead the path preview looks
+const PREVIEW_CELLS: usize = 8;
+
+// Assist overlay marking the cells the head is headed for, from the current heading and
+// anything already buffered. Where that path runs into a wall, an obstacle or the body,
+// the cell turns red and the preview stops there.
+pub fn draw_path_preview(snake: &Snake, is_blocked: impl Fn(Segment) -> bool) {
+    let offset = get_offset();
+    let path = snake.planned_path(PREVIEW_CELLS);
+
+    for (step, cell) in path.iter().enumerate() {
+        let hits = !is_within_grid(cell.x, cell.y)
+            || is_blocked(*cell)
+            || snake.occupies_after(*cell, step + 1)
+            || path[..step].contains(cell);
+        // Fades out the further ahead it looks
+        let alpha = 0.45 - step as f32 * 0.04;
+        let (x, y) = (offset.x + cell.x as f32 * CELL_SIZE, offset.y + cell.y as f32 * CELL_SIZE);
+        if hits {
+            draw_rectangle(x, y, CELL_SIZE, CELL_SIZE, Color::new(1.0, 0.1, 0.1, 0.5));
+            draw_rectangle_lines(x, y, CELL_SIZE, CELL_SIZE, 2.0, RED);
+            return;
+        }
+        draw_rectangle_lines(x + 2.0, y + 2.0, CELL_SIZE - 4.0, CELL_SIZE - 4.0, 2.0, Color::new(1.0, 1.0, 1.0, alpha));
+    }
+}
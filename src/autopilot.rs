@@ -0,0 +1,147 @@
+use macroquad::prelude::*;
+use crate::motion;
+use std::collections::VecDeque;
+use crate::bots::BotView;
+use crate::difficulty::Difficulty;
+use crate::grid::{draw_grid, GRID_HEIGHT, GRID_WIDTH};
+use crate::headless::HeadlessGame;
+use crate::input::PlayerCommand;
+use crate::skins::SnakeSkin;
+use crate::snake::{Direction, Segment};
+use crate::themes::get_theme;
+
+// Seconds the title screen has to sit untouched before the demo starts
+pub const IDLE_TIME: f32 = 30.0;
+
+// Greedy steering with a flood-fill lookahead: heads for the food, but won't turn
+// into a pocket that's too small to fit the snake if there's anywhere roomier
+pub struct AutopilotController;
+
+impl AutopilotController {
+    pub fn decide(&self, view: &BotView) -> Option<PlayerCommand> {
+        let head = view.head();
+        let length = view.snake.len();
+        let mut best: Option<((bool, i32), Direction)> = None;
+
+        for dir in [Direction::Up, Direction::Down, Direction::Left, Direction::Right] {
+            if dir == view.direction.opposite() {
+                continue;
+            }
+            let Some(next) = view.step_from(head, dir) else {
+                continue;
+            };
+            if !view.is_free(next) {
+                continue;
+            }
+
+            let roomy = open_cells(next, view, length + 2) > length;
+            let distance = (next.x - view.food.x).abs() + (next.y - view.food.y).abs();
+            let score = (roomy, -distance);
+            if best.is_none_or(|(best_score, _)| score > best_score) {
+                best = Some((score, dir));
+            }
+        }
+
+        best.map(|(_, dir)| PlayerCommand::SetDirection(dir))
+    }
+}
+
+// Counts free cells reachable from `start`, stopping early once `limit` is reached
+fn open_cells(start: Segment, view: &BotView, limit: usize) -> usize {
+    let mut visited = vec![false; (GRID_WIDTH * GRID_HEIGHT) as usize];
+    let mut frontier = VecDeque::from([start]);
+    visited[(start.y * GRID_WIDTH + start.x) as usize] = true;
+    let mut count = 0;
+
+    while let Some(cell) = frontier.pop_front() {
+        count += 1;
+        if count >= limit {
+            break;
+        }
+        for dir in [Direction::Up, Direction::Down, Direction::Left, Direction::Right] {
+            let Some(next) = view.step_from(cell, dir) else {
+                continue;
+            };
+            let index = (next.y * GRID_WIDTH + next.x) as usize;
+            if visited[index] || !view.is_free(next) {
+                continue;
+            }
+            visited[index] = true;
+            frontier.push_back(next);
+        }
+    }
+
+    count
+}
+
+// Title screen demo: the autopilot plays the first level until someone presses a key
+pub struct AttractMode {
+    pub showing: bool,
+    game: HeadlessGame,
+    autopilot: AutopilotController,
+    move_timer: f32,
+}
+
+impl AttractMode {
+    pub fn new() -> Self {
+        Self {
+            showing: false,
+            game: HeadlessGame::new(Difficulty::Normal),
+            autopilot: AutopilotController,
+            move_timer: 0.0,
+        }
+    }
+
+    pub fn start(&mut self) {
+        self.showing = true;
+        self.game = HeadlessGame::new(Difficulty::Normal);
+        self.move_timer = 0.0;
+    }
+
+    pub fn stop(&mut self) {
+        self.showing = false;
+    }
+
+    pub fn update(&mut self, delta_time: f32) {
+        self.move_timer += delta_time;
+        loop {
+            let snake = self.game.snake();
+            let delay = snake.move_delay / snake.speed_multiplier;
+            if self.move_timer < delay {
+                break;
+            }
+            self.move_timer -= delay;
+
+            let command = self.autopilot.decide(&BotView::new(&self.game));
+            let observation = self.game.step(command);
+            // Start over once the level is cleared or the autopilot gets itself stuck
+            if !observation.alive || observation.level > 1 {
+                self.game = HeadlessGame::new(Difficulty::Normal);
+            }
+        }
+    }
+
+    pub fn draw(&self, skin: &SnakeSkin, smooth: bool) {
+        let theme = get_theme(self.game.level());
+        clear_background(theme.background);
+        draw_grid(theme.grid, self.game.snake().wrap);
+        self.game.arena().draw(&theme);
+        self.game.snake().draw(&theme, skin, smooth);
+        self.game.food().draw(&theme);
+
+        let title = "DEMO";
+        let title_width = measure_text(title, None, 48, 1.0).width;
+        draw_text(title, (screen_width() - title_width) / 2.0, 50.0, 48.0, theme.ui_text);
+
+        let prompt = "Press any key to play";
+        let prompt_width = measure_text(prompt, None, 28, 1.0).width;
+        let pulse = motion::pulse(4.0, 0.3, 0.7);
+        draw_text(
+            prompt,
+            (screen_width() - prompt_width) / 2.0,
+            screen_height() - 30.0,
+            28.0,
+            Color::new(1.0, 1.0, 1.0, pulse),
+        );
+    }
+}
@@ -0,0 +1,119 @@
+use std::time::{Duration, Instant};
+use crate::arena::Arena;
+use crate::food::Food;
+use crate::cpu_snake::CpuSnakeManager;
+use crate::grid::GRID_WIDTH;
+use crate::input::PlayerCommand;
+use crate::perf::{System, SYSTEMS};
+use crate::snake::{Direction, Snake};
+use crate::tension::Tension;
+
+// Cells the benchmark snake grows to
+const SNAKE_LENGTH: usize = 1000;
+// A level with extra food, speed zones and teleporters, so the arena has plenty to do
+const BENCH_LEVEL: usize = 7;
+
+// `--bench [ticks]`: runs the game systems on a long snake without a window and prints
+// how long each took per tick. The snake snakes back and forth across the board
+// with the walls open, so it can grow long without crashing.
+pub fn run_bench(ticks: usize) {
+    let mut snake = Snake::new();
+    snake.wrap = true;
+    // Grown to full length before the clock starts, one segment per move
+    for _ in 0..SNAKE_LENGTH {
+        snake.grow();
+        snake.apply(PlayerCommand::SetDirection(serpentine(&snake)));
+        snake.update(snake.move_delay, None);
+    }
+    let mut arena = Arena::new();
+    arena.setup(BENCH_LEVEL, &snake);
+    let mut food = Food::new(&snake, |pos| arena.is_occupied(pos));
+    let mut tension = Tension::new();
+    let mut cpu_snakes = CpuSnakeManager::new();
+    let mut crash_reported = false;
+    let mut times: Vec<Vec<Duration>> = vec![Vec::with_capacity(ticks); SYSTEMS.len()];
+    let mut record = |system: System, started: Instant| {
+        let index = SYSTEMS.iter().position(|s| *s == system).unwrap_or(0);
+        times[index].push(started.elapsed());
+    };
+
+    let started_all = Instant::now();
+    for _ in 0..ticks {
+        // One move per tick, so every tick does a full move's work
+        let delta_time = snake.move_delay;
+
+        let started = Instant::now();
+        snake.apply(PlayerCommand::SetDirection(serpentine(&snake)));
+        snake.update(delta_time, None);
+        record(System::Movement, started);
+
+        let started = Instant::now();
+        let crashed = snake.is_dead() || arena.is_blocked(snake.head());
+        record(System::Collision, started);
+        if crashed && !crash_reported {
+            crash_reported = true;
+            println!("Warning: the benchmark snake crashed at length {}, timings past here are not representative", snake.length());
+        }
+
+        let started = Instant::now();
+        arena.update(delta_time, &snake);
+        record(System::Arena, started);
+
+        let started = Instant::now();
+        if food.is_at(snake.head()) || food.boxed_in || arena.is_covered(food.position) {
+            food.relocate(&snake, |pos| arena.is_occupied(pos));
+        }
+        food.wander(delta_time, &snake, |pos| arena.is_occupied(pos));
+        record(System::Food, started);
+
+        let started = Instant::now();
+        cpu_snakes.update(delta_time, BENCH_LEVEL);
+        record(System::CpuSnakes, started);
+
+        let started = Instant::now();
+        tension.update(delta_time, &snake, |pos| arena.is_blocked(pos));
+        record(System::Tension, started);
+    }
+    let total = started_all.elapsed();
+
+    println!("bench: {} ticks, snake length {}, level {}", ticks, snake.length(), BENCH_LEVEL);
+    for (system, samples) in SYSTEMS.iter().zip(&mut times) {
+        if samples.is_empty() {
+            continue;
+        }
+        samples.sort();
+        let micros = |time: Duration| time.as_secs_f64() * 1_000_000.0;
+        let mean = samples.iter().map(|time| micros(*time)).sum::<f64>() / samples.len() as f64;
+        let percentile = |p: f64| micros(samples[((samples.len() - 1) as f64 * p) as usize]);
+        println!(
+            "{:>10}: mean={:.2}us p50={:.2}us p99={:.2}us max={:.2}us",
+            system.name(),
+            mean,
+            percentile(0.5),
+            percentile(0.99),
+            micros(samples[samples.len() - 1])
+        );
+    }
+    println!(
+        "total: {:.1}ms ({:.2}us per tick, {:.0} ticks per second)",
+        total.as_secs_f64() * 1000.0,
+        total.as_secs_f64() * 1_000_000.0 / ticks.max(1) as f64,
+        ticks as f64 / total.as_secs_f64()
+    );
+}
+
+// Right along even rows and left along odd ones, dropping a row at each edge. The board
+// has an even number of rows, so this carries on when the snake wraps to the top.
+fn serpentine(snake: &Snake) -> Direction {
+    let head = snake.head();
+    let along = if head.y % 2 == 0 { Direction::Right } else { Direction::Left };
+    let at_edge = match along {
+        Direction::Right => head.x == GRID_WIDTH - 1,
+        _ => head.x == 0,
+    };
+    if at_edge || along == snake.dir.opposite() {
+        Direction::Down
+    } else {
+        along
+    }
+}
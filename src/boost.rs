@@ -0,0 +1,84 @@
+use macroquad::prelude::*;
+use crate::hud::hud_scale;
+use crate::themes::Theme;
+
+// How much faster the snake goes while boosting
+const BOOST_SPEED: f32 = 2.0;
+// Seconds of boosting a full bar holds, and seconds it takes to fill back up
+const DRAIN_TIME: f32 = 2.5;
+const REFILL_TIME: f32 = 5.0;
+// Once the bar runs dry it has to refill this far before boosting works again
+const RECOVER_AT: f32 = 0.3;
+// Points lost for each plant smashed while boosting
+pub const SMASH_COST: u32 = 15;
+
+// Holding SHIFT boosts the snake to double speed for as long as the stamina bar lasts.
+// Boosting into grown plants smashes through them instead of crashing, at a cost.
+pub struct Stamina {
+    pub value: f32, // 0..1
+    boosting: bool,
+    exhausted: bool,
+}
+
+impl Stamina {
+    pub fn new() -> Self {
+        Self {
+            value: 1.0,
+            boosting: false,
+            exhausted: false,
+        }
+    }
+
+    pub fn held() -> bool {
+        is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift)
+    }
+
+    pub fn refill(&mut self) {
+        *self = Self::new();
+    }
+
+    pub fn update(&mut self, delta_time: f32, held: bool) {
+        if self.exhausted && self.value >= RECOVER_AT {
+            self.exhausted = false;
+        }
+        self.boosting = held && !self.exhausted;
+
+        if self.boosting {
+            self.value -= delta_time / DRAIN_TIME;
+            if self.value <= 0.0 {
+                self.value = 0.0;
+                self.boosting = false;
+                self.exhausted = true;
+            }
+        } else {
+            self.value = (self.value + delta_time / REFILL_TIME).min(1.0);
+        }
+    }
+
+    pub fn is_boosting(&self) -> bool {
+        self.boosting
+    }
+
+    pub fn speed_multiplier(&self) -> f32 {
+        if self.boosting {
+            BOOST_SPEED
+        } else {
+            1.0
+        }
+    }
+
+    // Sits to the right of the whip meter
+    pub fn draw(&self, theme: &Theme) {
+        let color = if self.exhausted {
+            RED
+        } else if self.boosting {
+            ORANGE
+        } else {
+            theme.snake_head
+        };
+        let hud = hud_scale();
+        draw_text("BOOST", 165.0 * hud, 80.0 * hud, 20.0 * hud, color);
+        draw_rectangle_lines(230.0 * hud, 68.0 * hud, 80.0 * hud, 10.0 * hud, 1.0, theme.ui_text);
+        draw_rectangle(230.0 * hud, 68.0 * hud, 80.0 * hud * self.value, 10.0 * hud, color);
+    }
+}
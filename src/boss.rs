@@ -0,0 +1,286 @@
+use macroquad::prelude::*;
+use ::rand::Rng;
+use crate::camera::add_trauma;
+use crate::garden::Garden;
+use crate::grid::{GRID_WIDTH, GRID_HEIGHT, CELL_SIZE, get_offset};
+use crate::hud::hud_scale;
+use crate::level::{foods_to_clear, BOSS_LEVEL};
+use crate::snake::{Direction, Segment, Snake};
+use crate::rng;
+
+const BOSS_SIZE: i32 = 2;
+const MOVE_DELAY: f32 = 0.5;
+const ATTACK_DELAY: f32 = 3.0;
+const PROJECTILE_DELAY: f32 = 0.12;
+const LUNGE_WINDUP: f32 = 0.6;
+const LUNGE_STEP_DELAY: f32 = 0.05;
+const LUNGE_DISTANCE: u32 = 6;
+const SUMMON_COUNT: usize = 2;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum BossAttack {
+    Idle,
+    LungeWindup(Direction),
+    Lunge { dir: Direction, steps_left: u32 },
+}
+
+pub struct Projectile {
+    pub position: Segment,
+    pub dir: Direction,
+    pub timer: f32,
+}
+
+pub struct Boss {
+    pub position: Segment, // Top-left cell of the 2x2 body
+    pub hp: u32,
+    max_hp: u32,
+    pub attack: BossAttack,
+    pub projectiles: Vec<Projectile>,
+    move_timer: f32,
+    attack_timer: f32,
+    hurt_timer: f32,
+}
+
+impl Boss {
+    // Spawns in the corner of the grid furthest from the player
+    pub fn new(snake: &Snake) -> Self {
+        let head = snake.head();
+        let x = if head.x < GRID_WIDTH / 2 { GRID_WIDTH - 6 } else { 4 };
+        let y = if head.y < GRID_HEIGHT / 2 { GRID_HEIGHT - 6 } else { 4 };
+
+        // One hit per food, so the fight lasts exactly as long as the level's food objective
+        let max_hp = foods_to_clear(BOSS_LEVEL);
+        Self {
+            position: Segment { x, y },
+            hp: max_hp,
+            max_hp,
+            attack: BossAttack::Idle,
+            projectiles: Vec::new(),
+            move_timer: 0.0,
+            attack_timer: ATTACK_DELAY,
+            hurt_timer: 0.0,
+        }
+    }
+
+    pub fn is_defeated(&self) -> bool {
+        self.hp == 0
+    }
+
+    pub fn take_hit(&mut self) {
+        self.hp = self.hp.saturating_sub(1);
+        self.hurt_timer = 0.3;
+        add_trauma(0.4);
+    }
+
+    pub fn cells(&self) -> Vec<Segment> {
+        let mut cells = Vec::new();
+        for dy in 0..BOSS_SIZE {
+            for dx in 0..BOSS_SIZE {
+                cells.push(Segment { x: self.position.x + dx, y: self.position.y + dy });
+            }
+        }
+        cells
+    }
+
+    // Body or projectiles, anything that kills the player on contact
+    pub fn hits(&self, position: Segment) -> bool {
+        self.cells().contains(&position)
+            || self.projectiles.iter().any(|projectile| projectile.position == position)
+    }
+
+    pub fn update(&mut self, delta_time: f32, snake: &Snake, garden: &mut Garden) {
+        self.hurt_timer = (self.hurt_timer - delta_time).max(0.0);
+        self.update_projectiles(delta_time);
+
+        if self.is_defeated() {
+            return;
+        }
+
+        self.move_timer += delta_time;
+        match self.attack {
+            BossAttack::Idle => {
+                if self.move_timer >= MOVE_DELAY {
+                    self.move_timer = 0.0;
+                    self.wander();
+                }
+
+                self.attack_timer -= delta_time;
+                if self.attack_timer <= 0.0 {
+                    self.attack_timer = ATTACK_DELAY;
+                    self.start_attack(snake, garden);
+                }
+            }
+            BossAttack::LungeWindup(dir) => {
+                if self.move_timer >= LUNGE_WINDUP {
+                    self.move_timer = 0.0;
+                    self.attack = BossAttack::Lunge { dir, steps_left: LUNGE_DISTANCE };
+                    add_trauma(0.5);
+                }
+            }
+            BossAttack::Lunge { dir, steps_left } => {
+                if self.move_timer >= LUNGE_STEP_DELAY {
+                    self.move_timer = 0.0;
+                    let moved = self.step(dir);
+                    self.attack = if moved && steps_left > 1 {
+                        BossAttack::Lunge { dir, steps_left: steps_left - 1 }
+                    } else {
+                        BossAttack::Idle
+                    };
+                }
+            }
+        }
+    }
+
+    fn start_attack(&mut self, snake: &Snake, garden: &mut Garden) {
+        let mut rng = rng::gameplay();
+        let toward_player = self.direction_to(snake.head());
+
+        match rng.gen_range(0..3) {
+            0 => {
+                // Spit a spread of three projectiles at the player
+                let center = self.center_cell();
+                for offset in -1..=1 {
+                    let position = match toward_player {
+                        Direction::Up | Direction::Down => Segment { x: center.x + offset, y: center.y },
+                        Direction::Left | Direction::Right => Segment { x: center.x, y: center.y + offset },
+                    };
+                    self.projectiles.push(Projectile { position, dir: toward_player, timer: 0.0 });
+                }
+                add_trauma(0.2);
+            }
+            1 => {
+                // Summon plants near the player
+                let head = snake.head();
+                for _ in 0..SUMMON_COUNT {
+                    let position = Segment {
+                        x: (head.x + rng.gen_range(-6..=6)).clamp(0, GRID_WIDTH - 1),
+                        y: (head.y + rng.gen_range(-6..=6)).clamp(0, GRID_HEIGHT - 1),
+                    };
+                    if !snake.is_at(position) {
+                        garden.force_seed(position);
+                    }
+                }
+            }
+            _ => {
+                self.move_timer = 0.0;
+                self.attack = BossAttack::LungeWindup(toward_player);
+            }
+        }
+    }
+
+    fn update_projectiles(&mut self, delta_time: f32) {
+        for projectile in &mut self.projectiles {
+            projectile.timer += delta_time;
+            if projectile.timer >= PROJECTILE_DELAY {
+                projectile.timer = 0.0;
+                match projectile.dir {
+                    Direction::Up => projectile.position.y -= 1,
+                    Direction::Down => projectile.position.y += 1,
+                    Direction::Left => projectile.position.x -= 1,
+                    Direction::Right => projectile.position.x += 1,
+                }
+            }
+        }
+
+        self.projectiles.retain(|projectile| {
+            let pos = projectile.position;
+            pos.x >= 0 && pos.x < GRID_WIDTH && pos.y >= 0 && pos.y < GRID_HEIGHT
+        });
+    }
+
+    fn wander(&mut self) {
+        let dir = match rng::gameplay().gen_range(0..4) {
+            0 => Direction::Up,
+            1 => Direction::Down,
+            2 => Direction::Left,
+            _ => Direction::Right,
+        };
+        self.step(dir);
+    }
+
+    // Moves one cell, returns false if the edge of the grid is in the way
+    fn step(&mut self, dir: Direction) -> bool {
+        let mut next = self.position;
+        match dir {
+            Direction::Up => next.y -= 1,
+            Direction::Down => next.y += 1,
+            Direction::Left => next.x -= 1,
+            Direction::Right => next.x += 1,
+        }
+
+        if next.x < 0 || next.x > GRID_WIDTH - BOSS_SIZE || next.y < 0 || next.y > GRID_HEIGHT - BOSS_SIZE {
+            return false;
+        }
+
+        self.position = next;
+        true
+    }
+
+    fn center_cell(&self) -> Segment {
+        Segment { x: self.position.x + BOSS_SIZE / 2, y: self.position.y + BOSS_SIZE / 2 }
+    }
+
+    // Straight-line direction toward a target along the longer axis
+    fn direction_to(&self, target: Segment) -> Direction {
+        let center = self.center_cell();
+        let dx = target.x - center.x;
+        let dy = target.y - center.y;
+
+        if dx.abs() >= dy.abs() {
+            if dx < 0 { Direction::Left } else { Direction::Right }
+        } else if dy < 0 {
+            Direction::Up
+        } else {
+            Direction::Down
+        }
+    }
+
+    pub fn draw(&self) {
+        let offset = get_offset();
+
+        for projectile in &self.projectiles {
+            draw_circle(
+                offset.x + (projectile.position.x as f32 + 0.5) * CELL_SIZE,
+                offset.y + (projectile.position.y as f32 + 0.5) * CELL_SIZE,
+                CELL_SIZE * 0.3,
+                Color::new(0.6, 1.0, 0.2, 1.0),
+            );
+        }
+
+        if self.is_defeated() {
+            return;
+        }
+
+        // Flash white when hit, red while winding up a lunge
+        let body_color = if self.hurt_timer > 0.0 {
+            WHITE
+        } else if matches!(self.attack, BossAttack::LungeWindup(_)) && (get_time() * 20.0).sin() > 0.0 {
+            RED
+        } else {
+            Color::new(0.5, 0.0, 0.1, 1.0)
+        };
+
+        let x = offset.x + self.position.x as f32 * CELL_SIZE;
+        let y = offset.y + self.position.y as f32 * CELL_SIZE;
+        let size = BOSS_SIZE as f32 * CELL_SIZE;
+
+        draw_rectangle(x, y, size, size, body_color);
+        draw_rectangle_lines(x, y, size, size, 3.0, MAROON);
+        draw_circle(x + size * 0.3, y + size * 0.35, 4.0, YELLOW);
+        draw_circle(x + size * 0.7, y + size * 0.35, 4.0, YELLOW);
+    }
+
+    pub fn draw_health_bar(&self) {
+        let hud = hud_scale();
+        let bar_width = 300.0 * hud;
+        let bar_x = (screen_width() - bar_width) / 2.0;
+        let bar_y = 45.0 * hud;
+        let bar_height = 12.0 * hud;
+        let health = self.hp as f32 / self.max_hp.max(1) as f32;
+
+        draw_text("VYPERTRON", bar_x, bar_y - 4.0 * hud, 20.0 * hud, RED);
+        draw_rectangle(bar_x, bar_y, bar_width, bar_height, Color::new(0.2, 0.0, 0.0, 1.0));
+        draw_rectangle(bar_x, bar_y, bar_width * health, bar_height, RED);
+        draw_rectangle_lines(bar_x, bar_y, bar_width, bar_height, 2.0, MAROON);
+    }
+}
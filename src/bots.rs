@@ -0,0 +1,200 @@
+use std::time::{Duration, Instant};
+use ::rand::prelude::Rng;
+use crate::autopilot::AutopilotController;
+use crate::difficulty::Difficulty;
+use crate::grid::{is_within_grid, wrap_position, GRID_HEIGHT, GRID_WIDTH};
+use crate::headless::{GameObservation, HeadlessGame};
+use crate::input::PlayerCommand;
+use crate::rng;
+use crate::snake::{Direction, Segment};
+
+// Time a bot gets to make each decision before its move is thrown away
+const TICK_BUDGET: Duration = Duration::from_millis(2);
+// Moves an exhibition game may last, so a bot that circles forever can't stall it
+const MAX_MOVES: u32 = 20_000;
+
+// What a bot can see of the board. It's a copy, so a bot can't reach into the game.
+pub struct BotView {
+    pub direction: Direction,
+    pub wrap: bool,
+    pub food: Segment, // A golden food while there is one, as it's worth the most
+    pub snake: Vec<Segment>, // Head first
+    blocked: Vec<bool>, // Walls, plants, and the snake itself
+}
+
+impl BotView {
+    pub fn new(game: &HeadlessGame) -> Self {
+        let snake = game.snake();
+        let mut blocked = vec![false; (GRID_WIDTH * GRID_HEIGHT) as usize];
+        for y in 0..GRID_HEIGHT {
+            for x in 0..GRID_WIDTH {
+                let cell = Segment { x, y };
+                blocked[(y * GRID_WIDTH + x) as usize] = snake.is_at(cell) || game.arena().is_blocked(cell)
+                    || game.arena().extra_food.is_poison(cell);
+            }
+        }
+
+        Self {
+            direction: snake.dir,
+            wrap: snake.wrap,
+            food: game.arena().extra_food.golden().unwrap_or(game.food().position),
+            snake: snake.body.iter().copied().collect(),
+            blocked,
+        }
+    }
+
+    pub fn head(&self) -> Segment {
+        self.snake[0]
+    }
+
+    // Cells off the board count as blocked
+    pub fn is_free(&self, cell: Segment) -> bool {
+        is_within_grid(cell.x, cell.y) && !self.blocked[(cell.y * GRID_WIDTH + cell.x) as usize]
+    }
+
+    // The cell one step away, wrapping around the edges when the walls are open
+    pub fn step_from(&self, cell: Segment, dir: Direction) -> Option<Segment> {
+        let (dx, dy) = dir.delta();
+        let (mut x, mut y) = (cell.x + dx, cell.y + dy);
+        if self.wrap {
+            (x, y) = wrap_position(x, y);
+        }
+        if is_within_grid(x, y) {
+            Some(Segment { x, y })
+        } else {
+            None
+        }
+    }
+}
+
+// Write your own snake AI by implementing this and adding it to `bot_named`.
+// `decide` is called once per snake move; return None to carry straight on.
+pub trait SnakeBot {
+    fn name(&self) -> &'static str;
+    fn decide(&mut self, view: &BotView) -> Option<PlayerCommand>;
+}
+
+impl SnakeBot for AutopilotController {
+    fn name(&self) -> &'static str {
+        "autopilot"
+    }
+
+    fn decide(&mut self, view: &BotView) -> Option<PlayerCommand> {
+        AutopilotController::decide(self, view)
+    }
+}
+
+// Mostly goes straight, turning at random onto any free cell. A baseline to beat.
+pub struct WanderBot;
+
+impl SnakeBot for WanderBot {
+    fn name(&self) -> &'static str {
+        "wander"
+    }
+
+    fn decide(&mut self, view: &BotView) -> Option<PlayerCommand> {
+        // From the run's seed, so --seed plays the same wander back
+        let mut rng = rng::gameplay();
+        let free = |dir: Direction| view.step_from(view.head(), dir).is_some_and(|cell| view.is_free(cell));
+        if free(view.direction) && rng.gen_bool(0.8) {
+            return None;
+        }
+        let turns: Vec<Direction> = [Direction::Up, Direction::Down, Direction::Left, Direction::Right]
+            .into_iter()
+            .filter(|dir| *dir != view.direction.opposite() && free(*dir))
+            .collect();
+        if turns.is_empty() {
+            return None;
+        }
+        Some(PlayerCommand::SetDirection(turns[rng.gen_range(0..turns.len())]))
+    }
+}
+
+pub const BOT_NAMES: [&str; 2] = ["autopilot", "wander"];
+
+pub fn bot_named(name: &str) -> Option<Box<dyn SnakeBot>> {
+    match name {
+        "autopilot" => Some(Box::new(AutopilotController)),
+        "wander" => Some(Box::new(WanderBot)),
+        _ => None,
+    }
+}
+
+// Plays a bot on its own game, holding it to the tick budget
+pub struct BotRunner {
+    pub bot: Box<dyn SnakeBot>,
+    pub game: HeadlessGame,
+    pub moves: u32,
+    pub overruns: u32, // Decisions that took longer than the budget and were ignored
+}
+
+impl BotRunner {
+    pub fn new(bot: Box<dyn SnakeBot>, difficulty: Difficulty) -> Self {
+        Self {
+            bot,
+            game: HeadlessGame::new(difficulty),
+            moves: 0,
+            overruns: 0,
+        }
+    }
+
+    pub fn step(&mut self) -> GameObservation {
+        let view = BotView::new(&self.game);
+        let started = Instant::now();
+        let mut command = self.bot.decide(&view);
+        if started.elapsed() > TICK_BUDGET {
+            command = None;
+            self.overruns += 1;
+        }
+        self.moves += 1;
+        self.game.step(command)
+    }
+}
+
+// `--exhibition bot_a bot_b [games]`: each bot plays the same number of games on
+// its own board and the better average wins
+pub fn run_exhibition(names: [&str; 2], games: u32, difficulty: Difficulty) {
+    let mut totals = [(0u32, 0u32); 2]; // Tails, points
+
+    for game in 1..=games {
+        for (side, name) in names.iter().enumerate() {
+            let Some(bot) = bot_named(name) else {
+                println!("Unknown bot: {} (try one of: {})", name, BOT_NAMES.join(", "));
+                return;
+            };
+            let mut runner = BotRunner::new(bot, difficulty);
+            let mut observation = runner.game.observe(false);
+            while observation.alive && runner.moves < MAX_MOVES {
+                observation = runner.step();
+            }
+
+            println!(
+                "game {} {}: level={} tails={} points={} moves={} overruns={}",
+                game,
+                runner.bot.name(),
+                observation.level,
+                observation.tails,
+                observation.points,
+                runner.moves,
+                runner.overruns
+            );
+            totals[side].0 += observation.tails;
+            totals[side].1 += observation.points;
+        }
+    }
+
+    let games = games.max(1);
+    for (side, name) in names.iter().enumerate() {
+        println!(
+            "{}: average tails={:.1} points={:.1}",
+            name,
+            totals[side].0 as f32 / games as f32,
+            totals[side].1 as f32 / games as f32
+        );
+    }
+    match totals[0].1.cmp(&totals[1].1) {
+        std::cmp::Ordering::Greater => println!("winner: {}", names[0]),
+        std::cmp::Ordering::Less => println!("winner: {}", names[1]),
+        std::cmp::Ordering::Equal => println!("draw"),
+    }
+}
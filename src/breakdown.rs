@@ -0,0 +1,146 @@
+use macroquad::prelude::*;
+use crate::motion;
+use crate::camera;
+use crate::touch;
+
+// Seconds between each line of the breakdown starting to count up
+const LINE_INTERVAL: f32 = 0.7;
+const COUNT_DURATION: f32 = 0.5;
+// Levels finished faster than this earn a time bonus
+const PAR_TIME: f32 = 60.0;
+const SLAM_DURATION: f32 = 0.25;
+
+// Works out the bonus lines awarded for finishing a level
+pub fn calculate_level_completion_score(
+    level_points: u32,
+    level_time: f32,
+    length: usize,
+    best_streak: u32,
+) -> Vec<(&'static str, u32)> {
+    let time_bonus = ((PAR_TIME - level_time).max(0.0) * 5.0) as u32;
+    let length_bonus = length as u32 * 2;
+    let combo_bonus = if best_streak >= 2 { best_streak * 15 } else { 0 };
+
+    vec![
+        ("Base Score", level_points),
+        ("Time Bonus", time_bonus),
+        ("Length Bonus", length_bonus),
+        ("Combo Bonus", combo_bonus),
+    ]
+}
+
+// Animated tally shown between levels
+pub struct LevelBreakdown {
+    pub showing: bool,
+    level: usize,
+    lines: Vec<(&'static str, u32)>,
+    timer: f32,
+    slammed: bool,
+}
+
+impl LevelBreakdown {
+    pub fn new() -> Self {
+        Self {
+            showing: false,
+            level: 0,
+            lines: Vec::new(),
+            timer: 0.0,
+            slammed: false,
+        }
+    }
+
+    // Returns the bonus points on top of the base score
+    pub fn start(&mut self, level: usize, lines: Vec<(&'static str, u32)>) -> u32 {
+        self.showing = true;
+        self.level = level;
+        self.lines = lines;
+        self.timer = 0.0;
+        self.slammed = false;
+        self.lines.iter().skip(1).map(|(_, value)| value).sum()
+    }
+
+    fn total(&self) -> u32 {
+        self.lines.iter().map(|(_, value)| value).sum()
+    }
+
+    // Time at which the total lands, once every line has finished counting
+    fn slam_time(&self) -> f32 {
+        self.lines.len() as f32 * LINE_INTERVAL + COUNT_DURATION
+    }
+
+    pub fn update(&mut self, delta_time: f32) {
+        if !self.showing {
+            return;
+        }
+
+        self.timer += delta_time;
+        if !self.slammed && self.timer >= self.slam_time() {
+            self.slammed = true;
+            camera::add_trauma(0.35);
+        }
+
+        if is_key_pressed(KeyCode::Space) || touch::tapped() {
+            if self.slammed {
+                self.showing = false;
+            } else {
+                // First press skips straight to the total
+                self.timer = self.slam_time();
+            }
+        }
+    }
+
+    pub fn draw(&self) {
+        if !self.showing {
+            return;
+        }
+
+        draw_rectangle(0.0, 0.0, screen_width(), screen_height(), Color::new(0.0, 0.0, 0.0, 0.85));
+
+        let heading = format!("LEVEL {} COMPLETE", self.level);
+        let heading_width = measure_text(&heading, None, 48, 1.0).width;
+        draw_text(&heading, (screen_width() - heading_width) / 2.0, 140.0, 48.0, GREEN);
+
+        let left = screen_width() / 2.0 - 200.0;
+        let right = screen_width() / 2.0 + 200.0;
+
+        for (i, (label, value)) in self.lines.iter().enumerate() {
+            let started = i as f32 * LINE_INTERVAL;
+            if self.timer < started {
+                break;
+            }
+
+            let progress = ((self.timer - started) / COUNT_DURATION).min(1.0);
+            let shown = (*value as f32 * progress) as u32;
+            let y = 220.0 + i as f32 * 45.0;
+            // Numbers flicker brighter while they're still ticking up
+            let color = if progress < 1.0 { YELLOW } else { WHITE };
+
+            draw_text(label, left, y, 32.0, LIGHTGRAY);
+            let number = format!("{}", shown);
+            let number_width = measure_text(&number, None, 32, 1.0).width;
+            draw_text(&number, right - number_width, y, 32.0, color);
+        }
+
+        if self.slammed {
+            // Total drops in oversized and snaps down to its resting size
+            let since = self.timer - self.slam_time();
+            let scale = 1.0 + (1.0 - (since / SLAM_DURATION).min(1.0)) * 1.5;
+            let size = 48.0 * scale;
+            let total = format!("TOTAL  {}", self.total());
+            let total_width = measure_text(&total, None, size as u16, 1.0).width;
+            let y = 240.0 + self.lines.len() as f32 * 45.0 + 30.0;
+            draw_text(&total, (screen_width() - total_width) / 2.0, y, size, GOLD);
+
+            let prompt = "Press SPACE to continue";
+            let prompt_width = measure_text(prompt, None, 26, 1.0).width;
+            let pulse = motion::pulse(4.0, 0.3, 0.7);
+            draw_text(
+                prompt,
+                (screen_width() - prompt_width) / 2.0,
+                screen_height() - 60.0,
+                26.0,
+                Color::new(0.8, 0.8, 0.8, pulse),
+            );
+        }
+    }
+}
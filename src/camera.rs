@@ -0,0 +1,210 @@
+use macroquad::prelude::*;
+use lazy_static::lazy_static;
+use std::sync::Mutex;
+use crate::grid::{get_offset, CELL_SIZE, GRID_HEIGHT, GRID_WIDTH};
+use crate::motion::reduced_motion;
+use crate::snake::Segment;
+
+// Largest offset in pixels at full trauma
+const MAX_SHAKE: f32 = 14.0;
+// Trauma lost per second
+const TRAUMA_DECAY: f32 = 1.5;
+
+// Trauma-based shake: events add trauma, the shake strength is trauma squared
+// so small bumps stay subtle and big hits really kick
+pub struct CameraShake {
+    pub trauma: f32,
+    time: f32,
+}
+
+lazy_static! {
+    static ref CAMERA_SHAKE: Mutex<CameraShake> = Mutex::new(CameraShake { trauma: 0.0, time: 0.0 });
+}
+
+pub fn add_trauma(amount: f32) {
+    let mut shake = CAMERA_SHAKE.lock().unwrap();
+    shake.trauma = (shake.trauma + amount).min(1.0);
+}
+
+pub fn update_shake(delta_time: f32) {
+    let mut shake = CAMERA_SHAKE.lock().unwrap();
+    // Drop any shake already in progress the moment reduced motion is switched on
+    shake.trauma = if reduced_motion() { 0.0 } else { (shake.trauma - TRAUMA_DECAY * delta_time).max(0.0) };
+    shake.time += delta_time;
+}
+
+// Uses smooth waves rather than random numbers so every draw call in a frame
+// gets the same offset
+pub fn shake_offset() -> Vec2 {
+    let shake = CAMERA_SHAKE.lock().unwrap();
+    let strength = shake.trauma * shake.trauma * MAX_SHAKE;
+    vec2(
+        (shake.time * 37.0).sin() * strength,
+        (shake.time * 53.0 + 1.3).cos() * strength,
+    )
+}
+
+// Zoom on a brand new snake in follow mode, easing out to the whole board as it grows
+const FOLLOW_ZOOM: f32 = 2.5;
+// Length at which the automatic zoom has pulled all the way out
+const FULL_VIEW_LENGTH: f32 = 60.0;
+// How quickly zoom and position catch up, per second
+const FOLLOW_SPEED: f32 = 3.0;
+// Bounds for the player's own zoom adjustment, multiplied onto the automatic zoom
+pub const MIN_ZOOM_PREFERENCE: f32 = 0.5;
+pub const MAX_ZOOM_PREFERENCE: f32 = 2.0;
+pub const ZOOM_PREFERENCE_STEP: f32 = 0.25;
+// Closest the paused free camera can zoom in
+const MAX_LOOK_ZOOM: f32 = 4.0;
+// Screen pixels per second the free camera pans with the keys
+const LOOK_PAN_SPEED: f32 = 600.0;
+// Zoom change per notch of the mouse wheel
+const LOOK_ZOOM_STEP: f32 = 1.15;
+// Strip kept clear above the board for the HUD, the same gap grid::get_offset leaves
+const HUD_HEIGHT: f32 = 40.0;
+
+// Zoom at which the whole board and the HUD strip above it fit in the window
+fn fit_zoom() -> f32 {
+    let width = GRID_WIDTH as f32 * CELL_SIZE;
+    let height = GRID_HEIGHT as f32 * CELL_SIZE + HUD_HEIGHT;
+    (screen_width() / width).min(screen_height() / height).min(1.0)
+}
+
+// False when the window is too small to show the board at full size
+pub fn level_fits() -> bool {
+    fit_zoom() >= 1.0
+}
+
+// Optional camera that tracks the snake's head, drawing the board zoomed in around it
+pub struct FollowCamera {
+    pub zoom: f32,
+    center: Option<Vec2>,
+    gameplay_view: Option<(f32, Option<Vec2>)>, // Zoom and centre to go back to after looking around
+    drag_from: Option<Vec2>,
+}
+
+impl FollowCamera {
+    pub fn new() -> Self {
+        Self {
+            zoom: 1.0,
+            center: None,
+            gameplay_view: None,
+            drag_from: None,
+        }
+    }
+
+    pub fn is_looking(&self) -> bool {
+        self.gameplay_view.is_some()
+    }
+
+    // Free camera for the pause screen: arrow keys or WASD and dragging with the mouse pan,
+    // the mouse wheel zooms. The view it started from comes back as soon as gameplay
+    // moves the camera again.
+    pub fn look_around(&mut self, delta_time: f32) {
+        if self.gameplay_view.is_none() {
+            self.gameplay_view = Some((self.zoom, self.center));
+        }
+        let mut center = self.center.unwrap_or(vec2(screen_width(), screen_height()) / 2.0);
+
+        let held = |keys: [KeyCode; 2]| if keys.iter().any(|key| is_key_down(*key)) { 1.0 } else { 0.0 };
+        let direction = vec2(
+            held([KeyCode::Right, KeyCode::D]) - held([KeyCode::Left, KeyCode::A]),
+            held([KeyCode::Down, KeyCode::S]) - held([KeyCode::Up, KeyCode::W]),
+        );
+        center += direction * LOOK_PAN_SPEED * delta_time / self.zoom;
+
+        let mouse = Vec2::from(mouse_position());
+        if is_mouse_button_down(MouseButton::Left) {
+            if let Some(from) = self.drag_from {
+                center -= (mouse - from) / self.zoom;
+            }
+            self.drag_from = Some(mouse);
+        } else {
+            self.drag_from = None;
+        }
+
+        let (_, wheel) = mouse_wheel();
+        if wheel > 0.0 {
+            self.zoom = (self.zoom * LOOK_ZOOM_STEP).min(MAX_LOOK_ZOOM);
+        } else if wheel < 0.0 {
+            self.zoom = (self.zoom / LOOK_ZOOM_STEP).max(fit_zoom());
+        }
+
+        self.center = Some(self.clamp_to_level(center));
+    }
+
+    // Puts the camera back where gameplay left it, if it was looking around
+    pub fn snap_back(&mut self) {
+        if let Some((zoom, center)) = self.gameplay_view.take() {
+            self.zoom = zoom;
+            self.center = center;
+        }
+        self.drag_from = None;
+    }
+
+    // `preference` is the player's saved zoom adjustment
+    pub fn update(&mut self, delta_time: f32, head: Segment, length: usize, preference: f32) {
+        self.snap_back();
+        let grown = (length as f32 / FULL_VIEW_LENGTH).min(1.0);
+        let automatic = FOLLOW_ZOOM - (FOLLOW_ZOOM - 1.0) * grown;
+        // Never zoom out past the point where the whole level is already on screen
+        let target_zoom = (automatic * preference).max(fit_zoom());
+
+        let blend = (FOLLOW_SPEED * delta_time).min(1.0);
+        self.zoom += (target_zoom - self.zoom) * blend;
+
+        let offset = get_offset();
+        let target = vec2(
+            offset.x + (head.x as f32 + 0.5) * CELL_SIZE,
+            offset.y + (head.y as f32 + 0.5) * CELL_SIZE,
+        );
+        let center = match self.center {
+            Some(center) => center + (target - center) * blend,
+            None => target,
+        };
+        self.center = Some(self.clamp_to_level(center));
+    }
+
+    // The fixed view: the whole board shrunk into the window, centred below the HUD
+    pub fn fit_level(&mut self) {
+        self.snap_back();
+        self.zoom = fit_zoom();
+        self.center = Some(vec2(screen_width() / 2.0, screen_height() / 2.0 + HUD_HEIGHT / 2.0));
+    }
+
+    // Keeps the view from showing past the edges of the board, centring it when
+    // it's zoomed out far enough to see past both edges
+    fn clamp_to_level(&self, center: Vec2) -> Vec2 {
+        let offset = get_offset();
+        let left = offset.x.min(0.0);
+        let top = (offset.y - HUD_HEIGHT).min(0.0);
+        let right = (offset.x + GRID_WIDTH as f32 * CELL_SIZE).max(screen_width());
+        let bottom = (offset.y + GRID_HEIGHT as f32 * CELL_SIZE).max(screen_height());
+
+        let half = vec2(screen_width(), screen_height()) / self.zoom / 2.0;
+        let clamp = |value: f32, low: f32, high: f32, half: f32| {
+            if high - low <= half * 2.0 {
+                (low + high) / 2.0
+            } else {
+                value.clamp(low + half, high - half)
+            }
+        };
+        vec2(clamp(center.x, left, right, half.x), clamp(center.y, top, bottom, half.y))
+    }
+
+    // Everything drawn until `end` is in board space and gets zoomed
+    pub fn begin(&self) {
+        let center = self.center.unwrap_or(vec2(screen_width(), screen_height()) / 2.0);
+        let size = vec2(screen_width(), screen_height()) / self.zoom;
+        set_camera(&Camera2D::from_display_rect(Rect::new(
+            center.x - size.x / 2.0,
+            center.y - size.y / 2.0,
+            size.x,
+            size.y,
+        )));
+    }
+
+    pub fn end(&self) {
+        set_default_camera();
+    }
+}
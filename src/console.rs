@@ -0,0 +1,173 @@
+// Developer console, opened with ` during a game. Commands go through the same hooks
+// as the test tools (see COMMANDS in src/hooks.rs) and take effect once it's closed.
+use macroquad::prelude::*;
+use std::collections::VecDeque;
+use crate::arena::Arena;
+use crate::food::Food;
+use crate::level::LevelTracker;
+use crate::objectives::ObjectiveTracker;
+use crate::hooks::{self, COMMANDS};
+use crate::snake::Snake;
+
+const MAX_OUTPUT: usize = 10;
+const MAX_HISTORY: usize = 50;
+const LINE_HEIGHT: f32 = 20.0;
+
+pub struct Console {
+    pub open: bool,
+    input: String,
+    history: Vec<String>,
+    browsing: Option<usize>, // Index into history while UP/DOWN are stepping through it
+    output: VecDeque<String>,
+}
+
+impl Console {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            input: String::new(),
+            history: Vec::new(),
+            browsing: None,
+            output: VecDeque::from(["Type help for a list of commands".to_string()]),
+        }
+    }
+
+    pub fn update(&mut self) {
+        if is_key_pressed(KeyCode::GraveAccent) {
+            self.open = !self.open;
+            // Drop the backquote and whatever was typed into the game
+            while get_char_pressed().is_some() {}
+            return;
+        }
+        if !self.open {
+            return;
+        }
+
+        while let Some(c) = get_char_pressed() {
+            if !c.is_control() && c != '`' {
+                self.input.push(c);
+            }
+        }
+        if is_key_pressed(KeyCode::Backspace) {
+            self.input.pop();
+        }
+
+        if is_key_pressed(KeyCode::Escape) {
+            self.open = false;
+        } else if is_key_pressed(KeyCode::Enter) {
+            let line = std::mem::take(&mut self.input);
+            self.run(line.trim());
+        } else if is_key_pressed(KeyCode::Tab) {
+            self.complete();
+        } else if is_key_pressed(KeyCode::Up) && !self.history.is_empty() {
+            let index = self.browsing.map_or(self.history.len() - 1, |index| index.saturating_sub(1));
+            self.browsing = Some(index);
+            self.input = self.history[index].clone();
+        } else if is_key_pressed(KeyCode::Down) {
+            if let Some(index) = self.browsing {
+                if index + 1 < self.history.len() {
+                    self.browsing = Some(index + 1);
+                    self.input = self.history[index + 1].clone();
+                } else {
+                    self.browsing = None;
+                    self.input.clear();
+                }
+            }
+        }
+    }
+
+    // Applies the commands sent since the last tick, from here or from the test hooks
+    pub fn apply_pending(
+        &self,
+        snake: &mut Snake,
+        food: &mut Food,
+        level_tracker: &mut LevelTracker,
+        arena: &mut Arena,
+        objectives: &mut ObjectiveTracker,
+        score: &mut u32,
+    ) {
+        hooks::apply_pending(snake, food, level_tracker, arena, objectives, score);
+    }
+
+    fn run(&mut self, line: &str) {
+        if line.is_empty() {
+            return;
+        }
+        self.browsing = None;
+        if self.history.last().is_none_or(|last| last != line) {
+            self.history.push(line.to_string());
+            if self.history.len() > MAX_HISTORY {
+                self.history.remove(0);
+            }
+        }
+        self.print(format!("> {}", line));
+
+        if line == "help" {
+            for command in &COMMANDS {
+                self.print(format!("{} - {}", command.usage, command.help));
+            }
+            return;
+        }
+        match hooks::parse(line) {
+            Some(hook) => {
+                hooks::send(hook);
+                self.print("OK, applied when the console closes".to_string());
+            }
+            None => {
+                let name = line.split_whitespace().next().unwrap_or_default();
+                match COMMANDS.iter().find(|command| command.names.contains(&name)) {
+                    Some(command) => self.print(format!("Usage: {}", command.usage)),
+                    None => self.print(format!("Unknown command {}, try help", name)),
+                }
+            }
+        }
+    }
+
+    // TAB finishes a command name, or lists the choices when more than one fits
+    fn complete(&mut self) {
+        if self.input.contains(' ') {
+            return;
+        }
+        let matches: Vec<&str> = COMMANDS
+            .iter()
+            .map(|command| command.names[0])
+            .chain(["help"])
+            .filter(|name| name.starts_with(self.input.as_str()))
+            .collect();
+        match matches.as_slice() {
+            [] => {}
+            [name] => self.input = format!("{} ", name),
+            _ => {
+                // Fill in as far as all of them agree
+                let common = matches[1..].iter().fold(matches[0].len(), |length, name| {
+                    matches[0].bytes().zip(name.bytes()).take(length).take_while(|(a, b)| a == b).count()
+                });
+                self.input = matches[0][..common].to_string();
+                self.print(matches.join("  "));
+            }
+        }
+    }
+
+    fn print(&mut self, line: String) {
+        self.output.push_back(line);
+        if self.output.len() > MAX_OUTPUT {
+            self.output.pop_front();
+        }
+    }
+
+    pub fn draw(&self) {
+        if !self.open {
+            return;
+        }
+
+        let height = (MAX_OUTPUT + 2) as f32 * LINE_HEIGHT;
+        draw_rectangle(0.0, 0.0, screen_width(), height, Color::new(0.0, 0.0, 0.0, 0.85));
+        draw_line(0.0, height, screen_width(), height, 2.0, GREEN);
+        for (i, line) in self.output.iter().enumerate() {
+            draw_text(line, 12.0, (i + 1) as f32 * LINE_HEIGHT, 18.0, LIGHTGRAY);
+        }
+        let cursor = if (get_time() * 2.0).fract() < 0.5 { "_" } else { "" };
+        let prompt = format!("> {}{}", self.input, cursor);
+        draw_text(&prompt, 12.0, height - LINE_HEIGHT / 2.0, 20.0, GREEN);
+    }
+}
@@ -0,0 +1,76 @@
+use macroquad::prelude::*;
+use crate::summary::RunSummary;
+
+// Continues left in the current campaign run, offered on the crash screen, and
+// the summary shown once the run is over for good
+pub struct Continues {
+    pub left: u32,
+    used: u32,
+    summary: Option<RunSummary>,
+}
+
+impl Continues {
+    pub fn new() -> Self {
+        Self {
+            left: 0,
+            used: 0,
+            summary: None,
+        }
+    }
+
+    // A fresh run, or one picked up from the checkpoint in the save file
+    pub fn start(&mut self, left: u32) {
+        self.left = left;
+        self.used = 0;
+    }
+
+    // Returns true if the player spends a continue on the crash screen
+    pub fn take(&mut self) -> bool {
+        if self.left == 0 || !is_key_pressed(KeyCode::C) {
+            return false;
+        }
+        self.left -= 1;
+        self.used += 1;
+        true
+    }
+
+    pub fn finish(&mut self, summary: RunSummary) {
+        self.summary = Some(summary);
+    }
+
+    pub fn has_summary(&self) -> bool {
+        self.summary.is_some()
+    }
+
+    pub fn update_summary(&mut self) {
+        if is_key_pressed(KeyCode::Space) || is_key_pressed(KeyCode::Enter) || is_key_pressed(KeyCode::Escape) {
+            self.summary = None;
+        }
+    }
+
+    // Extra line under the crash screen's own prompt
+    pub fn draw_offer(&self, level: usize) {
+        let (text, color) = if self.left > 0 {
+            (t!("continue-offer", level = level, left = self.left), YELLOW)
+        } else {
+            (t!("continue-none-left"), GRAY)
+        };
+        draw_centered(&text, screen_height() / 2.0 + 60.0, 24.0, color);
+    }
+
+    pub fn draw_summary(&self) {
+        let Some(summary) = &self.summary else {
+            return;
+        };
+        draw_rectangle(0.0, 0.0, screen_width(), screen_height(), Color::new(0.0, 0.0, 0.0, 0.85));
+        summary.draw_panel(&t!("game-over-title"));
+        let center_y = screen_height() / 2.0;
+        draw_centered(&t!("game-over-continues", used = self.used), center_y + 180.0, 24.0, YELLOW);
+        draw_centered(&t!("game-over-dismiss"), center_y + 215.0, 20.0, GRAY);
+    }
+}
+
+fn draw_centered(text: &str, y: f32, size: f32, color: Color) {
+    let width = measure_text(text, None, size as u16, 1.0).width;
+    draw_text(text, (screen_width() - width) / 2.0, y, size, color);
+}
@@ -0,0 +1,86 @@
+use macroquad::prelude::KeyCode;
+
+// Which keys steer the snake in the four-direction control scheme
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum KeyPreset {
+    ArrowsAndWasd,
+    Arrows,
+    Wasd,
+    Vim,
+}
+
+impl KeyPreset {
+    // Up, down, left and right, for each set of keys the preset listens to
+    pub fn bindings(&self) -> &'static [[KeyCode; 4]] {
+        const ARROWS: [KeyCode; 4] = [KeyCode::Up, KeyCode::Down, KeyCode::Left, KeyCode::Right];
+        const WASD: [KeyCode; 4] = [KeyCode::W, KeyCode::S, KeyCode::A, KeyCode::D];
+        const VIM: [KeyCode; 4] = [KeyCode::K, KeyCode::J, KeyCode::H, KeyCode::L];
+        match self {
+            KeyPreset::ArrowsAndWasd => &[ARROWS, WASD],
+            KeyPreset::Arrows => &[ARROWS],
+            KeyPreset::Wasd => &[WASD],
+            KeyPreset::Vim => &[VIM],
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ControlScheme {
+    Keys(KeyPreset),
+    // Single button accessibility mode: each press turns the snake, alternating
+    // left and right relative to its heading. With auto-straighten the snake
+    // sidesteps one cell and then carries on in its original direction.
+    OneButton { auto_straighten: bool },
+}
+
+impl ControlScheme {
+    pub fn next(&self) -> ControlScheme {
+        match self {
+            ControlScheme::Keys(KeyPreset::ArrowsAndWasd) => ControlScheme::Keys(KeyPreset::Arrows),
+            ControlScheme::Keys(KeyPreset::Arrows) => ControlScheme::Keys(KeyPreset::Wasd),
+            ControlScheme::Keys(KeyPreset::Wasd) => ControlScheme::Keys(KeyPreset::Vim),
+            ControlScheme::Keys(KeyPreset::Vim) => ControlScheme::OneButton { auto_straighten: false },
+            ControlScheme::OneButton { auto_straighten: false } => ControlScheme::OneButton { auto_straighten: true },
+            ControlScheme::OneButton { auto_straighten: true } => ControlScheme::Keys(KeyPreset::ArrowsAndWasd),
+        }
+    }
+
+    // Lines for the controls guide
+    pub fn help_lines(&self) -> &'static [&'static str] {
+        match self {
+            ControlScheme::Keys(KeyPreset::ArrowsAndWasd) => &["Arrow Keys or WASD - Steer your snake"],
+            ControlScheme::Keys(KeyPreset::Arrows) => &["Arrow Keys - Steer your snake"],
+            ControlScheme::Keys(KeyPreset::Wasd) => &["WASD - Steer your snake"],
+            ControlScheme::Keys(KeyPreset::Vim) => &["H J K L - Steer your snake (left, down, up, right)"],
+            ControlScheme::OneButton { auto_straighten: false } => {
+                &["SPACE - Turn, alternating left and right"]
+            }
+            ControlScheme::OneButton { auto_straighten: true } => {
+                &["SPACE - Sidestep, alternating left and right"]
+            }
+        }
+    }
+
+    // "arrows" is kept for arrows + WASD so saves from before the presets get both
+    pub fn save_key(&self) -> &'static str {
+        match self {
+            ControlScheme::Keys(KeyPreset::ArrowsAndWasd) => "arrows",
+            ControlScheme::Keys(KeyPreset::Arrows) => "arrows_only",
+            ControlScheme::Keys(KeyPreset::Wasd) => "wasd",
+            ControlScheme::Keys(KeyPreset::Vim) => "vim",
+            ControlScheme::OneButton { auto_straighten: false } => "one_button",
+            ControlScheme::OneButton { auto_straighten: true } => "one_button_straighten",
+        }
+    }
+
+    pub fn from_save_key(key: &str) -> ControlScheme {
+        match key {
+            "arrows_only" => ControlScheme::Keys(KeyPreset::Arrows),
+            "wasd" => ControlScheme::Keys(KeyPreset::Wasd),
+            "vim" => ControlScheme::Keys(KeyPreset::Vim),
+            "one_button" => ControlScheme::OneButton { auto_straighten: false },
+            "one_button_straighten" => ControlScheme::OneButton { auto_straighten: true },
+            _ => ControlScheme::Keys(KeyPreset::ArrowsAndWasd),
+        }
+    }
+}
@@ -1,7 +1,7 @@
 use macroquad::prelude::*;
 use crate::grid::{GRID_WIDTH, GRID_HEIGHT, CELL_SIZE, get_offset};
 use crate::snake::{Snake, Segment, Direction};
-use ::rand::thread_rng;
+use crate::rng;
 use ::rand::prelude::Rng;
 
 pub struct CpuSnake {
@@ -11,11 +11,12 @@ pub struct CpuSnake {
     pub move_delay: f32,
     pub color_head: Color,
     pub color_body: Color,
+    pub moves: u32, // Cells travelled, shown when spectating
 }
 
 impl CpuSnake {
     pub fn new() -> Self {
-        let mut rng = thread_rng();
+        let mut rng = rng::gameplay();
         let x = rng.gen_range(0..GRID_WIDTH);
         let y = rng.gen_range(0..GRID_HEIGHT);
 
@@ -26,11 +27,12 @@ impl CpuSnake {
             move_delay: 0.25,
             color_head: RED,
             color_body: DARKGRAY,
+            moves: 0,
         }
     }
 
     pub fn new_with_colors(head_color: Color, body_color: Color) -> Self {
-        let mut rng = thread_rng();
+        let mut rng = rng::gameplay();
         let x = rng.gen_range(0..GRID_WIDTH);
         let y = rng.gen_range(0..GRID_HEIGHT);
 
@@ -41,11 +43,12 @@ impl CpuSnake {
             move_delay: 0.25,
             color_head: head_color,
             color_body: body_color,
+            moves: 0,
         }
     }
 
-    pub fn update(&mut self, level: usize) {
-        self.move_timer += get_frame_time();
+    pub fn update(&mut self, delta_time: f32, level: usize) {
+        self.move_timer += delta_time;
 
         // Increase speed as level increases
         self.move_delay = (0.25 - level as f32 * 0.01).max(0.05);
@@ -53,7 +56,7 @@ impl CpuSnake {
         if self.move_timer >= self.move_delay {
             self.move_timer = 0.0;
 
-            let mut rng = thread_rng();
+            let mut rng = rng::gameplay();
             let mut new_dir = self.dir;
 
             if rng.gen_bool(0.3) {
@@ -78,6 +81,7 @@ impl CpuSnake {
             if new_head.x >= 0 && new_head.x < GRID_WIDTH && new_head.y >= 0 && new_head.y < GRID_HEIGHT {
                 self.body.insert(0, new_head);
                 self.body.pop();
+                self.moves += 1;
             }
         }
     }
@@ -118,7 +122,7 @@ impl CpuSnakeManager {
         }
     }
 
-    pub fn update(&mut self, level: usize) {
+    pub fn update(&mut self, delta_time: f32, level: usize) {
         // Check if we need to add more snakes
         if level != self.current_level {
             self.current_level = level;
@@ -127,7 +131,7 @@ impl CpuSnakeManager {
 
         // Update all snakes
         for snake in &mut self.snakes {
-            snake.update(level);
+            snake.update(delta_time, level);
         }
     }
 
@@ -0,0 +1,95 @@
+// The roll is only compiled in with the `cutscenes` feature. Without it the boss
+// level ends with the usual score breakdown, like every other level.
+#[cfg(feature = "cutscenes")]
+use macroquad::prelude::*;
+#[cfg(feature = "cutscenes")]
+use crate::motion;
+
+#[cfg(feature = "cutscenes")]
+const SCROLL_SPEED: f32 = 40.0;
+
+#[cfg(feature = "cutscenes")]
+const CREDIT_LINES: [&str; 9] = [
+    "VYPERTRON DEFEATED",
+    "",
+    "Game by Myrmecology",
+    "Built with Rust and Macroquad",
+    "Soundtrack made in BeepBox",
+    "",
+    "Thanks for playing!",
+    "",
+    "The grid goes on forever...",
+];
+
+// Ending shown after the level 10 boss goes down
+pub struct Credits {
+    pub showing: bool,
+    #[cfg(feature = "cutscenes")]
+    scroll: f32,
+}
+
+#[cfg(feature = "cutscenes")]
+impl Credits {
+    pub fn new() -> Self {
+        Self {
+            showing: false,
+            scroll: 0.0,
+        }
+    }
+
+    pub fn start(&mut self) {
+        self.showing = true;
+        self.scroll = 0.0;
+    }
+
+    pub fn update(&mut self, delta_time: f32) {
+        if self.showing {
+            self.scroll += SCROLL_SPEED * delta_time;
+        }
+    }
+
+    pub fn draw(&self) {
+        if !self.showing {
+            return;
+        }
+
+        draw_rectangle(0.0, 0.0, screen_width(), screen_height(), Color::new(0.0, 0.0, 0.0, 0.85));
+
+        // Roll up from the bottom and settle in the middle of the screen
+        let settled_y = screen_height() / 2.0 - CREDIT_LINES.len() as f32 * 20.0;
+        let top = (screen_height() - self.scroll).max(settled_y);
+
+        for (i, line) in CREDIT_LINES.iter().enumerate() {
+            let size = if i == 0 { 56.0 } else { 30.0 };
+            let width = measure_text(line, None, size as u16, 1.0).width;
+            let color = if i == 0 { RED } else { WHITE };
+            draw_text(line, (screen_width() - width) / 2.0, top + i as f32 * 40.0, size, color);
+        }
+
+        if top <= settled_y {
+            let prompt = "Press SPACE to continue to level 11";
+            let width = measure_text(prompt, None, 26, 1.0).width;
+            let pulse = motion::pulse(4.0, 0.3, 0.7);
+            draw_text(
+                prompt,
+                (screen_width() - width) / 2.0,
+                screen_height() - 60.0,
+                26.0,
+                Color::new(0.8, 0.8, 0.8, pulse),
+            );
+        }
+    }
+}
+
+#[cfg(not(feature = "cutscenes"))]
+impl Credits {
+    pub fn new() -> Self {
+        Self { showing: false }
+    }
+
+    pub fn start(&mut self) {}
+
+    pub fn update(&mut self, _delta_time: f32) {}
+
+    pub fn draw(&self) {}
+}
@@ -0,0 +1,133 @@
+use macroquad::audio::{load_sound_from_bytes, Sound};
+use std::f32::consts::{FRAC_PI_2, TAU};
+use crate::grid::{is_within_grid, wrap_position, GRID_HEIGHT, GRID_WIDTH};
+use crate::mixer::play_sfx;
+use crate::snake::{Segment, Snake};
+use crate::tension::encode_stereo_wav;
+
+const SAMPLE_RATE: u32 = 22050;
+// Food pings come in this many positions from hard left to hard right...
+const PAN_STEPS: usize = 5;
+// ...and this many pitches, higher for food above the snake
+const PITCH_STEPS: usize = 3;
+// Cells ahead to start warning about a wall or obstacle
+const WARNING_DISTANCE: usize = 3;
+const MENU_NOTES: usize = 6;
+
+// A short tone with its volume split between the ears, `pan` running from -1 (left) to 1 (right)
+fn tone_wav(frequency: f32, length: f32, pan: f32) -> Vec<u8> {
+    let count = (SAMPLE_RATE as f32 * length) as usize;
+    // Equal-power panning keeps the loudness steady across the positions
+    let angle = (pan + 1.0) / 2.0 * FRAC_PI_2;
+    let (left_gain, right_gain) = (angle.cos(), angle.sin());
+
+    let mut samples = Vec::with_capacity(count * 2);
+    for i in 0..count {
+        let t = i as f32 / SAMPLE_RATE as f32;
+        let envelope = (t / 0.004).min(1.0) * (-t * 12.0).exp();
+        let value = (t * frequency * TAU).sin() * envelope * i16::MAX as f32 * 0.6;
+        samples.push((value * left_gain) as i16);
+        samples.push((value * right_gain) as i16);
+    }
+    encode_stereo_wav(SAMPLE_RATE, &samples)
+}
+
+async fn load_tone(frequency: f32, length: f32, pan: f32) -> Option<Sound> {
+    match load_sound_from_bytes(&tone_wav(frequency, length, pan)).await {
+        Ok(sound) => Some(sound),
+        Err(e) => {
+            println!("Warning: Could not create audio cue: {:?}", e);
+            None
+        }
+    }
+}
+
+// Sounds that describe the board for players who can't rely on seeing it: where new
+// food is, walls coming up ahead, and which menu button has focus
+pub struct AudioCues {
+    food_pings: Vec<Option<Sound>>, // Indexed by pitch * PAN_STEPS + pan
+    wall_tones: Vec<Option<Sound>>, // Nearest wall last
+    menu_notes: Vec<Option<Sound>>,
+    last_food: Option<Segment>,
+    last_head: Option<Segment>,
+}
+
+impl AudioCues {
+    pub async fn load() -> Self {
+        let mut food_pings = Vec::with_capacity(PAN_STEPS * PITCH_STEPS);
+        for pitch in 0..PITCH_STEPS {
+            for pan in 0..PAN_STEPS {
+                let pan = pan as f32 / (PAN_STEPS - 1) as f32 * 2.0 - 1.0;
+                food_pings.push(load_tone(880.0 * 1.25f32.powi(pitch as i32), 0.25, pan).await);
+            }
+        }
+
+        let mut wall_tones = Vec::with_capacity(WARNING_DISTANCE);
+        for step in 0..WARNING_DISTANCE {
+            wall_tones.push(load_tone(220.0 * 1.5f32.powi(step as i32), 0.08, 0.0).await);
+        }
+
+        // A rising major pentatonic run, one note per menu position
+        let mut menu_notes = Vec::with_capacity(MENU_NOTES);
+        for semitones in [0, 2, 4, 7, 9, 12] {
+            menu_notes.push(load_tone(523.25 * 2f32.powf(semitones as f32 / 12.0), 0.12, 0.0).await);
+        }
+
+        Self {
+            food_pings,
+            wall_tones,
+            menu_notes,
+            last_food: None,
+            last_head: None,
+        }
+    }
+
+    // Call each frame of play
+    pub fn update(&mut self, snake: &Snake, food: Segment, is_blocked: impl Fn(Segment) -> bool) {
+        let head = snake.head();
+
+        if self.last_food != Some(food) {
+            self.last_food = Some(food);
+            let pan = ((food.x - head.x) as f32 / GRID_WIDTH as f32 * 2.0).clamp(-1.0, 1.0);
+            let height = ((head.y - food.y) as f32 / GRID_HEIGHT as f32 * 2.0).clamp(-1.0, 1.0);
+            let pan_index = ((pan + 1.0) / 2.0 * (PAN_STEPS - 1) as f32).round() as usize;
+            let pitch_index = ((height + 1.0) / 2.0 * (PITCH_STEPS - 1) as f32).round() as usize;
+            play(&self.food_pings[pitch_index * PAN_STEPS + pan_index]);
+        }
+
+        // One warning per move, rising in pitch as the wall gets closer
+        if self.last_head != Some(head) {
+            self.last_head = Some(head);
+            if let Some(distance) = distance_ahead(snake, &is_blocked) {
+                play(&self.wall_tones[WARNING_DISTANCE - distance]);
+            }
+        }
+    }
+
+    pub fn focus(&self, index: usize) {
+        play(&self.menu_notes[index % MENU_NOTES]);
+    }
+}
+
+fn play(sound: &Option<Sound>) {
+    if let Some(sound) = sound {
+        play_sfx(sound, 0.8);
+    }
+}
+
+// Cells until the snake would hit something if it kept going straight, if that's close
+fn distance_ahead(snake: &Snake, is_blocked: &impl Fn(Segment) -> bool) -> Option<usize> {
+    let (dx, dy) = snake.dir.delta();
+    let mut cell = snake.head();
+    for distance in 1..=WARNING_DISTANCE {
+        let (mut x, mut y) = (cell.x + dx, cell.y + dy);
+        if snake.wrap {
+            (x, y) = wrap_position(x, y);
+        }
+        cell = Segment { x, y };
+        if !is_within_grid(x, y) || is_blocked(cell) || snake.is_at(cell) {
+            return Some(distance);
+        }
+    }
+    None
+}
@@ -0,0 +1,57 @@
+use macroquad::prelude::*;
+use crate::grid::{is_within_grid, wrap_position};
+use crate::snake::{Segment, Snake};
+
+// Seconds the edge flash takes to fade
+const FLASH_TIME: f32 = 0.4;
+
+// A quick warning when the very next move would crash: a brief flash around the edges
+// of the screen, and a tick from the caller. Checked once per move.
+pub struct DangerWarning {
+    flash: f32, // 0..1
+    last_head: Option<Segment>,
+}
+
+impl DangerWarning {
+    pub fn new() -> Self {
+        Self {
+            flash: 0.0,
+            last_head: None,
+        }
+    }
+
+    // True on the move the snake comes within one cell of a crash
+    pub fn update(&mut self, delta_time: f32, snake: &Snake, is_blocked: impl Fn(Segment) -> bool) -> bool {
+        self.flash = (self.flash - delta_time / FLASH_TIME).max(0.0);
+
+        let head = snake.head();
+        if self.last_head == Some(head) {
+            return false;
+        }
+        self.last_head = Some(head);
+        if snake.is_invincible() {
+            return false;
+        }
+
+        let (dx, dy) = snake.queued_heading().delta();
+        let (mut x, mut y) = (head.x + dx, head.y + dy);
+        if snake.wrap {
+            (x, y) = wrap_position(x, y);
+        }
+        let next = Segment { x, y };
+        let danger = !is_within_grid(x, y) || is_blocked(next) || snake.occupies_after(next, 1);
+        if danger {
+            self.flash = 1.0;
+        }
+        danger
+    }
+
+    // Kept thin and faint, so it's noticed without hiding the board
+    pub fn draw(&self) {
+        if self.flash <= 0.0 {
+            return;
+        }
+        let color = Color::new(1.0, 0.35, 0.1, 0.35 * self.flash);
+        draw_rectangle_lines(0.0, 0.0, screen_width(), screen_height(), 12.0, color);
+    }
+}
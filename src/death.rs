@@ -0,0 +1,50 @@
+use crate::particles::ParticleSystem;
+use crate::snake::Snake;
+use crate::themes::Theme;
+
+// Real seconds the explosion plays out in slow motion before the crash screen
+const DURATION: f32 = 1.0;
+// Speed of the world while it does
+const SLOW_MOTION: f32 = 0.3;
+
+// The snake bursting apart when it crashes, every segment thrown clear of the head
+pub struct DeathSequence {
+    time_left: f32,
+    pub body_gone: bool, // Exploded, so there's no snake left to draw
+}
+
+impl DeathSequence {
+    pub fn new() -> Self {
+        Self {
+            time_left: 0.0,
+            body_gone: false,
+        }
+    }
+
+    pub fn start(&mut self, snake: &Snake, theme: &Theme, particles: &mut ParticleSystem) {
+        let head = snake.head();
+        for (index, segment) in snake.body.iter().enumerate() {
+            let color = if index == 0 { theme.snake_head } else { theme.snake_body };
+            particles.debris(*segment, head, color);
+        }
+        self.time_left = DURATION;
+        self.body_gone = true;
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.time_left > 0.0
+    }
+
+    pub fn time_scale(&self) -> f32 {
+        if self.is_playing() { SLOW_MOTION } else { 1.0 }
+    }
+
+    // Returns true on the frame the sequence ends
+    pub fn update(&mut self, delta_time: f32) -> bool {
+        if !self.is_playing() {
+            return false;
+        }
+        self.time_left -= delta_time;
+        !self.is_playing()
+    }
+}
@@ -0,0 +1,45 @@
+use macroquad::prelude::*;
+use macroquad::audio::{load_sound, load_sound_from_bytes, Sound};
+
+// Tiny fallbacks baked into the binary so the game still runs with an empty assets folder.
+// Anything dropped into assets/ with the same name takes priority.
+pub const SNAKE_HEAD: &[u8] = include_bytes!("../assets/defaults/snake_head.png");
+pub const UI_BEEP: &[u8] = include_bytes!("../assets/defaults/beep.wav");
+
+pub async fn load_texture_or(path: &str, fallback: &[u8]) -> Texture2D {
+    match load_texture(path).await {
+        Ok(texture) => texture,
+        Err(_) => {
+            println!("Warning: Could not load {}, using the built-in fallback", path);
+            let texture = Texture2D::from_file_with_format(fallback, None);
+            // The fallbacks are pixel art, keep them crisp when scaled up
+            texture.set_filter(FilterMode::Nearest);
+            texture
+        }
+    }
+}
+
+pub async fn load_sound_or(path: &str, fallback: &[u8]) -> Option<Sound> {
+    if let Ok(sound) = load_sound(path).await {
+        return Some(sound);
+    }
+
+    println!("Warning: Could not load {}, using the built-in fallback", path);
+    match load_sound_from_bytes(fallback).await {
+        Ok(sound) => Some(sound),
+        Err(e) => {
+            println!("Warning: Could not load the built-in fallback for {}: {:?}", path, e);
+            None
+        }
+    }
+}
+
+pub async fn load_text_or(path: &str, fallback: &str) -> String {
+    match load_string(path).await {
+        Ok(text) => text,
+        Err(_) => {
+            println!("Warning: Could not load {}, using the built-in fallback", path);
+            fallback.to_string()
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use macroquad::prelude::*;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Difficulty {
+    Easy,
+    Normal,
+    Hard,
+    Insane,
+}
+
+impl Difficulty {
+    pub fn next(&self) -> Difficulty {
+        match self {
+            Difficulty::Easy => Difficulty::Normal,
+            Difficulty::Normal => Difficulty::Hard,
+            Difficulty::Hard => Difficulty::Insane,
+            Difficulty::Insane => Difficulty::Easy,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "EASY",
+            Difficulty::Normal => "NORMAL",
+            Difficulty::Hard => "HARD",
+            Difficulty::Insane => "INSANE",
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        match self {
+            Difficulty::Easy => GREEN,
+            Difficulty::Normal => SKYBLUE,
+            Difficulty::Hard => ORANGE,
+            Difficulty::Insane => RED,
+        }
+    }
+
+    // Seconds between snake moves on level 1
+    pub fn base_delay(&self) -> f32 {
+        match self {
+            Difficulty::Easy => 0.18,
+            Difficulty::Normal => 0.15,
+            Difficulty::Hard => 0.12,
+            Difficulty::Insane => 0.1,
+        }
+    }
+
+    // How steeply the speed climbs with each level
+    pub fn speed_ramp(&self) -> f32 {
+        match self {
+            Difficulty::Easy => 0.2,
+            Difficulty::Normal => 0.3,
+            Difficulty::Hard => 0.4,
+            Difficulty::Insane => 0.5,
+        }
+    }
+
+    // Odds that eaten food leaves a seed behind
+    pub fn seed_chance(&self) -> f64 {
+        match self {
+            Difficulty::Easy => 0.1,
+            Difficulty::Normal => 0.25,
+            Difficulty::Hard => 0.35,
+            Difficulty::Insane => 0.5,
+        }
+    }
+
+    // Food moves somewhere else if it isn't eaten in time
+    pub fn food_lifetime(&self) -> Option<f32> {
+        match self {
+            Difficulty::Easy | Difficulty::Normal => None,
+            Difficulty::Hard => Some(12.0),
+            Difficulty::Insane => Some(8.0),
+        }
+    }
+
+    // Running back over the ice trail crashes the snake instead of just slowing it
+    pub fn trail_is_deadly(&self) -> bool {
+        matches!(self, Difficulty::Hard | Difficulty::Insane)
+    }
+
+    // The one-cell-from-a-crash warning is a help the harder difficulties do without
+    pub fn danger_warning(&self) -> bool {
+        matches!(self, Difficulty::Easy | Difficulty::Normal)
+    }
+
+    // Times a campaign run can pick up from the level it crashed on
+    pub fn continues(&self) -> u32 {
+        match self {
+            Difficulty::Easy => 3,
+            Difficulty::Normal => 2,
+            Difficulty::Hard => 1,
+            Difficulty::Insane => 0,
+        }
+    }
+
+    pub fn score_percent(&self) -> u32 {
+        match self {
+            Difficulty::Easy => 50,
+            Difficulty::Normal => 100,
+            Difficulty::Hard => 150,
+            Difficulty::Insane => 200,
+        }
+    }
+
+    pub fn save_key(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Normal => "normal",
+            Difficulty::Hard => "hard",
+            Difficulty::Insane => "insane",
+        }
+    }
+
+    pub fn from_save_key(key: &str) -> Difficulty {
+        match key {
+            "easy" => Difficulty::Easy,
+            "hard" => Difficulty::Hard,
+            "insane" => Difficulty::Insane,
+            _ => Difficulty::Normal,
+        }
+    }
+}
@@ -0,0 +1,244 @@
+use macroquad::miniquad::conf::Icon;
+use macroquad::prelude::*;
+use std::fs;
+
+// Kept apart from the profile saves, since the window is opened before a profile is picked
+const DISPLAY_PATH: &str = "vypertron_display.txt";
+const ICON_PNG: &[u8] = include_bytes!("../assets/snake_head.png");
+// Seconds the window has to stay put after a move or resize before it's saved
+const SAVE_DELAY: f32 = 1.0;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Resolution {
+    Small,
+    Medium,
+    Wide,
+    Large,
+    Full,
+}
+
+impl Resolution {
+    pub fn next(&self) -> Resolution {
+        match self {
+            Resolution::Small => Resolution::Medium,
+            Resolution::Medium => Resolution::Wide,
+            Resolution::Wide => Resolution::Large,
+            Resolution::Large => Resolution::Full,
+            Resolution::Full => Resolution::Small,
+        }
+    }
+
+    pub fn size(&self) -> (u32, u32) {
+        match self {
+            Resolution::Small => (800, 600),
+            Resolution::Medium => (1024, 768),
+            Resolution::Wide => (1280, 720),
+            Resolution::Large => (1600, 900),
+            Resolution::Full => (1920, 1080),
+        }
+    }
+
+    pub fn save_key(&self) -> &'static str {
+        match self {
+            Resolution::Small => "800x600",
+            Resolution::Medium => "1024x768",
+            Resolution::Wide => "1280x720",
+            Resolution::Large => "1600x900",
+            Resolution::Full => "1920x1080",
+        }
+    }
+
+    pub fn from_save_key(key: &str) -> Resolution {
+        match key {
+            "1024x768" => Resolution::Medium,
+            "1280x720" => Resolution::Wide,
+            "1600x900" => Resolution::Large,
+            "1920x1080" => Resolution::Full,
+            _ => Resolution::Small,
+        }
+    }
+}
+
+// Fullscreen, the window size preset, and where the window was last left on this computer
+pub struct DisplaySettings {
+    pub fullscreen: bool,
+    pub resolution: Resolution,
+    size: (u32, u32), // Last windowed size, which may have been dragged away from the preset
+    position: Option<(u32, u32)>,
+    unsaved_for: Option<f32>,
+    settling: f32, // Seconds to ignore size changes after switching modes
+}
+
+impl DisplaySettings {
+    pub fn load() -> Self {
+        let mut settings = Self {
+            fullscreen: false,
+            resolution: Resolution::Small,
+            size: Resolution::Small.size(),
+            position: None,
+            unsaved_for: None,
+            settling: 0.0,
+        };
+
+        if let Ok(contents) = fs::read_to_string(DISPLAY_PATH) {
+            for line in contents.lines() {
+                if let Some((key, value)) = line.split_once('=') {
+                    let value = value.trim();
+                    match key.trim() {
+                        "fullscreen" => settings.fullscreen = value == "true",
+                        "resolution" => settings.resolution = Resolution::from_save_key(value),
+                        "window_size" => settings.size = parse_pair(value).unwrap_or(settings.size),
+                        "window_position" => settings.position = parse_pair(value),
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        settings
+    }
+
+    fn save(&self) {
+        let mut contents = format!(
+            "fullscreen={}\nresolution={}\nwindow_size={},{}\n",
+            self.fullscreen,
+            self.resolution.save_key(),
+            self.size.0,
+            self.size.1
+        );
+        if let Some((x, y)) = self.position {
+            contents.push_str(&format!("window_position={},{}\n", x, y));
+        }
+
+        if let Err(e) = fs::write(DISPLAY_PATH, contents) {
+            println!("Warning: Could not write display settings: {:?}", e);
+        }
+    }
+
+    pub fn window_conf(&self, title: &str) -> Conf {
+        Conf {
+            window_title: title.to_string(),
+            window_width: self.size.0 as i32,
+            window_height: self.size.1 as i32,
+            fullscreen: self.fullscreen,
+            icon: load_icon(),
+            ..Default::default()
+        }
+    }
+
+    // The window can only be moved once it's open
+    pub fn restore_position(&self) {
+        if let (Some((x, y)), false) = (self.position, self.fullscreen) {
+            macroquad::miniquad::window::set_window_position(x, y);
+        }
+    }
+
+    pub fn toggle_fullscreen(&mut self) {
+        self.fullscreen = !self.fullscreen;
+        set_fullscreen(self.fullscreen);
+        if !self.fullscreen {
+            request_new_screen_size(self.size.0 as f32, self.size.1 as f32);
+        }
+        self.settling = SAVE_DELAY;
+        self.save();
+    }
+
+    pub fn cycle_resolution(&mut self) {
+        self.resolution = self.resolution.next();
+        self.size = self.resolution.size();
+        if !self.fullscreen {
+            request_new_screen_size(self.size.0 as f32, self.size.1 as f32);
+        }
+        self.settling = SAVE_DELAY;
+        self.save();
+    }
+
+    // F11 or Alt+Enter anywhere, and remembering the window when it's moved or resized
+    pub fn update(&mut self, delta_time: f32) {
+        if is_key_pressed(KeyCode::F11) || (alt_held() && is_key_pressed(KeyCode::Enter)) {
+            self.toggle_fullscreen();
+        }
+
+        self.settling = (self.settling - delta_time).max(0.0);
+        if self.fullscreen || self.settling > 0.0 {
+            return;
+        }
+
+        let size = (screen_width() as u32, screen_height() as u32);
+        let position = window_position().or(self.position);
+        if size != self.size || position != self.position {
+            self.size = size;
+            self.position = position;
+            self.unsaved_for = Some(0.0);
+        }
+
+        if let Some(time) = &mut self.unsaved_for {
+            *time += delta_time;
+            if *time >= SAVE_DELAY {
+                self.unsaved_for = None;
+                self.save();
+            }
+        }
+    }
+}
+
+// So ENTER shortcuts can leave Alt+Enter to the fullscreen toggle
+pub fn alt_held() -> bool {
+    is_key_down(KeyCode::LeftAlt) || is_key_down(KeyCode::RightAlt)
+}
+
+// Only some platforms can report where the window is
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+fn window_position() -> Option<(u32, u32)> {
+    Some(macroquad::miniquad::window::get_window_position())
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
+fn window_position() -> Option<(u32, u32)> {
+    None
+}
+
+fn parse_pair(value: &str) -> Option<(u32, u32)> {
+    let (a, b) = value.split_once(',')?;
+    Some((a.trim().parse().ok()?, b.trim().parse().ok()?))
+}
+
+// The snake head artwork, shrunk to the three sizes the OS asks for
+fn load_icon() -> Option<Icon> {
+    let image = match Image::from_file_with_format(ICON_PNG, Some(ImageFormat::Png)) {
+        Ok(image) => image,
+        Err(e) => {
+            println!("Warning: Could not load window icon: {:?}", e);
+            return None;
+        }
+    };
+    Some(Icon {
+        small: shrink(&image, 16).try_into().ok()?,
+        medium: shrink(&image, 32).try_into().ok()?,
+        big: shrink(&image, 64).try_into().ok()?,
+    })
+}
+
+// Averages each block of pixels into one, which looks far better than skipping pixels
+fn shrink(image: &Image, size: usize) -> Vec<u8> {
+    let (width, height) = (image.width as usize, image.height as usize);
+    let mut pixels = Vec::with_capacity(size * size * 4);
+    for y in 0..size {
+        for x in 0..size {
+            let (x0, x1) = (x * width / size, ((x + 1) * width / size).max(x * width / size + 1));
+            let (y0, y1) = (y * height / size, ((y + 1) * height / size).max(y * height / size + 1));
+            let mut sum = [0u32; 4];
+            for sy in y0..y1 {
+                for sx in x0..x1 {
+                    let index = (sy * width + sx) * 4;
+                    for (channel, total) in sum.iter_mut().enumerate() {
+                        *total += image.bytes[index + channel] as u32;
+                    }
+                }
+            }
+            let count = ((x1 - x0) * (y1 - y0)) as u32;
+            pixels.extend(sum.iter().map(|total| (total / count) as u8));
+        }
+    }
+    pixels
+}
@@ -1,7 +1,8 @@
 use macroquad::prelude::*;
-use ::rand::{Rng, thread_rng};
+use ::rand::Rng;
 use crate::grid::{GRID_WIDTH, GRID_HEIGHT, CELL_SIZE, get_offset};
 use crate::snake::Segment;
+use crate::rng;
 
 use lazy_static::lazy_static;
 use std::sync::Mutex;
@@ -28,7 +29,7 @@ pub enum Direction {
 
 impl MovingSnake {
     pub fn new() -> Self {
-        let mut rng = thread_rng();
+        let mut rng = rng::effects();
         let start_x = rng.gen_range(0..GRID_WIDTH as i32);
         let start_y = rng.gen_range(0..GRID_HEIGHT as i32);
 
@@ -0,0 +1,144 @@
+use macroquad::prelude::*;
+use lazy_static::lazy_static;
+use std::collections::VecDeque;
+use std::fs;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use crate::save::{json_escape, SaveData};
+use crate::summary::RunSummary;
+
+// Recent events kept for bug reports
+const MAX_EVENTS: usize = 30;
+const MAX_MESSAGE_LENGTH: usize = 500;
+const REPORT_SCHEMA_VERSION: u32 = 1;
+
+lazy_static! {
+    static ref EVENTS: Mutex<VecDeque<(f64, String)>> = Mutex::new(VecDeque::new());
+}
+
+// Notes something that happened, so a bug report can show what led up to it
+pub fn log_event(event: &str) {
+    let mut events = EVENTS.lock().unwrap();
+    events.push_back((get_time(), event.to_string()));
+    if events.len() > MAX_EVENTS {
+        events.pop_front();
+    }
+}
+
+// Lets the player type a short report, saved as JSON with enough context to act on
+pub struct FeedbackForm {
+    pub showing: bool,
+    message: String,
+}
+
+impl FeedbackForm {
+    pub fn new() -> Self {
+        Self {
+            showing: false,
+            message: String::new(),
+        }
+    }
+
+    pub fn open(&mut self) {
+        self.showing = true;
+        self.message.clear();
+        // Drop whatever was typed before the form opened
+        while get_char_pressed().is_some() {}
+    }
+
+    // Returns the written file's path once the report is sent
+    pub fn update(&mut self, summary: &RunSummary, save_data: &SaveData) -> Option<Result<String, std::io::Error>> {
+        if !self.showing {
+            return None;
+        }
+
+        while let Some(c) = get_char_pressed() {
+            if !c.is_control() && self.message.chars().count() < MAX_MESSAGE_LENGTH {
+                self.message.push(c);
+            }
+        }
+        if is_key_pressed(KeyCode::Backspace) {
+            self.message.pop();
+        }
+
+        if is_key_pressed(KeyCode::Escape) {
+            self.showing = false;
+        } else if is_key_pressed(KeyCode::Enter) && !self.message.trim().is_empty() {
+            self.showing = false;
+            return Some(self.write(summary, save_data));
+        }
+        None
+    }
+
+    fn write(&self, summary: &RunSummary, save_data: &SaveData) -> Result<String, std::io::Error> {
+        let created = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |time| time.as_secs());
+        let path = format!("vypertron_feedback_{}.json", created);
+
+        let events: Vec<String> = EVENTS
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(time, event)| format!("    {{\"time\": {:.2}, \"event\": \"{}\"}}", time, json_escape(event)))
+            .collect();
+
+        let report = format!(
+            "{{\n  \"schema_version\": {},\n  \"created\": {},\n  \"game_version\": \"{}\",\n  \"message\": \"{}\",\n  \"run\": {{\"level\": {}, \"tails\": {}, \"points\": {}, \"length\": {}, \"best_combo\": {}}},\n  \"settings\": {{\"difficulty\": \"{}\", \"controls\": \"{}\", \"wrap_around\": {}, \"follow_camera\": {}}},\n  \"events\": [\n{}\n  ]\n}}\n",
+            REPORT_SCHEMA_VERSION,
+            created,
+            env!("CARGO_PKG_VERSION"),
+            json_escape(self.message.trim()),
+            summary.level,
+            summary.tails,
+            summary.points,
+            summary.length,
+            summary.best_combo,
+            save_data.difficulty.save_key(),
+            save_data.controls.save_key(),
+            save_data.wrap_around,
+            save_data.follow_camera,
+            events.join(",\n"),
+        );
+
+        fs::write(&path, report)?;
+        Ok(path)
+    }
+
+    pub fn draw(&self) {
+        if !self.showing {
+            return;
+        }
+
+        draw_rectangle(0.0, 0.0, screen_width(), screen_height(), Color::new(0.0, 0.0, 0.0, 0.85));
+        draw_centered("SEND FEEDBACK", 120.0, 48.0, SKYBLUE);
+        draw_centered("Describe what happened. Your current run and settings are included.", 165.0, 20.0, LIGHTGRAY);
+
+        // Wrap the message across lines that fit the box
+        let box_width = screen_width() - 160.0;
+        let mut lines = vec![String::new()];
+        for word in self.message.split(' ') {
+            let current = lines.last_mut().unwrap();
+            let candidate = if current.is_empty() { word.to_string() } else { format!("{} {}", current, word) };
+            if measure_text(&candidate, None, 24, 1.0).width > box_width && !current.is_empty() {
+                lines.push(word.to_string());
+            } else {
+                *current = candidate;
+            }
+        }
+        let cursor = if (get_time() * 2.0).fract() < 0.5 { "_" } else { "" };
+        if let Some(last) = lines.last_mut() {
+            last.push_str(cursor);
+        }
+
+        draw_rectangle_lines(70.0, 190.0, screen_width() - 140.0, 230.0, 2.0, GRAY);
+        for (i, line) in lines.iter().take(8).enumerate() {
+            draw_text(line, 80.0, 220.0 + i as f32 * 26.0, 24.0, WHITE);
+        }
+
+        draw_centered("ENTER - Save report   ESC - Cancel", screen_height() - 40.0, 24.0, LIGHTGRAY);
+    }
+}
+
+fn draw_centered(text: &str, y: f32, size: f32, color: Color) {
+    let width = measure_text(text, None, size as u16, 1.0).width;
+    draw_text(text, (screen_width() - width) / 2.0, y, size, color);
+}
@@ -1,51 +1,436 @@
 use macroquad::prelude::*;
-use ::rand::{Rng, thread_rng};
+use ::rand::Rng;
+use ::rand::seq::SliceRandom;
+use std::collections::VecDeque;
 
 use crate::snake::{Segment, Snake};
-use crate::grid::{GRID_WIDTH, GRID_HEIGHT, CELL_SIZE, get_offset};
-use crate::themes::Theme;
+use crate::grid::{GRID_WIDTH, GRID_HEIGHT, CELL_SIZE, get_offset, wrap_position};
+use crate::level::{max_concurrent_food, BOSS_LEVEL};
+use crate::motion;
+use crate::themes::{shaped_food, Theme};
+use crate::rng;
+
+// Seconds between steps when food is wandering
+const WANDER_DELAY: f32 = 0.6;
+// Food holds still when the snake head gets this close, so it can be caught
+const WANDER_PAUSE_DISTANCE: i32 = 2;
+// Seconds between extra foods turning up on levels with several at once
+const EXTRA_FOOD_DELAY: f32 = 2.5;
+// Foods that have to be out at once before a golden one joins them
+const GOLDEN_THRESHOLD: usize = 3;
+// Seconds a golden food waits to be eaten
+const GOLDEN_LIFETIME: f32 = 6.0;
+// Seconds before another golden food can turn up after one is eaten or runs out
+const GOLDEN_COOLDOWN: f32 = 4.0;
+pub const GOLDEN_FOOD_POINTS: u32 = 50;
+// Seconds a golden food lets the snake pass through walls and itself
+pub const GOLDEN_INVINCIBILITY: f32 = 6.0;
+// First level poison food can turn up on
+const POISON_FROM_LEVEL: usize = 4;
+// Seconds between rolls for a new poison food
+const POISON_DELAY: f32 = 3.0;
+// Seconds a poison food lingers before it goes away
+const POISON_LIFETIME: f32 = 10.0;
+const MAX_POISON: usize = 2;
+// Cells lost and points taken away for eating poison
+pub const POISON_SHRINK: usize = 2;
+pub const POISON_PENALTY: u32 = 25;
+pub const POISON_COLOR: Color = Color::new(0.55, 0.8, 0.1, 1.0);
+// First level the slow motion power-up can turn up on
+const SLOW_MOTION_FROM_LEVEL: usize = 3;
+// Seconds between chances for a slow motion power-up, and the odds of each
+const SLOW_MOTION_DELAY: f32 = 12.0;
+const SLOW_MOTION_CHANCE: f64 = 0.35;
+// Seconds the power-up waits to be picked up
+const SLOW_MOTION_LIFETIME: f32 = 8.0;
 
 pub struct Food {
     pub position: Segment,
+    pub wander_timer: f32,
+    pub age: f32,
+    pub lifetime: Option<f32>, // Set by harder difficulties, food moves on when it runs out
+    pub sequence: VecDeque<Segment>, // Cells to use in order, for time attack
+    pub boxed_in: bool, // There was nowhere free to put it, so it's off the board until there is
 }
 
 impl Food {
-    pub fn new(snake: &Snake) -> Self {
+    // `is_blocked` reports cells taken by obstacles, food never goes there
+    pub fn new(snake: &Snake, is_blocked: impl Fn(Segment) -> bool) -> Self {
         let mut food = Food {
             position: Segment { x: 0, y: 0 },
+            wander_timer: 0.0,
+            age: 0.0,
+            lifetime: None,
+            sequence: VecDeque::new(),
+            boxed_in: false,
         };
-        food.relocate(snake);
+        food.relocate(snake, is_blocked);
         food
     }
 
-    pub fn relocate(&mut self, snake: &Snake) {
-        let mut rng = thread_rng();
-        loop {
-            let pos = Segment {
-                x: rng.gen_range(0..GRID_WIDTH),
-                y: rng.gen_range(0..GRID_HEIGHT),
-            };
-            if !snake.is_at(pos) {
-                self.position = pos;
-                break;
+    // False when every cell is taken, the food is then boxed in until it's moved again
+    pub fn relocate(&mut self, snake: &Snake, is_blocked: impl Fn(Segment) -> bool) -> bool {
+        let pos = self.next_cell(snake, is_blocked);
+        self.boxed_in = pos.is_none();
+        if let Some(pos) = pos {
+            self.position = pos;
+            self.age = 0.0;
+        }
+        !self.boxed_in
+    }
+
+    fn next_cell(&mut self, snake: &Snake, is_blocked: impl Fn(Segment) -> bool) -> Option<Segment> {
+        // Time attack lays the food out in a fixed order, skipping cells that are taken
+        while let Some(pos) = self.sequence.pop_front() {
+            if !snake.is_at(pos) && !is_blocked(pos) {
+                return Some(pos);
+            }
+        }
+
+        let mut rng = rng::gameplay();
+
+        // Only spawn where the snake can actually get to
+        if let Some(&pos) = reachable_cells(snake, &is_blocked).choose(&mut *rng) {
+            return Some(pos);
+        }
+
+        // Completely cut off, any free cell will do
+        let free_cells: Vec<Segment> = (0..GRID_HEIGHT)
+            .flat_map(|y| (0..GRID_WIDTH).map(move |x| Segment { x, y }))
+            .filter(|pos| !snake.is_at(*pos) && !is_blocked(*pos))
+            .collect();
+        free_cells.choose(&mut *rng).copied()
+    }
+
+    // Whether the food is on this cell to be eaten
+    pub fn is_at(&self, cell: Segment) -> bool {
+        !self.boxed_in && self.position == cell
+    }
+
+    // Moving food mechanic: step to a random free neighbouring cell on a timer
+    pub fn wander(&mut self, delta_time: f32, snake: &Snake, is_blocked: impl Fn(Segment) -> bool) {
+        if self.boxed_in {
+            return;
+        }
+        let head = snake.head();
+        let distance = (head.x - self.position.x).abs() + (head.y - self.position.y).abs();
+        if distance <= WANDER_PAUSE_DISTANCE {
+            return;
+        }
+
+        self.wander_timer += delta_time;
+        if self.wander_timer < WANDER_DELAY {
+            return;
+        }
+        self.wander_timer = 0.0;
+
+        let free_cells: Vec<Segment> = [(0, -1), (0, 1), (-1, 0), (1, 0)]
+            .iter()
+            .map(|(dx, dy)| Segment { x: self.position.x + dx, y: self.position.y + dy })
+            .filter(|pos| pos.x >= 0 && pos.x < GRID_WIDTH && pos.y >= 0 && pos.y < GRID_HEIGHT)
+            .filter(|pos| !snake.is_at(*pos) && !is_blocked(*pos))
+            .collect();
+
+        if !free_cells.is_empty() {
+            let index = rng::gameplay().gen_range(0..free_cells.len());
+            self.position = free_cells[index];
+        }
+    }
+
+    pub fn update_lifetime(&mut self, delta_time: f32, snake: &Snake, is_blocked: impl Fn(Segment) -> bool) {
+        if let Some(lifetime) = self.lifetime {
+            self.age += delta_time;
+            if self.age >= lifetime {
+                self.relocate(snake, is_blocked);
             }
         }
     }
 
     pub fn draw(&self, theme: &Theme) {
-        let offset = get_offset();
-        draw_rectangle(
-            offset.x + self.position.x as f32 * CELL_SIZE,
-            offset.y + self.position.y as f32 * CELL_SIZE,
-            CELL_SIZE,
-            CELL_SIZE,
-            theme.food,
-        );
+        if self.boxed_in {
+            return;
+        }
+        // Blink faster and faster in the last few seconds before expiring
+        if let Some(lifetime) = self.lifetime {
+            let remaining = lifetime - self.age;
+            if remaining < 3.0 && (get_time() * (12.0 - remaining as f64 * 2.0)).sin() < 0.0 {
+                return;
+            }
+        }
+
+        draw_food_cell(self.position, theme.food);
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum FoodKind {
+    Normal,
+    Golden,
+    Poison, // To be avoided, shrinks the snake and costs points
+    SlowMotion, // A power-up rather than food, it doesn't grow the snake
+}
+
+impl FoodKind {
+    // Whether eating it grows the snake and counts toward the level
+    pub fn is_food(&self) -> bool {
+        matches!(self, FoodKind::Normal | FoodKind::Golden)
+    }
+}
+
+// Odds that a roll for poison comes up, growing with the level and capped at one in two
+fn poison_chance(level: usize) -> f64 {
+    if level < POISON_FROM_LEVEL || level == BOSS_LEVEL {
+        return 0.0;
+    }
+    (0.2 + (level - POISON_FROM_LEVEL) as f64 * 0.04).min(0.5)
+}
+
+pub fn random_food_kind(level: usize, rng: &mut impl Rng) -> FoodKind {
+    if rng.gen_bool(poison_chance(level)) {
+        FoodKind::Poison
+    } else {
+        FoodKind::Normal
     }
 }
 
+// The food beyond the main one on levels with several out at once. They turn up one
+// at a time, and a golden food worth bonus points joins them while enough are out.
+// Later levels also scatter poison food and the odd slow motion power-up, on every
+// level and not just those.
+pub struct ExtraFood {
+    cells: Vec<Segment>,
+    golden: Option<(Segment, f32)>,      // Cell and seconds left
+    poison: Vec<(Segment, f32)>,         // Cell and seconds left
+    slow_motion: Option<(Segment, f32)>, // Cell and seconds left
+    max_concurrent: usize,          // Counting the main food
+    level: usize,
+    timer: f32,
+    golden_cooldown: f32,
+    poison_timer: f32,
+    slow_motion_timer: f32,
+}
 
+impl ExtraFood {
+    pub fn new() -> Self {
+        Self {
+            cells: Vec::new(),
+            golden: None,
+            poison: Vec::new(),
+            slow_motion: None,
+            max_concurrent: 1,
+            level: 1,
+            timer: 0.0,
+            golden_cooldown: 0.0,
+            poison_timer: 0.0,
+            slow_motion_timer: 0.0,
+        }
+    }
 
+    pub fn setup(&mut self, level: usize) {
+        self.cells.clear();
+        self.golden = None;
+        self.poison.clear();
+        self.slow_motion = None;
+        self.max_concurrent = max_concurrent_food(level);
+        self.level = level;
+        self.timer = 0.0;
+        self.golden_cooldown = 0.0;
+        self.poison_timer = 0.0;
+        self.slow_motion_timer = 0.0;
+    }
 
+    pub fn update(&mut self, delta_time: f32, snake: &Snake, is_blocked: impl Fn(Segment) -> bool) {
+        if let Some((_, left)) = &mut self.golden {
+            *left -= delta_time;
+            if *left <= 0.0 {
+                self.golden = None;
+                self.golden_cooldown = GOLDEN_COOLDOWN;
+            }
+        }
+        self.golden_cooldown -= delta_time;
 
+        for (_, left) in &mut self.poison {
+            *left -= delta_time;
+        }
+        self.poison.retain(|(_, left)| *left > 0.0);
+        self.poison_timer += delta_time;
+        if self.poison_timer >= POISON_DELAY && self.poison.len() < MAX_POISON {
+            self.poison_timer = 0.0;
+            if random_food_kind(self.level, &mut *rng::gameplay()) == FoodKind::Poison {
+                if let Some(cell) = self.free_cell(snake, &is_blocked) {
+                    self.poison.push((cell, POISON_LIFETIME));
+                }
+            }
+        }
 
+        if let Some((_, left)) = &mut self.slow_motion {
+            *left -= delta_time;
+            if *left <= 0.0 {
+                self.slow_motion = None;
+            }
+        }
+        self.slow_motion_timer += delta_time;
+        if self.slow_motion_timer >= SLOW_MOTION_DELAY && self.slow_motion.is_none() {
+            self.slow_motion_timer = 0.0;
+            let allowed = self.level >= SLOW_MOTION_FROM_LEVEL && self.level != BOSS_LEVEL;
+            if allowed && rng::gameplay().gen_bool(SLOW_MOTION_CHANCE) {
+                if let Some(cell) = self.free_cell(snake, &is_blocked) {
+                    self.slow_motion = Some((cell, SLOW_MOTION_LIFETIME));
+                }
+            }
+        }
+
+        if 1 + self.cells.len() < self.max_concurrent {
+            self.timer += delta_time;
+            if self.timer >= EXTRA_FOOD_DELAY {
+                self.timer = 0.0;
+                if let Some(cell) = self.free_cell(snake, &is_blocked) {
+                    self.cells.push(cell);
+                }
+            }
+        }
+
+        if self.golden.is_none() && self.golden_cooldown <= 0.0 && 1 + self.cells.len() >= GOLDEN_THRESHOLD {
+            if let Some(cell) = self.free_cell(snake, &is_blocked) {
+                self.golden = Some((cell, GOLDEN_LIFETIME));
+            }
+        }
+    }
+
+    // Puts one of the given kind on a free cell straight away, false if there's no room
+    #[cfg(any(feature = "console", debug_assertions))]
+    pub fn spawn(&mut self, kind: FoodKind, snake: &Snake, is_blocked: impl Fn(Segment) -> bool) -> bool {
+        let Some(cell) = self.free_cell(snake, &is_blocked) else {
+            return false;
+        };
+        match kind {
+            FoodKind::Normal => self.cells.push(cell),
+            FoodKind::Golden => self.golden = Some((cell, GOLDEN_LIFETIME)),
+            FoodKind::Poison => self.poison.push((cell, POISON_LIFETIME)),
+            FoodKind::SlowMotion => self.slow_motion = Some((cell, SLOW_MOTION_LIFETIME)),
+        }
+        true
+    }
+
+    pub fn golden(&self) -> Option<Segment> {
+        self.golden.map(|(cell, _)| cell)
+    }
+
+    fn free_cell(&self, snake: &Snake, is_blocked: &impl Fn(Segment) -> bool) -> Option<Segment> {
+        let cells = reachable_cells(snake, &|pos| is_blocked(pos) || self.is_at(pos));
+        cells.choose(&mut *rng::gameplay()).copied()
+    }
+
+    pub fn is_at(&self, position: Segment) -> bool {
+        self.cells.contains(&position)
+            || self.golden.is_some_and(|(cell, _)| cell == position)
+            || self.is_poison(position)
+            || self.slow_motion.is_some_and(|(cell, _)| cell == position)
+    }
+
+    pub fn is_poison(&self, position: Segment) -> bool {
+        self.poison.iter().any(|(cell, _)| *cell == position)
+    }
+
+    // Eats whatever is at `position`
+    pub fn take(&mut self, position: Segment) -> Option<FoodKind> {
+        if self.golden.is_some_and(|(cell, _)| cell == position) {
+            self.golden = None;
+            self.golden_cooldown = GOLDEN_COOLDOWN;
+            return Some(FoodKind::Golden);
+        }
+        if let Some(index) = self.poison.iter().position(|(cell, _)| *cell == position) {
+            self.poison.swap_remove(index);
+            return Some(FoodKind::Poison);
+        }
+        if self.slow_motion.is_some_and(|(cell, _)| cell == position) {
+            self.slow_motion = None;
+            return Some(FoodKind::SlowMotion);
+        }
+        let index = self.cells.iter().position(|cell| *cell == position)?;
+        self.cells.swap_remove(index);
+        Some(FoodKind::Normal)
+    }
+
+    pub fn draw(&self, theme: &Theme) {
+        for cell in &self.cells {
+            draw_food_cell(*cell, theme.food);
+        }
+        if let Some((cell, _)) = self.golden {
+            let shine = motion::pulse(6.0, 0.25, 0.75);
+            draw_food_cell(cell, Color::new(GOLD.r, GOLD.g, GOLD.b, shine));
+        }
+        for (cell, _) in &self.poison {
+            // A sickly throb, with a dark cross so it reads as bad without the colour
+            let throb = motion::pulse(3.0, 0.25, 0.7);
+            draw_food_cell(*cell, Color::new(POISON_COLOR.r * throb, POISON_COLOR.g * throb, POISON_COLOR.b, 1.0));
+            let offset = get_offset();
+            let (x, y) = (offset.x + cell.x as f32 * CELL_SIZE, offset.y + cell.y as f32 * CELL_SIZE);
+            let inset = CELL_SIZE * 0.3;
+            let far = CELL_SIZE - inset;
+            draw_line(x + inset, y + inset, x + far, y + far, 2.0, DARKGREEN);
+            draw_line(x + far, y + inset, x + inset, y + far, 2.0, DARKGREEN);
+        }
+        if let Some((cell, _)) = self.slow_motion {
+            // An hourglass, two triangles meeting in the middle of the cell
+            let offset = get_offset();
+            let (x, y) = (offset.x + cell.x as f32 * CELL_SIZE, offset.y + cell.y as f32 * CELL_SIZE);
+            let color = Color::new(SKYBLUE.r, SKYBLUE.g, SKYBLUE.b, motion::pulse(4.0, 0.2, 0.8));
+            let middle = vec2(x + CELL_SIZE / 2.0, y + CELL_SIZE / 2.0);
+            let (left, right) = (x + 2.0, x + CELL_SIZE - 2.0);
+            draw_triangle(vec2(left, y + 2.0), vec2(right, y + 2.0), middle, color);
+            draw_triangle(vec2(left, y + CELL_SIZE - 2.0), vec2(right, y + CELL_SIZE - 2.0), middle, color);
+        }
+    }
+}
+
+fn draw_food_cell(position: Segment, color: Color) {
+    let offset = get_offset();
+    let x = offset.x + position.x as f32 * CELL_SIZE;
+    let y = offset.y + position.y as f32 * CELL_SIZE;
+    if shaped_food() {
+        // A diamond with a dark outline can't be mistaken for a square snake segment
+        let center = vec2(x + CELL_SIZE / 2.0, y + CELL_SIZE / 2.0);
+        let half = CELL_SIZE / 2.0;
+        let top = center - vec2(0.0, half);
+        let right = center + vec2(half, 0.0);
+        let bottom = center + vec2(0.0, half);
+        let left = center - vec2(half, 0.0);
+        draw_triangle(top, right, bottom, color);
+        draw_triangle(top, bottom, left, color);
+        for (from, to) in [(top, right), (right, bottom), (bottom, left), (left, top)] {
+            draw_line(from.x, from.y, to.x, to.y, 2.0, BLACK);
+        }
+    } else {
+        draw_rectangle(x, y, CELL_SIZE, CELL_SIZE, color);
+    }
+}
+
+// Flood fill from the head over cells that aren't the snake or an obstacle
+fn reachable_cells(snake: &Snake, is_blocked: &impl Fn(Segment) -> bool) -> Vec<Segment> {
+    let mut visited = vec![false; (GRID_WIDTH * GRID_HEIGHT) as usize];
+    let mut frontier = VecDeque::from([snake.head()]);
+    let mut reachable = Vec::new();
+
+    while let Some(cell) = frontier.pop_front() {
+        for (dx, dy) in [(0, -1), (0, 1), (-1, 0), (1, 0)] {
+            let mut next = Segment { x: cell.x + dx, y: cell.y + dy };
+            if snake.wrap {
+                (next.x, next.y) = wrap_position(next.x, next.y);
+            }
+            if next.x < 0 || next.x >= GRID_WIDTH || next.y < 0 || next.y >= GRID_HEIGHT {
+                continue;
+            }
+
+            let index = (next.y * GRID_WIDTH + next.x) as usize;
+            if visited[index] || snake.is_at(next) || is_blocked(next) {
+                continue;
+            }
+            visited[index] = true;
+            frontier.push_back(next);
+            reachable.push(next);
+        }
+    }
+
+    reachable
+}
@@ -0,0 +1,114 @@
+use macroquad::prelude::*;
+use ::rand::Rng;
+use crate::grid::{CELL_SIZE, get_offset};
+use crate::snake::{Segment, Snake};
+use crate::rng;
+
+// Chance that eaten food leaves a seed behind
+// Seconds a seed is telegraphed before it becomes a solid plant
+const GROW_TIME: f32 = 5.0;
+// Keep the arena playable on long runs
+const MAX_PLANTS: usize = 30;
+
+pub struct Plant {
+    pub position: Segment,
+    pub grow_timer: f32,
+}
+
+impl Plant {
+    pub fn is_grown(&self) -> bool {
+        self.grow_timer <= 0.0
+    }
+}
+
+// Plants that sprout where food was eaten, slowly cluttering the arena
+pub struct Garden {
+    pub plants: Vec<Plant>,
+}
+
+impl Garden {
+    pub fn new() -> Self {
+        Self { plants: Vec::new() }
+    }
+
+    pub fn plant_seed(&mut self, position: Segment, chance: f64) {
+        if self.plants.len() >= MAX_PLANTS || self.is_occupied(position) {
+            return;
+        }
+
+        if rng::gameplay().gen_bool(chance) {
+            self.plants.push(Plant {
+                position,
+                grow_timer: GROW_TIME,
+            });
+        }
+    }
+
+    // Plants a seed no matter the odds, used by the boss's summon attack
+    pub fn force_seed(&mut self, position: Segment) {
+        if self.plants.len() < MAX_PLANTS && !self.is_occupied(position) {
+            self.plants.push(Plant {
+                position,
+                grow_timer: GROW_TIME,
+            });
+        }
+    }
+
+    pub fn update(&mut self, delta_time: f32, snake: &Snake) {
+        for plant in &mut self.plants {
+            if plant.is_grown() {
+                continue;
+            }
+
+            plant.grow_timer -= delta_time;
+
+            // Don't grow into the snake, wait until the cell is clear
+            if plant.is_grown() && snake.is_at(plant.position) {
+                plant.grow_timer = 0.1;
+            }
+        }
+    }
+
+    // Only fully grown plants are solid
+    pub fn is_blocked(&self, position: Segment) -> bool {
+        self.plants.iter().any(|plant| plant.is_grown() && plant.position == position)
+    }
+
+    // Breaks the grown plant at `position` along with any grown plants touching it,
+    // returning how many went
+    pub fn smash(&mut self, position: Segment) -> usize {
+        let before = self.plants.len();
+        self.plants.retain(|plant| {
+            let distance = (plant.position.x - position.x).abs() + (plant.position.y - position.y).abs();
+            !(plant.is_grown() && distance <= 1)
+        });
+        before - self.plants.len()
+    }
+
+    // Seeds count too, so food never spawns on a cell that's about to grow
+    pub fn is_occupied(&self, position: Segment) -> bool {
+        self.plants.iter().any(|plant| plant.position == position)
+    }
+
+    pub fn draw(&self) {
+        let offset = get_offset();
+
+        for plant in &self.plants {
+            let x = offset.x + plant.position.x as f32 * CELL_SIZE;
+            let y = offset.y + plant.position.y as f32 * CELL_SIZE;
+            let center_x = x + CELL_SIZE / 2.0;
+            let center_y = y + CELL_SIZE / 2.0;
+
+            if plant.is_grown() {
+                draw_rectangle(x, y, CELL_SIZE, CELL_SIZE, DARKGREEN);
+                draw_circle(center_x, center_y, CELL_SIZE * 0.35, LIME);
+            } else {
+                // Blink faster as the seed gets closer to sprouting
+                let progress = 1.0 - plant.grow_timer / GROW_TIME;
+                let blink = (get_time() * (4.0 + progress as f64 * 12.0)).sin() * 0.5 + 0.5;
+                let radius = CELL_SIZE * (0.15 + progress * 0.25);
+                draw_circle_lines(center_x, center_y, radius, 2.0, Color::new(0.5, 1.0, 0.3, blink as f32));
+            }
+        }
+    }
+}
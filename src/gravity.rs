@@ -0,0 +1,109 @@
+use macroquad::prelude::*;
+use ::rand::Rng;
+use crate::grid::{GRID_WIDTH, GRID_HEIGHT, CELL_SIZE, get_offset};
+use crate::level::{special_mechanics, SpecialMechanic};
+use crate::snake::{Direction, Segment, Snake};
+use crate::rng;
+
+const WELL_RADIUS: i32 = 4;
+const WELL_COUNT: usize = 2;
+
+pub struct GravityWell {
+    pub center: Segment,
+    pub radius: i32,
+    pub strength: f32, // Chance per move that the snake gets bent toward the well
+}
+
+impl GravityWell {
+    fn contains(&self, position: Segment) -> bool {
+        let dx = (position.x - self.center.x) as f32;
+        let dy = (position.y - self.center.y) as f32;
+        (dx * dx + dy * dy).sqrt() <= self.radius as f32
+    }
+}
+
+pub struct GravityField {
+    pub wells: Vec<GravityWell>,
+}
+
+impl GravityField {
+    pub fn new() -> Self {
+        Self { wells: Vec::new() }
+    }
+
+    // Rebuild the wells for a level, or clear them if the level has none
+    pub fn setup(&mut self, level: usize) {
+        self.wells.clear();
+
+        if !special_mechanics(level).contains(&SpecialMechanic::GravityWells) {
+            return;
+        }
+
+        // Wells pull harder as the levels go on
+        let strength = (0.25 + level as f32 * 0.02).min(0.6);
+        let mut rng = rng::gameplay();
+
+        for _ in 0..WELL_COUNT {
+            let center = Segment {
+                x: rng.gen_range(WELL_RADIUS..GRID_WIDTH - WELL_RADIUS),
+                y: rng.gen_range(WELL_RADIUS..GRID_HEIGHT - WELL_RADIUS),
+            };
+            self.wells.push(GravityWell {
+                center,
+                radius: WELL_RADIUS,
+                strength,
+            });
+        }
+    }
+
+    // Direction the snake is being pulled this move, if its head is inside a well.
+    // Only sideways pulls are returned, so the snake bends rather than reverses.
+    pub fn pull_on(&self, snake: &Snake) -> Option<Direction> {
+        let head = snake.head();
+        let well = self.wells.iter().find(|well| well.contains(head))?;
+
+        if !rng::gameplay().gen_bool(well.strength as f64) {
+            return None;
+        }
+
+        let dx = well.center.x - head.x;
+        let dy = well.center.y - head.y;
+
+        match snake.dir {
+            Direction::Left | Direction::Right if dy < 0 => Some(Direction::Up),
+            Direction::Left | Direction::Right if dy > 0 => Some(Direction::Down),
+            Direction::Up | Direction::Down if dx < 0 => Some(Direction::Left),
+            Direction::Up | Direction::Down if dx > 0 => Some(Direction::Right),
+            _ => None,
+        }
+    }
+
+    pub fn draw(&self) {
+        let offset = get_offset();
+        let time = get_time() as f32;
+
+        for well in &self.wells {
+            let center_x = offset.x + (well.center.x as f32 + 0.5) * CELL_SIZE;
+            let center_y = offset.y + (well.center.y as f32 + 0.5) * CELL_SIZE;
+            let radius = well.radius as f32 * CELL_SIZE;
+
+            draw_circle(center_x, center_y, radius, Color::new(0.5, 0.0, 0.8, 0.08));
+            draw_circle_lines(center_x, center_y, radius, 1.0, Color::new(0.6, 0.2, 1.0, 0.4));
+
+            // Swirl of dots spiralling into the center
+            for ring in 0..3 {
+                let ring_radius = radius * (1.0 - ring as f32 * 0.3);
+                let speed = 1.0 + ring as f32 * 0.8;
+                for arm in 0..4 {
+                    let angle = time * speed + arm as f32 * std::f32::consts::FRAC_PI_2 + ring as f32;
+                    draw_circle(
+                        center_x + angle.cos() * ring_radius * 0.8,
+                        center_y + angle.sin() * ring_radius * 0.8,
+                        2.0 + ring as f32,
+                        Color::new(0.7, 0.3, 1.0, 0.3 + ring as f32 * 0.2),
+                    );
+                }
+            }
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use macroquad::prelude::*;
+use crate::camera::shake_offset;
 
 pub const GRID_WIDTH: i32 = 40;
 pub const GRID_HEIGHT: i32 = 30;
@@ -14,10 +15,11 @@ pub fn get_offset() -> Vec2 {
     let offset_x = (screen_width - grid_pixel_width) / 2.0;
     let offset_y = (screen_height - grid_pixel_height) / 2.0 + 40.0; // Added offset for UI elements
 
-    vec2(offset_x, offset_y)
+    vec2(offset_x, offset_y) + shake_offset()
 }
 
-pub fn draw_grid(color: Color) {
+// Open levels draw a dashed border to show the snake can pass through
+pub fn draw_grid(color: Color, wrap: bool) {
     let offset = get_offset();
 
     // Draw grid lines with the specified color
@@ -43,6 +45,24 @@ pub fn draw_grid(color: Color) {
         );
     }
 
+    if wrap {
+        let left = offset.x - 2.0;
+        let top = offset.y - 2.0;
+        let right = offset.x + GRID_WIDTH as f32 * CELL_SIZE + 2.0;
+        let bottom = offset.y + GRID_HEIGHT as f32 * CELL_SIZE + 2.0;
+        for x in (0..GRID_WIDTH).step_by(2) {
+            let x = offset.x + x as f32 * CELL_SIZE;
+            draw_line(x, top, x + CELL_SIZE, top, 2.0, color);
+            draw_line(x, bottom, x + CELL_SIZE, bottom, 2.0, color);
+        }
+        for y in (0..GRID_HEIGHT).step_by(2) {
+            let y = offset.y + y as f32 * CELL_SIZE;
+            draw_line(left, y, left, y + CELL_SIZE, 2.0, color);
+            draw_line(right, y, right, y + CELL_SIZE, 2.0, color);
+        }
+        return;
+    }
+
     // Draw border around the grid for better visibility
     draw_rectangle_lines(
         offset.x - 2.0,
@@ -54,6 +74,11 @@ pub fn draw_grid(color: Color) {
     );
 }
 
+// Brings a position that went off one edge back in from the opposite edge
+pub fn wrap_position(x: i32, y: i32) -> (i32, i32) {
+    (x.rem_euclid(GRID_WIDTH), y.rem_euclid(GRID_HEIGHT))
+}
+
 pub fn is_within_grid(x: i32, y: i32) -> bool {
     x >= 0 && x < GRID_WIDTH && y >= 0 && y < GRID_HEIGHT
 }
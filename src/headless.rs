@@ -0,0 +1,254 @@
+use std::io::{BufRead, Write};
+use crate::arena::Arena;
+use crate::breakdown::calculate_level_completion_score;
+use crate::difficulty::Difficulty;
+use crate::food::{Food, FoodKind, GOLDEN_FOOD_POINTS, GOLDEN_INVINCIBILITY, POISON_PENALTY, POISON_SHRINK};
+use crate::input::PlayerCommand;
+use crate::level::{LevelTracker, SpecialMechanic};
+use crate::milestones::Milestones;
+use crate::objectives::ObjectiveTracker;
+use crate::score::Score;
+use crate::snake::{Direction, Segment, Snake};
+
+// Everything a bot needs to know about the board after a step
+pub struct GameObservation {
+    pub level: usize,
+    pub tails: u32,
+    pub points: u32,
+    pub alive: bool,
+    pub ate: bool, // Food was eaten on this step
+    pub direction: Direction,
+    pub food: Segment,
+    pub snake: Vec<Segment>, // Head first
+}
+
+impl GameObservation {
+    // One line, e.g. level=1 tails=0 points=0 alive=true ate=false dir=R food=3,4 snake=20,15;19,15
+    pub fn encode(&self) -> String {
+        let cells = |cells: &[Segment]| {
+            cells
+                .iter()
+                .map(|cell| format!("{},{}", cell.x, cell.y))
+                .collect::<Vec<_>>()
+                .join(";")
+        };
+        format!(
+            "level={} tails={} points={} alive={} ate={} dir={} food={} snake={}",
+            self.level,
+            self.tails,
+            self.points,
+            self.alive,
+            self.ate,
+            direction_letter(self.direction),
+            cells(&[self.food]),
+            cells(&self.snake),
+        )
+    }
+}
+
+// The game rules from the main loop with no window, sound, or clock: each step is
+// exactly one snake move. Used by `--headless` for automated tests and bots, and
+// by the title screen's attract mode.
+pub struct HeadlessGame {
+    snake: Snake,
+    food: Food,
+    arena: Arena,
+    level_tracker: LevelTracker,
+    objectives: ObjectiveTracker,
+    milestones: Milestones,
+    points: Score,
+    tails: u32,
+    difficulty: Difficulty,
+    alive: bool,
+}
+
+impl HeadlessGame {
+    pub fn new(difficulty: Difficulty) -> Self {
+        let snake = Snake::new();
+        let mut arena = Arena::new();
+        let level_tracker = LevelTracker::new();
+        arena.setup(level_tracker.level, &snake);
+        let mut food = Food::new(&snake, |pos| arena.is_occupied(pos));
+        food.lifetime = difficulty.food_lifetime();
+        let mut points = Score::new();
+        points.percent = difficulty.score_percent();
+
+        Self {
+            snake,
+            food,
+            arena,
+            level_tracker,
+            objectives: ObjectiveTracker::new(),
+            milestones: Milestones::new(),
+            points,
+            tails: 0,
+            difficulty,
+            alive: true,
+        }
+    }
+
+    pub fn observe(&self, ate: bool) -> GameObservation {
+        GameObservation {
+            level: self.level_tracker.level,
+            tails: self.tails,
+            points: self.points.value,
+            alive: self.alive,
+            ate,
+            direction: self.snake.dir,
+            food: self.food.position,
+            snake: self.snake.body.iter().copied().collect(),
+        }
+    }
+
+    pub fn snake(&self) -> &Snake {
+        &self.snake
+    }
+
+    pub fn food(&self) -> &Food {
+        &self.food
+    }
+
+    pub fn arena(&self) -> &Arena {
+        &self.arena
+    }
+
+    pub fn level(&self) -> usize {
+        self.level_tracker.level
+    }
+
+    // Applies a command (or carries straight on with None) and advances the game by one snake move
+    pub fn step(&mut self, command: Option<PlayerCommand>) -> GameObservation {
+        if !self.alive {
+            return self.observe(false);
+        }
+
+        self.snake.update_speed(self.level_tracker.level, self.difficulty);
+        self.snake.wrap = self.level_tracker.has_mechanic(SpecialMechanic::WrapAround);
+        self.snake.speed_multiplier = self.arena.speed_multiplier_at(self.snake.head());
+        if let Some(command) = command {
+            self.snake.apply(command);
+        }
+
+        // Exactly the time the snake needs for its next move
+        self.snake.move_timer = 0.0;
+        let delta_time = self.snake.move_delay / self.snake.speed_multiplier;
+        self.level_tracker.update(delta_time);
+        let pull = self.arena.gravity.pull_on(&self.snake);
+        self.snake.update(delta_time, pull);
+        if let Some(exit) = self.arena.teleporters.check(&self.snake) {
+            self.snake.teleport_head(exit);
+        }
+        self.points.update(delta_time);
+        self.arena.update(delta_time, &self.snake);
+        let arena = &self.arena;
+        if self.level_tracker.has_mechanic(SpecialMechanic::MovingFood) {
+            self.food.wander(delta_time, &self.snake, |pos| arena.is_occupied(pos));
+        }
+        self.food.update_lifetime(delta_time, &self.snake, |pos| arena.is_occupied(pos));
+        if self.food.boxed_in || arena.is_covered(self.food.position) {
+            self.food.relocate(&self.snake, |pos| arena.is_occupied(pos));
+        }
+
+        let head = self.snake.head();
+        let iced = self.difficulty.trail_is_deadly() && self.arena.trail.is_at(head);
+        if self.snake.is_dead() || (!self.snake.is_invincible() && (self.arena.is_blocked(head) || iced)) {
+            self.alive = false;
+            return self.observe(false);
+        }
+
+        let ate_main = self.food.is_at(head);
+        let ate_extra = self.arena.extra_food.take(head);
+        if ate_extra == Some(FoodKind::Poison) {
+            self.snake.shrink(POISON_SHRINK);
+            self.points.lose(POISON_PENALTY);
+        }
+        let ate_extra = ate_extra.filter(FoodKind::is_food);
+        let ate = ate_main || ate_extra.is_some();
+        if ate {
+            self.snake.grow();
+            self.points.register_food();
+            if ate_extra == Some(FoodKind::Golden) {
+                self.points.add(GOLDEN_FOOD_POINTS);
+                self.snake.make_invincible(GOLDEN_INVINCIBILITY);
+            }
+            if let Some(boss) = &mut self.arena.boss {
+                boss.take_hit();
+            }
+            self.arena.garden.plant_seed(head, self.difficulty.seed_chance());
+            if ate_main {
+                let arena = &self.arena;
+                self.food.relocate(&self.snake, |pos| arena.is_occupied(pos));
+            }
+            self.tails += 1;
+            self.objectives.record_food(ate_extra);
+        }
+
+        if let Some(milestone) = self.milestones.check(self.snake.length()) {
+            self.points.add(milestone.bonus);
+        }
+
+        if self.objectives.is_complete(self.snake.length(), self.level_tracker.level_time) {
+            let lines = calculate_level_completion_score(
+                self.points.level_points(),
+                self.level_tracker.level_time,
+                self.snake.length(),
+                self.points.level_best_streak,
+            );
+            let bonus: u32 = lines.iter().skip(1).map(|(_, value)| value).sum();
+            self.points.add(bonus);
+            self.points.start_level();
+            self.level_tracker.next_level();
+            self.arena.setup(self.level_tracker.level, &self.snake);
+            self.objectives.start(self.level_tracker.level, self.snake.length());
+        }
+
+        self.observe(ate)
+    }
+}
+
+// Drives a headless game over stdin/stdout, one command per line:
+//   step R   (U/D/L/R to steer, - to carry on) prints the observation after the move
+//   reset    starts a new game and prints its first observation
+pub fn run_stdio(difficulty: Difficulty) {
+    let mut game = HeadlessGame::new(difficulty);
+    let stdout = std::io::stdout();
+    let mut out = stdout.lock();
+    let _ = writeln!(out, "{}", game.observe(false).encode());
+
+    for line in std::io::stdin().lock().lines().map_while(Result::ok) {
+        let mut parts = line.split_whitespace();
+        let observation = match (parts.next(), parts.next()) {
+            (Some("reset"), _) => {
+                game = HeadlessGame::new(difficulty);
+                game.observe(false)
+            }
+            (Some("step"), dir) => game.step(dir.and_then(parse_direction).map(PlayerCommand::SetDirection)),
+            _ => {
+                let _ = writeln!(out, "error unknown command: {}", line);
+                continue;
+            }
+        };
+        let _ = writeln!(out, "{}", observation.encode());
+        let _ = out.flush();
+    }
+}
+
+// Anything else, like `-`, means no turn
+fn parse_direction(text: &str) -> Option<Direction> {
+    match text.to_ascii_uppercase().as_str() {
+        "U" => Some(Direction::Up),
+        "D" => Some(Direction::Down),
+        "L" => Some(Direction::Left),
+        "R" => Some(Direction::Right),
+        _ => None,
+    }
+}
+
+fn direction_letter(direction: Direction) -> char {
+    match direction {
+        Direction::Up => 'U',
+        Direction::Down => 'D',
+        Direction::Left => 'L',
+        Direction::Right => 'R',
+    }
+}
@@ -0,0 +1,480 @@
+use macroquad::prelude::*;
+use std::collections::BTreeMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+use crate::difficulty::Difficulty;
+
+const TABLE_SIZE: usize = 10;
+const MIN_NAME_LENGTH: usize = 3;
+const MAX_NAME_LENGTH: usize = 12;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Key {
+    Char(char),
+    Delete,
+    Done,
+}
+
+// The on-screen keyboard, ten keys to a row
+const KEYS: [Key; 40] = {
+    let chars = *b"ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789 -";
+    let mut keys = [Key::Done; 40];
+    let mut i = 0;
+    while i < chars.len() {
+        keys[i] = Key::Char(chars[i] as char);
+        i += 1;
+    }
+    keys[38] = Key::Delete;
+    keys
+};
+const ROW_LENGTH: usize = 10;
+
+#[derive(Clone, Debug)]
+pub struct HighScoreEntry {
+    pub name: String,
+    pub score: u32,
+    pub difficulty: Difficulty,
+    pub level: usize,
+    pub date: String, // YYYY-MM-DD
+}
+
+impl HighScoreEntry {
+    // A score from a run that just ended, still waiting for a name
+    pub fn new(score: u32, difficulty: Difficulty, level: usize) -> Self {
+        Self {
+            name: String::new(),
+            score,
+            difficulty,
+            level,
+            date: today(),
+        }
+    }
+
+    fn encode(&self) -> String {
+        format!("{}|{}|{}|{}|{}", self.name, self.score, self.difficulty.save_key(), self.level, self.date)
+    }
+
+    fn decode(value: &str) -> Option<Self> {
+        let mut parts = value.split('|');
+        Some(Self {
+            name: parts.next()?.to_string(),
+            score: parts.next()?.parse().ok()?,
+            difficulty: Difficulty::from_save_key(parts.next()?),
+            level: parts.next()?.parse().ok()?,
+            date: parts.next()?.to_string(),
+        })
+    }
+}
+
+// Best runs on this profile, highest score first
+pub struct HighScoreTable {
+    entries: Vec<HighScoreEntry>,
+}
+
+impl HighScoreTable {
+    pub fn new() -> Self {
+        Self { entries: Vec::new() }
+    }
+
+    pub fn qualifies(&self, score: u32) -> bool {
+        score > 0 && (self.entries.len() < TABLE_SIZE || self.entries.last().is_some_and(|last| score > last.score))
+    }
+
+    // Returns where the entry landed, for highlighting
+    pub fn insert(&mut self, entry: HighScoreEntry) -> Option<usize> {
+        let index = self.entries.iter().position(|other| entry.score > other.score).unwrap_or(self.entries.len());
+        self.entries.insert(index, entry);
+        self.entries.truncate(TABLE_SIZE);
+        (index < TABLE_SIZE).then_some(index)
+    }
+
+    pub fn write_lines(&self, out: &mut String) {
+        for entry in &self.entries {
+            out.push_str(&format!("high_score={}\n", entry.encode()));
+        }
+    }
+
+    // `value` is `name|score|difficulty|level|date`
+    pub fn read_line(&mut self, value: &str) {
+        if let Some(entry) = HighScoreEntry::decode(value) {
+            self.insert(entry);
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct LevelScore {
+    pub name: String,
+    pub score: u32, // Points earned on the level, clear bonus included
+    pub difficulty: Difficulty,
+    pub length: usize,
+    pub date: String,
+}
+
+impl LevelScore {
+    pub fn new(name: &str, score: u32, difficulty: Difficulty, length: usize) -> Self {
+        Self {
+            name: name.to_string(),
+            score,
+            difficulty,
+            length,
+            date: today(),
+        }
+    }
+
+    fn decode(value: &str) -> Option<(usize, Self)> {
+        let mut parts = value.split('|');
+        let level = parts.next()?.parse().ok()?;
+        let entry = Self {
+            name: parts.next()?.to_string(),
+            score: parts.next()?.parse().ok()?,
+            difficulty: Difficulty::from_save_key(parts.next()?),
+            length: parts.next()?.parse().ok()?,
+            date: parts.next()?.to_string(),
+        };
+        Some((level, entry))
+    }
+}
+
+// Best clears of each level on this profile, highest score first
+pub struct LevelLeaderboard {
+    levels: BTreeMap<usize, Vec<LevelScore>>,
+}
+
+impl LevelLeaderboard {
+    pub fn new() -> Self {
+        Self { levels: BTreeMap::new() }
+    }
+
+    pub fn record(&mut self, level: usize, entry: LevelScore) {
+        let scores = self.levels.entry(level).or_default();
+        let index = scores.iter().position(|other| entry.score > other.score).unwrap_or(scores.len());
+        scores.insert(index, entry);
+        scores.truncate(TABLE_SIZE);
+    }
+
+    // Each level's table, lowest level first
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &[LevelScore])> {
+        self.levels.iter().map(|(level, scores)| (*level, scores.as_slice()))
+    }
+
+    // Levels with at least one clear, lowest first
+    #[cfg(feature = "leaderboard")]
+    fn levels(&self) -> Vec<usize> {
+        self.levels.keys().copied().collect()
+    }
+
+    pub fn write_lines(&self, out: &mut String) {
+        for (level, scores) in &self.levels {
+            for entry in scores {
+                out.push_str(&format!(
+                    "level_score={}|{}|{}|{}|{}|{}\n",
+                    level,
+                    entry.name,
+                    entry.score,
+                    entry.difficulty.save_key(),
+                    entry.length,
+                    entry.date
+                ));
+            }
+        }
+    }
+
+    // `value` is `level|name|score|difficulty|length|date`
+    pub fn read_line(&mut self, value: &str) {
+        if let Some((level, entry)) = LevelScore::decode(value) {
+            self.record(level, entry);
+        }
+    }
+}
+
+// The run table and one tab per level, LEFT and RIGHT to switch. Scores are still
+// recorded without the `leaderboard` feature, there's just no screen to show them.
+pub struct HighScoreScreen {
+    pub showing: bool,
+    #[cfg(feature = "leaderboard")]
+    tab: usize, // 0 is whole runs, anything else is that level
+    #[cfg(feature = "leaderboard")]
+    highlight: Option<usize>, // Row just added to the run table
+}
+
+#[cfg(feature = "leaderboard")]
+impl HighScoreScreen {
+    pub fn new() -> Self {
+        Self {
+            showing: false,
+            tab: 0,
+            highlight: None,
+        }
+    }
+
+    pub fn open(&mut self, highlight: Option<usize>) {
+        self.showing = true;
+        self.tab = 0;
+        self.highlight = highlight;
+    }
+
+    pub fn update(&mut self, levels: &LevelLeaderboard) {
+        let mut tabs = vec![0];
+        tabs.extend(levels.levels());
+        let current = tabs.iter().position(|tab| *tab == self.tab).unwrap_or(0);
+
+        if is_key_pressed(KeyCode::Space) || is_key_pressed(KeyCode::Escape) || is_key_pressed(KeyCode::K) {
+            self.showing = false;
+            self.highlight = None;
+        } else if is_key_pressed(KeyCode::Right) {
+            self.tab = tabs[(current + 1) % tabs.len()];
+        } else if is_key_pressed(KeyCode::Left) {
+            self.tab = tabs[(current + tabs.len() - 1) % tabs.len()];
+        }
+    }
+
+    pub fn draw(&self, runs: &HighScoreTable, levels: &LevelLeaderboard) {
+        draw_rectangle(0.0, 0.0, screen_width(), screen_height(), Color::new(0.0, 0.0, 0.0, 0.9));
+        draw_centered(&t!("high-scores-title"), 70.0, 48.0, GOLD);
+
+        let (tab_name, headings, rows): (String, [String; 5], Vec<Row>) = if self.tab == 0 {
+            let rows = runs
+                .entries
+                .iter()
+                .enumerate()
+                .map(|(index, entry)| {
+                    let cells = [
+                        format!("{}.", index + 1),
+                        entry.name.clone(),
+                        entry.score.to_string(),
+                        entry.level.to_string(),
+                        entry.date.clone(),
+                    ];
+                    (cells, entry.difficulty)
+                })
+                .collect();
+            (t!("high-scores-runs"), column_headings("high-scores-level"), rows)
+        } else {
+            let rows = levels
+                .levels
+                .get(&self.tab)
+                .map(|scores| {
+                    scores
+                        .iter()
+                        .enumerate()
+                        .map(|(index, entry)| {
+                            let cells = [
+                                format!("{}.", index + 1),
+                                entry.name.clone(),
+                                entry.score.to_string(),
+                                entry.length.to_string(),
+                                entry.date.clone(),
+                            ];
+                            (cells, entry.difficulty)
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            (t!("high-scores-level-tab", level = self.tab), column_headings("high-scores-length"), rows)
+        };
+        let more_tabs = !levels.levels.is_empty();
+        let tab_text = if more_tabs { format!("< {} >", tab_name) } else { tab_name };
+        draw_centered(&tab_text, 105.0, 26.0, SKYBLUE);
+
+        if rows.is_empty() {
+            draw_centered(&t!("high-scores-empty"), screen_height() / 2.0, 28.0, GRAY);
+        }
+
+        let left = screen_width() / 2.0 - 300.0;
+        let columns = [0.0, 50.0, 260.0, 370.0, 480.0];
+        for (heading, x) in headings.iter().zip(columns) {
+            draw_text(heading, left + x, 140.0, 20.0, GRAY);
+        }
+        for (index, (cells, difficulty)) in rows.iter().enumerate() {
+            let y = 175.0 + index as f32 * 32.0;
+            let highlighted = self.tab == 0 && self.highlight == Some(index);
+            let color = if highlighted { YELLOW } else { WHITE };
+            for (cell, x) in cells.iter().zip(columns) {
+                draw_text(cell, left + x, y, 24.0, color);
+            }
+            // Difficulty colour marks what the score was set on
+            draw_circle(left + 250.0, y - 7.0, 5.0, difficulty.color());
+        }
+
+        let help = if more_tabs { t!("high-scores-tabs-help") } else { t!("high-scores-dismiss") };
+        draw_centered(&help, screen_height() - 30.0, 20.0, GRAY);
+    }
+}
+
+#[cfg(not(feature = "leaderboard"))]
+impl HighScoreScreen {
+    pub fn new() -> Self {
+        Self { showing: false }
+    }
+
+    pub fn open(&mut self, _highlight: Option<usize>) {}
+
+    pub fn update(&mut self, _levels: &LevelLeaderboard) {}
+
+    pub fn draw(&self, _runs: &HighScoreTable, _levels: &LevelLeaderboard) {}
+}
+
+// One line of a table, with the difficulty it was set on
+#[cfg(feature = "leaderboard")]
+type Row = ([String; 5], Difficulty);
+
+// The fourth column is the level reached for whole runs, the snake's length for single levels
+#[cfg(feature = "leaderboard")]
+fn column_headings(fourth: &str) -> [String; 5] {
+    [
+        t!("high-scores-rank"),
+        t!("high-scores-name"),
+        t!("high-scores-score"),
+        t!(fourth),
+        t!("high-scores-date"),
+    ]
+}
+
+// Arcade-style name entry after a run makes the table
+pub struct NameEntry {
+    pub showing: bool,
+    name: String,
+    selected: usize, // Index into KEYS
+    pending: Option<HighScoreEntry>,
+}
+
+impl NameEntry {
+    pub fn new() -> Self {
+        Self {
+            showing: false,
+            name: String::new(),
+            selected: 0,
+            pending: None,
+        }
+    }
+
+    // Starts from the profile name, which is usually what people want
+    pub fn open(&mut self, entry: HighScoreEntry, default_name: &str) {
+        self.showing = true;
+        self.name = default_name
+            .to_uppercase()
+            .chars()
+            .filter(|c| KEYS.contains(&Key::Char(*c)))
+            .take(MAX_NAME_LENGTH)
+            .collect();
+        self.selected = KEYS.len() - 1;
+        self.pending = Some(entry);
+        // Drop whatever was typed before the screen opened
+        while get_char_pressed().is_some() {}
+    }
+
+    // Arrow keys move around the keyboard and ENTER presses the key. Letters can also
+    // be typed straight in. Returns the named entry once it's done.
+    pub fn update(&mut self) -> Option<HighScoreEntry> {
+        let rows = KEYS.len() / ROW_LENGTH;
+        let (row, column) = (self.selected / ROW_LENGTH, self.selected % ROW_LENGTH);
+        if is_key_pressed(KeyCode::Left) {
+            self.selected = row * ROW_LENGTH + (column + ROW_LENGTH - 1) % ROW_LENGTH;
+        } else if is_key_pressed(KeyCode::Right) {
+            self.selected = row * ROW_LENGTH + (column + 1) % ROW_LENGTH;
+        } else if is_key_pressed(KeyCode::Up) {
+            self.selected = (row + rows - 1) % rows * ROW_LENGTH + column;
+        } else if is_key_pressed(KeyCode::Down) {
+            self.selected = (row + 1) % rows * ROW_LENGTH + column;
+        }
+
+        if is_key_pressed(KeyCode::Backspace) {
+            return self.press(Key::Delete);
+        }
+        if is_key_pressed(KeyCode::Enter) {
+            return self.press(KEYS[self.selected]);
+        }
+        while let Some(c) = get_char_pressed() {
+            let c = c.to_ascii_uppercase();
+            if KEYS.contains(&Key::Char(c)) {
+                self.press(Key::Char(c));
+            }
+        }
+        None
+    }
+
+    fn press(&mut self, key: Key) -> Option<HighScoreEntry> {
+        match key {
+            Key::Char(c) if self.name.len() < MAX_NAME_LENGTH => self.name.push(c),
+            Key::Char(_) => {}
+            Key::Delete => {
+                self.name.pop();
+            }
+            Key::Done if self.name.trim().len() >= MIN_NAME_LENGTH => {
+                self.showing = false;
+                let mut entry = self.pending.take()?;
+                entry.name = self.name.trim().to_string();
+                return Some(entry);
+            }
+            Key::Done => {}
+        }
+        None
+    }
+
+    pub fn draw(&self) {
+        draw_rectangle(0.0, 0.0, screen_width(), screen_height(), Color::new(0.0, 0.0, 0.0, 0.9));
+        let center_y = screen_height() / 2.0;
+        draw_centered(&t!("name-entry-title"), center_y - 180.0, 44.0, GOLD);
+        if let Some(entry) = &self.pending {
+            draw_centered(&t!("name-entry-score", score = entry.score), center_y - 140.0, 26.0, WHITE);
+        }
+
+        // Underscores show how much room is left
+        let shown: String = self.name.chars().chain(std::iter::repeat('_')).take(MAX_NAME_LENGTH).collect();
+        draw_centered(&shown, center_y - 85.0, 40.0, YELLOW);
+
+        let key_size = 44.0;
+        let left = (screen_width() - key_size * ROW_LENGTH as f32) / 2.0;
+        for (index, key) in KEYS.iter().enumerate() {
+            let x = left + (index % ROW_LENGTH) as f32 * key_size;
+            let y = center_y - 50.0 + (index / ROW_LENGTH) as f32 * key_size;
+            let label = match key {
+                Key::Char(' ') => "SPC".to_string(),
+                Key::Char(c) => c.to_string(),
+                Key::Delete => "DEL".to_string(),
+                Key::Done => "END".to_string(),
+            };
+            let selected = index == self.selected;
+            if selected {
+                draw_rectangle(x + 2.0, y + 2.0, key_size - 4.0, key_size - 4.0, Color::new(0.2, 0.4, 0.2, 1.0));
+            }
+            draw_rectangle_lines(x + 2.0, y + 2.0, key_size - 4.0, key_size - 4.0, 1.0, if selected { GREEN } else { GRAY });
+            let size = if label.len() > 1 { 16.0 } else { 24.0 };
+            let width = measure_text(&label, None, size as u16, 1.0).width;
+            draw_text(&label, x + (key_size - width) / 2.0, y + key_size / 2.0 + size / 3.0, size, WHITE);
+        }
+
+        let help = if self.name.trim().len() < MIN_NAME_LENGTH {
+            t!("name-entry-too-short", min = MIN_NAME_LENGTH)
+        } else {
+            t!("name-entry-help")
+        };
+        draw_centered(&help, center_y + 160.0, 20.0, GRAY);
+    }
+}
+
+// Today's date from the system clock, in UTC
+pub fn today() -> String {
+    let seconds = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |time| time.as_secs());
+    let (year, month, day) = civil_from_days((seconds / 86_400) as i64);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+// Days since 1970-01-01 to a calendar date (Howard Hinnant's algorithm)
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+fn draw_centered(text: &str, y: f32, size: f32, color: Color) {
+    let width = measure_text(text, None, size as u16, 1.0).width;
+    draw_text(text, (screen_width() - width) / 2.0, y, size, color);
+}
@@ -0,0 +1,272 @@
+use macroquad::prelude::*;
+use std::collections::VecDeque;
+use crate::arena::Arena;
+use crate::difficulty::Difficulty;
+use crate::grid::is_within_grid;
+use crate::highscores::today;
+use crate::snake::Snake;
+
+// Oldest runs drop off the log past this many
+const MAX_RECORDS: usize = 100;
+const ROWS_SHOWN: usize = 12;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum RunMode {
+    Campaign,
+    TimeAttack,
+}
+
+impl RunMode {
+    pub fn save_key(&self) -> &'static str {
+        match self {
+            RunMode::Campaign => "campaign",
+            RunMode::TimeAttack => "time_attack",
+        }
+    }
+
+    pub fn from_save_key(key: &str) -> RunMode {
+        match key {
+            "time_attack" => RunMode::TimeAttack,
+            _ => RunMode::Campaign,
+        }
+    }
+}
+
+// What finished the run off
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum RunEnd {
+    Wall,
+    Tail,
+    Obstacle,
+    Ice,
+    Cleared, // A time attack level finished
+}
+
+impl RunEnd {
+    pub fn save_key(&self) -> &'static str {
+        match self {
+            RunEnd::Wall => "wall",
+            RunEnd::Tail => "tail",
+            RunEnd::Obstacle => "obstacle",
+            RunEnd::Ice => "ice",
+            RunEnd::Cleared => "cleared",
+        }
+    }
+
+    // Works out what the snake just crashed into
+    pub fn for_crash(snake: &Snake, arena: &Arena) -> RunEnd {
+        let head = snake.head();
+        if !is_within_grid(head.x, head.y) {
+            RunEnd::Wall
+        } else if snake.is_dead() {
+            RunEnd::Tail
+        } else if arena.is_blocked(head) {
+            RunEnd::Obstacle
+        } else {
+            RunEnd::Ice
+        }
+    }
+
+    pub fn from_save_key(key: &str) -> RunEnd {
+        match key {
+            "wall" => RunEnd::Wall,
+            "obstacle" => RunEnd::Obstacle,
+            "ice" => RunEnd::Ice,
+            "cleared" => RunEnd::Cleared,
+            _ => RunEnd::Tail,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct RunRecord {
+    pub date: String, // YYYY-MM-DD
+    pub mode: RunMode,
+    pub difficulty: Difficulty,
+    pub score: u32,
+    pub level: usize,
+    pub ending: RunEnd,
+    pub seconds: u32, // Time spent playing, pauses left out
+}
+
+impl RunRecord {
+    pub fn new(mode: RunMode, difficulty: Difficulty, score: u32, level: usize, ending: RunEnd, seconds: f32) -> Self {
+        Self {
+            date: today(),
+            mode,
+            difficulty,
+            score,
+            level,
+            ending,
+            seconds: seconds as u32,
+        }
+    }
+
+    fn encode(&self) -> String {
+        format!(
+            "{}|{}|{}|{}|{}|{}|{}",
+            self.date,
+            self.mode.save_key(),
+            self.difficulty.save_key(),
+            self.score,
+            self.level,
+            self.ending.save_key(),
+            self.seconds
+        )
+    }
+
+    fn decode(value: &str) -> Option<Self> {
+        let mut parts = value.split('|');
+        Some(Self {
+            date: parts.next()?.to_string(),
+            mode: RunMode::from_save_key(parts.next()?),
+            difficulty: Difficulty::from_save_key(parts.next()?),
+            score: parts.next()?.parse().ok()?,
+            level: parts.next()?.parse().ok()?,
+            ending: RunEnd::from_save_key(parts.next()?),
+            seconds: parts.next()?.parse().ok()?,
+        })
+    }
+}
+
+// Every finished run on this profile, newest first
+pub struct RunHistory {
+    records: VecDeque<RunRecord>,
+}
+
+impl RunHistory {
+    pub fn new() -> Self {
+        Self { records: VecDeque::new() }
+    }
+
+    pub fn record(&mut self, record: RunRecord) {
+        self.records.push_front(record);
+        self.records.truncate(MAX_RECORDS);
+    }
+
+    pub fn write_lines(&self, out: &mut String) {
+        for record in &self.records {
+            out.push_str(&format!("run={}\n", record.encode()));
+        }
+    }
+
+    // `value` is `date|mode|difficulty|score|level|ending|seconds`. Lines are written
+    // newest first, so each one read goes on the end.
+    pub fn read_line(&mut self, value: &str) {
+        if let Some(record) = RunRecord::decode(value) {
+            if self.records.len() < MAX_RECORDS {
+                self.records.push_back(record);
+            }
+        }
+    }
+}
+
+// The run log, narrowed down by mode (M) and difficulty (D), scrolled with UP and DOWN
+pub struct HistoryScreen {
+    pub showing: bool,
+    mode: Option<RunMode>,
+    difficulty: Option<Difficulty>,
+    scroll: usize,
+}
+
+impl HistoryScreen {
+    pub fn new() -> Self {
+        Self {
+            showing: false,
+            mode: None,
+            difficulty: None,
+            scroll: 0,
+        }
+    }
+
+    pub fn open(&mut self) {
+        self.showing = true;
+        self.scroll = 0;
+    }
+
+    pub fn update(&mut self, history: &RunHistory) {
+        let shown = self.filtered(history).len();
+        if is_key_pressed(KeyCode::Space) || is_key_pressed(KeyCode::Escape) || is_key_pressed(KeyCode::Y) {
+            self.showing = false;
+        } else if is_key_pressed(KeyCode::M) {
+            self.mode = match self.mode {
+                None => Some(RunMode::Campaign),
+                Some(RunMode::Campaign) => Some(RunMode::TimeAttack),
+                Some(RunMode::TimeAttack) => None,
+            };
+            self.scroll = 0;
+        } else if is_key_pressed(KeyCode::D) {
+            // All, then each difficulty in turn, then back to all
+            self.difficulty = match self.difficulty {
+                None => Some(Difficulty::Easy),
+                Some(Difficulty::Insane) => None,
+                Some(difficulty) => Some(difficulty.next()),
+            };
+            self.scroll = 0;
+        } else if is_key_pressed(KeyCode::Down) && self.scroll + ROWS_SHOWN < shown {
+            self.scroll += 1;
+        } else if is_key_pressed(KeyCode::Up) {
+            self.scroll = self.scroll.saturating_sub(1);
+        }
+    }
+
+    fn filtered<'a>(&self, history: &'a RunHistory) -> Vec<&'a RunRecord> {
+        history
+            .records
+            .iter()
+            .filter(|record| self.mode.is_none_or(|mode| record.mode == mode))
+            .filter(|record| self.difficulty.is_none_or(|difficulty| record.difficulty == difficulty))
+            .collect()
+    }
+
+    pub fn draw(&self, history: &RunHistory) {
+        draw_rectangle(0.0, 0.0, screen_width(), screen_height(), Color::new(0.0, 0.0, 0.0, 0.9));
+        draw_centered(&t!("history-title"), 70.0, 48.0, SKYBLUE);
+
+        let mode = match self.mode {
+            None => t!("history-all"),
+            Some(mode) => t!(&format!("history-mode-{}", mode.save_key())),
+        };
+        let difficulty = match self.difficulty {
+            None => t!("history-all"),
+            Some(difficulty) => t!(&format!("difficulty-{}", difficulty.save_key())),
+        };
+        draw_centered(&t!("history-filters", mode = mode, difficulty = difficulty), 105.0, 22.0, GRAY);
+
+        let records = self.filtered(history);
+        if records.is_empty() {
+            draw_centered(&t!("history-empty"), screen_height() / 2.0, 28.0, GRAY);
+        }
+
+        let left = screen_width() / 2.0 - 340.0;
+        let columns = [0.0, 130.0, 290.0, 380.0, 470.0, 560.0];
+        let headings = ["history-date", "history-mode", "history-level", "history-score", "history-time", "history-ending"];
+        for (heading, x) in headings.iter().zip(columns) {
+            draw_text(&t!(heading), left + x, 140.0, 20.0, GRAY);
+        }
+        for (index, record) in records.iter().skip(self.scroll).take(ROWS_SHOWN).enumerate() {
+            let y = 175.0 + index as f32 * 30.0;
+            let cells = [
+                record.date.clone(),
+                t!(&format!("history-mode-{}", record.mode.save_key())),
+                record.level.to_string(),
+                record.score.to_string(),
+                format!("{}:{:02}", record.seconds / 60, record.seconds % 60),
+                t!(&format!("history-ending-{}", record.ending.save_key())),
+            ];
+            let color = if record.ending == RunEnd::Cleared { GREEN } else { WHITE };
+            for (cell, x) in cells.iter().zip(columns) {
+                draw_text(cell, left + x, y, 22.0, color);
+            }
+            // Difficulty colour marks what the run was played on
+            draw_circle(left + 280.0, y - 7.0, 5.0, record.difficulty.color());
+        }
+
+        draw_centered(&t!("history-help"), screen_height() - 30.0, 20.0, GRAY);
+    }
+}
+
+fn draw_centered(text: &str, y: f32, size: f32, color: Color) {
+    let width = measure_text(text, None, size as u16, 1.0).width;
+    draw_text(text, (screen_width() - width) / 2.0, y, size, color);
+}
@@ -0,0 +1,191 @@
+use lazy_static::lazy_static;
+#[cfg(feature = "test-hooks")]
+use std::io::BufRead;
+use std::sync::Mutex;
+use crate::arena::Arena;
+use crate::food::{Food, FoodKind};
+use crate::level::LevelTracker;
+use crate::objectives::ObjectiveTracker;
+use crate::snake::{Direction, Segment, Snake};
+use crate::timestep::set_time_scale;
+
+// Most segments one give_length can add, several times what fills the board
+const MAX_GROWTH: usize = 10_000;
+
+// The one sanctioned way for tests, tools and the debug console to poke at a running game.
+// Built alongside the console, so it's there wherever the console is.
+#[derive(Clone, PartialEq, Debug)]
+pub enum TestHook {
+    SpawnFoodAt(Segment),
+    SpawnFood(FoodKind),
+    SetSnakePath(Vec<Direction>),
+    ForceLevel(usize),
+    GrowSnake(usize),
+    ToggleGodMode,
+    SetSpeed(f32),
+}
+
+// One command either stdin or the console can send. The first name is the one autocomplete offers.
+pub struct Command {
+    pub names: &'static [&'static str],
+    pub usage: &'static str,
+    pub help: &'static str,
+    parse: fn(&[&str]) -> Option<TestHook>,
+}
+
+pub const COMMANDS: [Command; 7] = [
+    Command {
+        names: &["give_length", "grow"],
+        usage: "give_length N",
+        help: "Add N segments (up to 10000), one per move",
+        parse: |args| {
+            let segments: usize = args.first()?.parse().ok()?;
+            (1..=MAX_GROWTH).contains(&segments).then_some(TestHook::GrowSnake(segments))
+        },
+    },
+    Command {
+        names: &["goto_level", "level"],
+        usage: "goto_level N",
+        help: "Jump to level N",
+        parse: |args| Some(TestHook::ForceLevel(args.first()?.parse().ok()?)),
+    },
+    Command {
+        names: &["spawn_food"],
+        usage: "spawn_food normal|golden|poison|slow",
+        help: "Put an extra food of that kind on a free cell",
+        parse: |args| {
+            let kind = match *args.first()? {
+                "normal" => FoodKind::Normal,
+                "golden" => FoodKind::Golden,
+                "poison" => FoodKind::Poison,
+                "slow" => FoodKind::SlowMotion,
+                _ => return None,
+            };
+            Some(TestHook::SpawnFood(kind))
+        },
+    },
+    Command {
+        names: &["food"],
+        usage: "food X Y",
+        help: "Move the food to a cell",
+        parse: |args| {
+            let x = args.first()?.parse().ok()?;
+            let y = args.get(1)?.parse().ok()?;
+            Some(TestHook::SpawnFoodAt(Segment { x, y }))
+        },
+    },
+    Command {
+        names: &["path"],
+        usage: "path RRDDL",
+        help: "Queue moves for the snake (U/D/L/R, one per step)",
+        parse: |args| {
+            let path = args
+                .first()?
+                .chars()
+                .map(|c| match c.to_ascii_uppercase() {
+                    'U' => Some(Direction::Up),
+                    'D' => Some(Direction::Down),
+                    'L' => Some(Direction::Left),
+                    'R' => Some(Direction::Right),
+                    _ => None,
+                })
+                .collect::<Option<Vec<_>>>()?;
+            Some(TestHook::SetSnakePath(path))
+        },
+    },
+    Command {
+        names: &["godmode"],
+        usage: "godmode",
+        help: "Toggle never crashing, the board edges wrap meanwhile",
+        parse: |_| Some(TestHook::ToggleGodMode),
+    },
+    Command {
+        names: &["set_speed"],
+        usage: "set_speed X",
+        help: "Game speed, 1.0 is normal (slow motion and restarts reset it)",
+        parse: |args| {
+            let speed: f32 = args.first()?.parse().ok()?;
+            (0.1..=10.0).contains(&speed).then_some(TestHook::SetSpeed(speed))
+        },
+    },
+];
+
+lazy_static! {
+    static ref PENDING: Mutex<Vec<TestHook>> = Mutex::new(Vec::new());
+}
+
+pub fn send(hook: TestHook) {
+    PENDING.lock().unwrap().push(hook);
+}
+
+// Reads hooks from stdin on a background thread so a test can drive the game binary, e.g.
+//   food 10 5
+//   path RRDDL
+//   level 4
+//   grow 1000   (stress test, adds segments one per move)
+#[cfg(feature = "test-hooks")]
+pub fn listen_stdin() {
+    std::thread::spawn(|| {
+        for line in std::io::stdin().lock().lines().map_while(Result::ok) {
+            match parse(&line) {
+                Some(hook) => send(hook),
+                None => {
+                    println!("Warning: Unknown test hook: {}", line);
+                    for command in &COMMANDS {
+                        println!("  {} - {}", command.usage, command.help);
+                    }
+                }
+            }
+        }
+    });
+}
+
+// Any of a command's names works, followed by its arguments
+pub fn parse(line: &str) -> Option<TestHook> {
+    let parts: Vec<&str> = line.split_whitespace().collect();
+    let (name, args) = parts.split_first()?;
+    let command = COMMANDS.iter().find(|command| command.names.contains(name))?;
+    (command.parse)(args)
+}
+
+// Applies everything sent since the last frame
+pub fn apply_pending(
+    snake: &mut Snake,
+    food: &mut Food,
+    level_tracker: &mut LevelTracker,
+    arena: &mut Arena,
+    objectives: &mut ObjectiveTracker,
+    score: &mut u32,
+) {
+    let pending: Vec<TestHook> = PENDING.lock().unwrap().drain(..).collect();
+    for hook in pending {
+        match hook {
+            TestHook::SpawnFoodAt(cell) => {
+                food.position = cell;
+                food.boxed_in = false;
+            }
+            TestHook::SpawnFood(kind) => {
+                if !arena.spawn_food(kind, snake) {
+                    println!("Warning: No free cell for {:?} food", kind);
+                }
+            }
+            TestHook::ToggleGodMode => snake.god_mode = !snake.god_mode,
+            TestHook::SetSpeed(speed) => set_time_scale(speed),
+            TestHook::SetSnakePath(path) => snake.scripted_path = path.into(),
+            TestHook::GrowSnake(segments) => snake.grow_tail = snake.grow_tail.saturating_add(segments),
+            TestHook::ForceLevel(level) => {
+                let level = level.max(1);
+                level_tracker.level = level;
+                level_tracker.score = 0;
+                level_tracker.score_to_next = level * 5;
+                level_tracker.level_time = 0.0;
+                arena.setup(level, snake);
+                objectives.start(level, snake.length());
+                if arena.is_occupied(food.position) {
+                    food.relocate(snake, |pos| arena.is_occupied(pos));
+                }
+                *score = (level as u32 - 1) * 5;
+            }
+        }
+    }
+}
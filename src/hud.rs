@@ -0,0 +1,24 @@
+use lazy_static::lazy_static;
+use std::sync::Mutex;
+
+// How much bigger the large HUD draws its text, bars and the gaps between them
+const LARGE_SCALE: f32 = 1.4;
+
+lazy_static! {
+    // Read by every part of the HUD as it draws, so switching it takes effect at once
+    static ref LARGE_HUD: Mutex<bool> = Mutex::new(false);
+}
+
+pub fn set_large_hud(large: bool) {
+    *LARGE_HUD.lock().unwrap() = large;
+}
+
+// Multiplier for HUD sizes and positions. Everything is still measured from the
+// screen edge or centre it's anchored to, so it reflows with the window.
+pub fn hud_scale() -> f32 {
+    if *LARGE_HUD.lock().unwrap() {
+        LARGE_SCALE
+    } else {
+        1.0
+    }
+}
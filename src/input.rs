@@ -0,0 +1,89 @@
+use macroquad::prelude::*;
+use crate::controls::ControlScheme;
+use crate::snake::Direction;
+
+// Seconds allowed between the two taps of a whip turn
+const WHIP_TAP_WINDOW: f64 = 0.25;
+
+// Everything the player can ask the snake to do. Gameplay only ever sees these,
+// so keyboard, AI, or replays can all drive a snake the same way.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum PlayerCommand {
+    TurnLeft,
+    TurnRight,
+    SetDirection(Direction),
+    // Turn for one cell, then carry on in the old heading
+    Sidestep(Direction),
+    // Cut diagonally across the corner, continuing the given heading for one more cell
+    Whip(Direction),
+}
+
+// Turns key presses into commands for the current control scheme
+pub struct KeyboardInput {
+    pub controls: ControlScheme,
+    pub next_turn_right: bool, // One-button mode alternates turn sides
+    whip_tap: Option<(Direction, Direction, f64)>, // Key tapped, heading before the tap, when
+}
+
+impl KeyboardInput {
+    pub fn new(controls: ControlScheme) -> Self {
+        Self {
+            controls,
+            next_turn_right: false,
+            whip_tap: None,
+        }
+    }
+
+    // `heading` is the direction the snake is currently facing
+    pub fn poll(&mut self, heading: Direction) -> Vec<PlayerCommand> {
+        let mut commands = Vec::new();
+
+        match self.controls {
+            ControlScheme::OneButton { auto_straighten } => {
+                if is_key_pressed(KeyCode::Space) {
+                    let dir = if self.next_turn_right { heading.turned_right() } else { heading.turned_left() };
+                    commands.push(if auto_straighten {
+                        PlayerCommand::Sidestep(dir)
+                    } else if self.next_turn_right {
+                        PlayerCommand::TurnRight
+                    } else {
+                        PlayerCommand::TurnLeft
+                    });
+                    self.next_turn_right = !self.next_turn_right;
+                }
+            }
+            ControlScheme::Keys(preset) => {
+                let dirs = [Direction::Up, Direction::Down, Direction::Left, Direction::Right];
+                let pressed = preset
+                    .bindings()
+                    .iter()
+                    .flat_map(|keys| keys.iter().zip(dirs))
+                    .find(|(key, _)| is_key_pressed(**key));
+                if let Some((_, dir)) = pressed {
+                    if let Some(from) = self.detect_whip(dir, heading) {
+                        commands.push(PlayerCommand::Whip(from));
+                    }
+                    commands.push(PlayerCommand::SetDirection(dir));
+                }
+            }
+        }
+
+        commands
+    }
+
+    // Double-tapping a direction perpendicular to the heading is a whip turn
+    fn detect_whip(&mut self, pressed: Direction, heading: Direction) -> Option<Direction> {
+        let now = get_time();
+        match self.whip_tap {
+            Some((tapped, from, time)) if tapped == pressed && now - time <= WHIP_TAP_WINDOW => {
+                self.whip_tap = None;
+                Some(from)
+            }
+            _ => {
+                let perpendicular = pressed != heading && pressed != heading.opposite();
+                self.whip_tap = if perpendicular { Some((pressed, heading, now)) } else { None };
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,186 @@
+use macroquad::prelude::*;
+use macroquad::audio::Sound;
+use crate::mixer::play_sfx;
+use crate::snake::{Segment, Snake};
+use crate::tension::encode_wav;
+use crate::touch;
+
+const MAX_LATENCY_MS: u32 = 300;
+const LATENCY_STEP_MS: u32 = 10;
+const MOVE_VOLUME: f32 = 0.25;
+// Calibration plays a click this often and averages this many taps
+const BEAT_INTERVAL: f64 = 0.6;
+const TAPS_NEEDED: usize = 8;
+
+// Short, soft click played on every snake move
+pub fn move_click_wav() -> Vec<u8> {
+    let sample_rate: u32 = 22050;
+    let length = sample_rate / 40;
+
+    let samples: Vec<i16> = (0..length)
+        .map(|i| {
+            let t = i as f32 / sample_rate as f32;
+            let value = (t * 1800.0 * std::f32::consts::TAU).sin() * (-t * 160.0).exp();
+            (value * i16::MAX as f32 * 0.6) as i16
+        })
+        .collect();
+    encode_wav(sample_rate, &samples)
+}
+
+// Snake moves happen on a known timer, so their sound can be started early by the
+// player's audio latency and land on the same frame as the visual move
+pub struct MoveSound {
+    last_head: Option<Segment>,
+    played_early: bool,
+}
+
+impl MoveSound {
+    pub fn new() -> Self {
+        Self {
+            last_head: None,
+            played_early: false,
+        }
+    }
+
+    pub fn update(&mut self, snake: &Snake, latency_ms: u32, click: &Sound) {
+        let head = snake.head();
+        let moved = self.last_head.is_some_and(|last| last != head);
+        self.last_head = Some(head);
+
+        if moved {
+            // Catch up on a move that came sooner than the lead time allowed for
+            if !self.played_early {
+                play_click(click);
+            }
+            self.played_early = false;
+        }
+
+        let lead = latency_ms as f32 / 1000.0;
+        let until_move = snake.move_delay / snake.speed_multiplier - snake.move_timer;
+        if lead > 0.0 && !self.played_early && until_move <= lead {
+            play_click(click);
+            self.played_early = true;
+        }
+    }
+}
+
+fn play_click(click: &Sound) {
+    play_sfx(click, MOVE_VOLUME);
+}
+
+// Tap along to a steady click; how late the taps land on average is the audio latency
+pub struct LatencyCalibration {
+    pub showing: bool,
+    latency_ms: u32,
+    started_at: f64,
+    beats_played: u32,
+    offsets: Vec<f64>,
+}
+
+impl LatencyCalibration {
+    pub fn new() -> Self {
+        Self {
+            showing: false,
+            latency_ms: 0,
+            started_at: 0.0,
+            beats_played: 0,
+            offsets: Vec::new(),
+        }
+    }
+
+    pub fn open(&mut self, latency_ms: u32) {
+        self.showing = true;
+        self.latency_ms = latency_ms;
+        self.restart();
+    }
+
+    fn restart(&mut self) {
+        self.started_at = get_time();
+        self.beats_played = 0;
+        self.offsets.clear();
+    }
+
+    // Returns the latency to keep once the player confirms it
+    pub fn update(&mut self, click: Option<&Sound>) -> Option<u32> {
+        let now = get_time();
+        let next_beat = self.started_at + BEAT_INTERVAL * (self.beats_played + 1) as f64;
+        if now >= next_beat {
+            self.beats_played += 1;
+            if let Some(click) = click {
+                play_sfx(click, 0.8);
+            }
+        }
+
+        let tapped = is_key_pressed(KeyCode::Space) || touch::tapped();
+        if tapped && self.offsets.len() < TAPS_NEEDED {
+            // Measure against whichever beat the tap is closest to
+            let beat = ((now - self.started_at) / BEAT_INTERVAL).round().max(1.0);
+            let offset = now - (self.started_at + beat * BEAT_INTERVAL);
+            if offset >= 0.0 {
+                self.offsets.push(offset);
+            }
+            if self.offsets.len() == TAPS_NEEDED {
+                self.offsets.sort_by(|a, b| a.total_cmp(b));
+                // The median shrugs off the odd early or late tap
+                let median = self.offsets[TAPS_NEEDED / 2];
+                self.latency_ms = snap((median * 1000.0) as u32);
+            }
+        }
+
+        if is_key_pressed(KeyCode::Left) {
+            self.latency_ms = self.latency_ms.saturating_sub(LATENCY_STEP_MS);
+        } else if is_key_pressed(KeyCode::Right) {
+            self.latency_ms = snap(self.latency_ms + LATENCY_STEP_MS);
+        } else if is_key_pressed(KeyCode::R) {
+            self.restart();
+        }
+
+        if is_key_pressed(KeyCode::Enter) {
+            self.showing = false;
+            return Some(self.latency_ms);
+        } else if is_key_pressed(KeyCode::Escape) {
+            self.showing = false;
+        }
+        None
+    }
+
+    pub fn draw(&self) {
+        if !self.showing {
+            return;
+        }
+
+        draw_rectangle(0.0, 0.0, screen_width(), screen_height(), Color::new(0.0, 0.0, 0.0, 0.9));
+        let center_y = screen_height() / 2.0;
+        draw_centered("AUDIO LATENCY", center_y - 160.0, 48.0, SKYBLUE);
+        draw_centered("Press SPACE in time with the clicks you hear", center_y - 110.0, 24.0, LIGHTGRAY);
+        draw_centered("(listen rather than watch - there's nothing on screen to follow)", center_y - 82.0, 18.0, GRAY);
+
+        // One dot per tap still needed
+        let dot_spacing = 30.0;
+        let left = screen_width() / 2.0 - dot_spacing * (TAPS_NEEDED - 1) as f32 / 2.0;
+        for i in 0..TAPS_NEEDED {
+            let filled = i < self.offsets.len();
+            let x = left + i as f32 * dot_spacing;
+            if filled {
+                draw_circle(x, center_y - 30.0, 10.0, GREEN);
+            } else {
+                draw_circle_lines(x, center_y - 30.0, 10.0, 2.0, GRAY);
+            }
+        }
+
+        let value = format!("{} ms", self.latency_ms);
+        draw_centered(&value, center_y + 40.0, 56.0, WHITE);
+        draw_centered("LEFT/RIGHT - Adjust by hand   R - Tap again", center_y + 100.0, 22.0, LIGHTGRAY);
+        draw_centered("ENTER - Save   ESC - Cancel", center_y + 130.0, 22.0, LIGHTGRAY);
+    }
+}
+
+// Rounds to the adjustment step and keeps within range
+fn snap(latency_ms: u32) -> u32 {
+    ((latency_ms + LATENCY_STEP_MS / 2) / LATENCY_STEP_MS * LATENCY_STEP_MS).min(MAX_LATENCY_MS)
+}
+
+fn draw_centered(text: &str, y: f32, size: f32, color: Color) {
+    let width = measure_text(text, None, size as u16, 1.0).width;
+    draw_text(text, (screen_width() - width) / 2.0, y, size, color);
+}
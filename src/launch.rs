@@ -0,0 +1,51 @@
+use crate::rng::parse_seed;
+
+// Options for one desktop launch, so testers and speedrunners can skip straight to what
+// they want. None of them are saved: the next plain launch is back to the saved settings.
+//   --level N               start a run at level N instead of showing the title
+//   --seed N | daily        play every run from this seed
+//   --windowed/--fullscreen override the saved window mode
+//   --mute                  silence all music and sound
+//   --skip-intro            no controls guide, tutorial or level intro cards
+pub struct LaunchOptions {
+    pub level: Option<usize>,
+    pub seed: Option<u64>,
+    pub fullscreen: Option<bool>,
+    pub mute: bool,
+    pub skip_intro: bool,
+}
+
+impl LaunchOptions {
+    pub fn parse(args: &[String]) -> Self {
+        let value_of = |flag: &str| args.iter().position(|arg| arg == flag).and_then(|index| args.get(index + 1));
+
+        let level = value_of("--level").and_then(|text| match text.parse::<usize>() {
+            Ok(level) if level >= 1 => Some(level),
+            _ => {
+                println!("Warning: Ignoring --level {}, expected a level number from 1", text);
+                None
+            }
+        });
+        let seed = value_of("--seed").and_then(|text| {
+            let seed = parse_seed(text);
+            if seed.is_none() {
+                println!("Warning: Ignoring seed {}, expected a number or daily", text);
+            }
+            seed
+        });
+        // Whichever comes last wins if both are given
+        let fullscreen = args.iter().rev().find_map(|arg| match arg.as_str() {
+            "--fullscreen" => Some(true),
+            "--windowed" => Some(false),
+            _ => None,
+        });
+
+        Self {
+            level,
+            seed,
+            fullscreen,
+            mute: args.iter().any(|arg| arg == "--mute"),
+            skip_intro: args.iter().any(|arg| arg == "--skip-intro"),
+        }
+    }
+}
@@ -0,0 +1,128 @@
+use macroquad::prelude::*;
+use std::fs;
+#[cfg(debug_assertions)]
+use std::time::SystemTime;
+use crate::grid::{CELL_SIZE, GRID_HEIGHT, GRID_WIDTH, get_offset, is_within_grid};
+use crate::snake::{Segment, Snake};
+
+const LAYOUT_DIR: &str = "assets/levels";
+// Cells kept clear in front of the snake's head, so a layout can't kill it the moment a level starts
+const SAFE_LANE: i32 = 4;
+// Seconds between checks for an edited layout file
+#[cfg(debug_assertions)]
+const RELOAD_CHECK: f32 = 0.5;
+
+// Fixed walls read from assets/levels/level_<n>.txt, one character per cell:
+// `#` is a wall, anything else is open floor, and lines starting with `;` are comments
+pub struct WallLayout {
+    cells: Vec<bool>,
+    path: String,
+    #[cfg(debug_assertions)]
+    modified: Option<SystemTime>,
+    #[cfg(debug_assertions)]
+    check_timer: f32,
+}
+
+impl WallLayout {
+    pub fn new() -> Self {
+        Self {
+            cells: vec![false; (GRID_WIDTH * GRID_HEIGHT) as usize],
+            path: String::new(),
+            #[cfg(debug_assertions)]
+            modified: None,
+            #[cfg(debug_assertions)]
+            check_timer: 0.0,
+        }
+    }
+
+    // Levels without a layout file have no fixed walls
+    pub fn setup(&mut self, level: usize, snake: &Snake) {
+        self.path = format!("{}/level_{}.txt", LAYOUT_DIR, level);
+        self.load(snake);
+    }
+
+    fn load(&mut self, snake: &Snake) {
+        self.cells.iter_mut().for_each(|cell| *cell = false);
+        #[cfg(debug_assertions)]
+        {
+            self.modified = modified_time(&self.path);
+        }
+
+        let contents = match fs::read_to_string(&self.path) {
+            Ok(contents) => contents,
+            Err(_) => return,
+        };
+
+        let mut outside = false;
+        let rows = contents.lines().filter(|line| !line.starts_with(';'));
+        for (y, row) in rows.enumerate() {
+            for (x, symbol) in row.chars().enumerate() {
+                if symbol != '#' {
+                    continue;
+                }
+                let (x, y) = (x as i32, y as i32);
+                if !is_within_grid(x, y) {
+                    outside = true;
+                    continue;
+                }
+                let cell = Segment { x, y };
+                if !snake.is_at(cell) && !in_safe_lane(snake, cell) {
+                    self.cells[(y * GRID_WIDTH + x) as usize] = true;
+                }
+            }
+        }
+
+        if outside {
+            println!("Warning: {} has walls outside the {}x{} board, they were left out", self.path, GRID_WIDTH, GRID_HEIGHT);
+        }
+    }
+
+    // Debug builds pick up edits to the layout file while the level is running
+    #[cfg(debug_assertions)]
+    pub fn update(&mut self, delta_time: f32, snake: &Snake) {
+        self.check_timer += delta_time;
+        if self.check_timer < RELOAD_CHECK || self.path.is_empty() {
+            return;
+        }
+        self.check_timer = 0.0;
+
+        if modified_time(&self.path) != self.modified {
+            println!("Reloading {}", self.path);
+            self.load(snake);
+        }
+    }
+
+    #[cfg(not(debug_assertions))]
+    pub fn update(&mut self, _delta_time: f32, _snake: &Snake) {}
+
+    pub fn is_wall(&self, position: Segment) -> bool {
+        is_within_grid(position.x, position.y)
+            && self.cells[(position.y * GRID_WIDTH + position.x) as usize]
+    }
+
+    pub fn draw(&self) {
+        let offset = get_offset();
+
+        for y in 0..GRID_HEIGHT {
+            for x in 0..GRID_WIDTH {
+                if !self.cells[(y * GRID_WIDTH + x) as usize] {
+                    continue;
+                }
+                let (px, py) = (offset.x + x as f32 * CELL_SIZE, offset.y + y as f32 * CELL_SIZE);
+                draw_rectangle(px, py, CELL_SIZE, CELL_SIZE, LIGHTGRAY);
+                draw_rectangle_lines(px, py, CELL_SIZE, CELL_SIZE, 2.0, GRAY);
+            }
+        }
+    }
+}
+
+fn in_safe_lane(snake: &Snake, cell: Segment) -> bool {
+    let head = snake.head();
+    let (dx, dy) = snake.dir.delta();
+    (1..=SAFE_LANE).any(|i| cell == Segment { x: head.x + dx * i, y: head.y + dy * i })
+}
+
+#[cfg(debug_assertions)]
+fn modified_time(path: &str) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
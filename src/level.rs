@@ -1,10 +1,331 @@
 use macroquad::prelude::*;
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+use std::fs;
+use std::sync::Mutex;
+use crate::difficulty::Difficulty;
+#[cfg(debug_assertions)]
+use std::time::SystemTime;
+
+pub const BOSS_LEVEL: usize = 10;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum SpecialMechanic {
+    MovingFood,
+    MultipleFoods,
+    GravityWells,
+    SpeedZones,
+    MovingWalls,
+    Teleporters,
+    WrapAround,
+    Trail,
+    Boss,
+}
+
+impl SpecialMechanic {
+    // As written in the levels file
+    pub fn name(&self) -> &'static str {
+        match self {
+            SpecialMechanic::MovingFood => "moving_food",
+            SpecialMechanic::MultipleFoods => "multiple_foods",
+            SpecialMechanic::GravityWells => "gravity_wells",
+            SpecialMechanic::SpeedZones => "speed_zones",
+            SpecialMechanic::MovingWalls => "moving_walls",
+            SpecialMechanic::Teleporters => "teleporters",
+            SpecialMechanic::WrapAround => "wrap_around",
+            SpecialMechanic::Trail => "trail",
+            SpecialMechanic::Boss => "boss",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<SpecialMechanic> {
+        match name {
+            "moving_food" => Some(SpecialMechanic::MovingFood),
+            "multiple_foods" => Some(SpecialMechanic::MultipleFoods),
+            "gravity_wells" => Some(SpecialMechanic::GravityWells),
+            "speed_zones" => Some(SpecialMechanic::SpeedZones),
+            "moving_walls" => Some(SpecialMechanic::MovingWalls),
+            "teleporters" => Some(SpecialMechanic::Teleporters),
+            "wrap_around" => Some(SpecialMechanic::WrapAround),
+            "trail" => Some(SpecialMechanic::Trail),
+            "boss" => Some(SpecialMechanic::Boss),
+            _ => None,
+        }
+    }
+}
+
+// Level tuning lives in assets/levels/levels.txt, so it can be changed without rebuilding
+const LEVELS_PATH: &str = "assets/levels/levels.txt";
+// Built-in copy, used when the file is missing
+const BUILT_IN: &str = include_str!("../assets/levels/levels.txt");
+// Foods that clear a level when the file doesn't say
+const DEFAULT_FOODS: u32 = 5;
+// Seconds between checks for an edited levels file
+#[cfg(debug_assertions)]
+const RELOAD_CHECK: f32 = 0.5;
+
+// One level's settings, worked out when the levels file is read so lookups during
+// play don't go back to the text
+#[derive(Clone, Debug)]
+struct LevelDefinition {
+    mechanics: Vec<SpecialMechanic>,
+    foods: u32,
+    speed: f32,
+    speed_curve: SpeedCurve,
+    speed_increase: Option<f32>, // None for the difficulty's own climb
+}
+
+impl LevelDefinition {
+    // `setting` finds a named value for this level in the levels file
+    fn new<'a>(setting: impl Fn(&str) -> Option<&'a String>) -> Self {
+        let mechanics = setting("mechanics")
+            .map(|names| names.split_whitespace().filter_map(SpecialMechanic::from_name).collect())
+            .unwrap_or_default();
+        let number = |name: &str| {
+            setting(name)
+                .and_then(|value| value.parse::<f32>().ok())
+                .filter(|value| *value >= 0.0)
+        };
+        // Anything left out falls back to the logarithmic ramp the game has always used
+        let speed_curve = SpeedCurve {
+            shape: setting("speed_curve")
+                .and_then(|name| CurveShape::from_name(name))
+                .unwrap_or(CurveShape::Logarithmic),
+            start: number("speed_start").filter(|start| *start > 0.0).unwrap_or(1.0),
+            increase: 0.0, // Filled in for the difficulty when it's asked for
+            max: number("speed_max").filter(|max| *max > 0.0),
+            step_every: number("speed_step").map_or(5, |step| step as usize),
+        };
+        Self {
+            mechanics,
+            foods: setting("foods").and_then(|value| value.parse().ok()).unwrap_or(DEFAULT_FOODS),
+            speed: setting("speed")
+                .and_then(|value| value.parse::<f32>().ok())
+                .filter(|speed| *speed > 0.0)
+                .unwrap_or(1.0),
+            speed_curve,
+            speed_increase: number("speed_increase"),
+        }
+    }
+}
+
+struct LevelTable {
+    levels: HashMap<usize, LevelDefinition>, // Levels the file names on their own
+    endings: Vec<LevelDefinition>, // Every other level, by its last digit
+    #[cfg(debug_assertions)]
+    modified: Option<SystemTime>,
+    #[cfg(debug_assertions)]
+    check_timer: f32,
+}
+
+lazy_static! {
+    // Shared so every mechanic can ask about a level without the table being passed around
+    static ref LEVELS: Mutex<LevelTable> = Mutex::new(LevelTable::new(BUILT_IN));
+}
+
+impl LevelTable {
+    fn new(source: &str) -> Self {
+        let mut table = Self {
+            levels: HashMap::new(),
+            endings: Vec::new(),
+            #[cfg(debug_assertions)]
+            modified: None,
+            #[cfg(debug_assertions)]
+            check_timer: 0.0,
+        };
+        table.read(source);
+        table
+    }
+
+    fn read(&mut self, source: &str) {
+        let settings = parse(source);
+        self.endings = (0..10)
+            .map(|digit| LevelDefinition::new(|name| settings.get(&format!("ending_{}.{}", digit, name))))
+            .collect();
+        self.levels = settings
+            .keys()
+            .filter_map(|key| key.strip_prefix("level_")?.split_once('.')?.0.parse::<usize>().ok())
+            .map(|level| {
+                let definition = LevelDefinition::new(|name| {
+                    settings
+                        .get(&format!("level_{}.{}", level, name))
+                        .or_else(|| settings.get(&format!("ending_{}.{}", level % 10, name)))
+                });
+                (level, definition)
+            })
+            .collect();
+    }
+
+    fn definition(&self, level: usize) -> &LevelDefinition {
+        self.levels.get(&level).unwrap_or(&self.endings[level % 10])
+    }
+}
+
+// Reads the levels file over the built-in table
+pub fn load() {
+    let mut table = LEVELS.lock().unwrap();
+    #[cfg(debug_assertions)]
+    {
+        table.modified = modified_time();
+    }
+    match fs::read_to_string(LEVELS_PATH) {
+        Ok(source) => table.read(&source),
+        Err(_) => {
+            println!("Warning: Could not load {}, using the built-in fallback", LEVELS_PATH);
+            table.read(BUILT_IN);
+        }
+    }
+}
+
+// Debug builds pick up edits to the levels file while the game is running.
+// Changes take effect from the next level that starts.
+#[cfg(debug_assertions)]
+pub fn watch(delta_time: f32) {
+    let changed = {
+        let mut table = LEVELS.lock().unwrap();
+        table.check_timer += delta_time;
+        if table.check_timer < RELOAD_CHECK {
+            return;
+        }
+        table.check_timer = 0.0;
+        modified_time() != table.modified
+    };
+    if changed {
+        println!("Reloading {}", LEVELS_PATH);
+        load();
+    }
+}
+
+#[cfg(not(debug_assertions))]
+pub fn watch(_delta_time: f32) {}
+
+#[cfg(debug_assertions)]
+fn modified_time() -> Option<SystemTime> {
+    fs::metadata(LEVELS_PATH).and_then(|metadata| metadata.modified()).ok()
+}
+
+// `key = value` lines, with `;` comments and blank lines skipped. Mechanics and speed
+// curves the game doesn't know are pointed out here, once, and left out of the level.
+fn parse(source: &str) -> HashMap<String, String> {
+    let mut settings = HashMap::new();
+    for line in source.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with(';') {
+            continue;
+        }
+        match line.split_once('=') {
+            Some((key, value)) => {
+                let (key, value) = (key.trim(), value.trim());
+                if key.ends_with(".mechanics") {
+                    for name in value.split_whitespace().filter(|name| SpecialMechanic::from_name(name).is_none()) {
+                        println!("Warning: Unknown mechanic {} for {}", name, key);
+                    }
+                }
+                if key.ends_with(".speed_curve") && CurveShape::from_name(value).is_none() {
+                    println!("Warning: Unknown speed curve {} for {}, using logarithmic", value, key);
+                }
+                settings.insert(key.to_string(), value.to_string());
+            }
+            None => println!("Warning: Skipping malformed levels line: {}", line),
+        }
+    }
+    settings
+}
+
+pub fn special_mechanics(level: usize) -> Vec<SpecialMechanic> {
+    LEVELS.lock().unwrap().definition(level).mechanics.clone()
+}
+
+pub fn has_mechanic(level: usize, mechanic: SpecialMechanic) -> bool {
+    LEVELS.lock().unwrap().definition(level).mechanics.contains(&mechanic)
+}
+
+// Food to eat to clear the level
+pub fn foods_to_clear(level: usize) -> u32 {
+    LEVELS.lock().unwrap().definition(level).foods
+}
+
+// Extra speed for the level, on top of the usual ramp
+pub fn speed_multiplier(level: usize) -> f32 {
+    LEVELS.lock().unwrap().definition(level).speed
+}
+
+// How the snake's speed climbs from level to level
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum CurveShape {
+    Logarithmic, // Quick gains early on, then slower ones
+    Linear,
+    Exponential,
+    Stepped, // Flat, with a jump every few levels
+}
+
+impl CurveShape {
+    fn from_name(name: &str) -> Option<CurveShape> {
+        match name {
+            "logarithmic" => Some(CurveShape::Logarithmic),
+            "linear" => Some(CurveShape::Linear),
+            "exponential" => Some(CurveShape::Exponential),
+            "stepped" => Some(CurveShape::Stepped),
+            _ => None,
+        }
+    }
+}
+
+// Snake speed as a multiple of the difficulty's base speed, worked out from the level number
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct SpeedCurve {
+    pub shape: CurveShape,
+    pub start: f32,          // Speed on level 1
+    pub increase: f32,       // Gain per level (per step when stepped, growth rate when exponential)
+    pub max: Option<f32>,    // Speed the curve never goes past
+    pub step_every: usize,   // Levels between jumps on a stepped curve
+}
+
+impl SpeedCurve {
+    pub fn factor(&self, level: usize) -> f32 {
+        let levels = level.saturating_sub(1) as f32;
+        let factor = match self.shape {
+            CurveShape::Logarithmic => self.start + levels.ln().max(0.0) * self.increase,
+            CurveShape::Linear => self.start + levels * self.increase,
+            CurveShape::Exponential => self.start * (1.0 + self.increase).powf(levels),
+            CurveShape::Stepped => {
+                let steps = level.saturating_sub(1) / self.step_every.max(1);
+                self.start + steps as f32 * self.increase
+            }
+        };
+        match self.max {
+            Some(max) => factor.min(max),
+            None => factor,
+        }
+    }
+}
+
+// The level's speed curve, with the difficulty setting the climb unless the levels file does
+pub fn speed_curve(level: usize, difficulty: Difficulty) -> SpeedCurve {
+    let table = LEVELS.lock().unwrap();
+    let definition = table.definition(level);
+    SpeedCurve {
+        increase: definition.speed_increase.unwrap_or(difficulty.speed_ramp()),
+        ..definition.speed_curve
+    }
+}
+
+// Foods out at once, counting the main one
+pub fn max_concurrent_food(level: usize) -> usize {
+    if has_mechanic(level, SpecialMechanic::MultipleFoods) {
+        4
+    } else {
+        1
+    }
+}
 
 pub struct LevelTracker {
     pub level: usize,
     pub score: usize,
     pub score_to_next: usize,
     pub in_game: bool,
+    pub level_time: f32, // Seconds spent on the current level
+    pub run_time: f32, // Seconds spent playing the whole run
 }
 
 impl LevelTracker {
@@ -14,6 +335,8 @@ impl LevelTracker {
             score: 0,
             score_to_next: 5,
             in_game: false,
+            level_time: 0.0,
+            run_time: 0.0,
         }
     }
 
@@ -29,6 +352,16 @@ impl LevelTracker {
         self.level += 1;
         self.score = 0;
         self.score_to_next += 5;
+        self.level_time = 0.0;
+    }
+
+    pub fn update(&mut self, delta_time: f32) {
+        self.level_time += delta_time;
+        self.run_time += delta_time;
+    }
+
+    pub fn has_mechanic(&self, mechanic: SpecialMechanic) -> bool {
+        has_mechanic(self.level, mechanic)
     }
 
     pub fn reset(&mut self) {
@@ -36,6 +369,8 @@ impl LevelTracker {
         self.score = 0;
         self.score_to_next = 5;
         self.in_game = false;
+        self.level_time = 0.0;
+        self.run_time = 0.0;
     }
 
     pub fn draw(&self) {
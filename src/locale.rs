@@ -0,0 +1,126 @@
+use std::collections::HashMap;
+use lazy_static::lazy_static;
+use std::sync::Mutex;
+use crate::defaults::load_text_or;
+
+// Built-in copies of the string tables, used when assets/lang is missing
+const ENGLISH: &str = include_str!("../assets/lang/en.ftl");
+const SPANISH: &str = include_str!("../assets/lang/es.ftl");
+
+// Looks up a UI string in the current language: `t!("hud-level", level = 3)`
+macro_rules! t {
+    ($key:expr) => {
+        $crate::locale::text($key, &[])
+    };
+    ($key:expr, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::locale::text($key, &[$((stringify!($name), $value.to_string())),+])
+    };
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Language {
+    English,
+    Spanish,
+}
+
+const LANGUAGES: [Language; 2] = [Language::English, Language::Spanish];
+
+impl Language {
+    pub fn next(&self) -> Language {
+        match self {
+            Language::English => Language::Spanish,
+            Language::Spanish => Language::English,
+        }
+    }
+
+    // Always in the language itself, so it can be found from any other
+    pub fn name(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::Spanish => "Español",
+        }
+    }
+
+    pub fn save_key(&self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::Spanish => "es",
+        }
+    }
+
+    pub fn from_save_key(key: &str) -> Language {
+        match key {
+            "es" => Language::Spanish,
+            _ => Language::English,
+        }
+    }
+
+    fn built_in(&self) -> &'static str {
+        match self {
+            Language::English => ENGLISH,
+            Language::Spanish => SPANISH,
+        }
+    }
+}
+
+struct Localization {
+    language: Language,
+    tables: HashMap<&'static str, HashMap<String, String>>, // By save key
+}
+
+lazy_static! {
+    // Shared so any screen can look up its text without the settings being passed around
+    static ref STRINGS: Mutex<Localization> = Mutex::new(Localization {
+        language: Language::English,
+        tables: HashMap::new(),
+    });
+}
+
+// Reads every language's table, preferring the files in assets/lang so translations
+// can be edited without rebuilding
+pub async fn load() {
+    for language in LANGUAGES {
+        let path = format!("assets/lang/{}.ftl", language.save_key());
+        let source = load_text_or(&path, language.built_in()).await;
+        let table = parse(&source);
+        STRINGS.lock().unwrap().tables.insert(language.save_key(), table);
+    }
+}
+
+pub fn set_language(language: Language) {
+    STRINGS.lock().unwrap().language = language;
+}
+
+// The string for `key` with its `{ $name }` placeholders filled in. Missing strings
+// fall back to English, then to the key itself so they're easy to spot.
+pub fn text(key: &str, args: &[(&str, String)]) -> String {
+    let strings = STRINGS.lock().unwrap();
+    let lookup = |language: Language| strings.tables.get(language.save_key()).and_then(|table| table.get(key));
+    let Some(template) = lookup(strings.language).or_else(|| lookup(Language::English)) else {
+        return key.to_string();
+    };
+
+    let mut text = template.clone();
+    for (name, value) in args {
+        text = text.replace(&format!("{{ ${} }}", name), value);
+    }
+    text
+}
+
+// The simple part of Fluent: `key = text` lines, with `#` comments and blank lines skipped
+fn parse(source: &str) -> HashMap<String, String> {
+    let mut table = HashMap::new();
+    for line in source.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        match line.split_once('=') {
+            Some((key, value)) => {
+                table.insert(key.trim().to_string(), value.trim().to_string());
+            }
+            None => println!("Warning: Skipping malformed string table line: {}", line),
+        }
+    }
+    table
+}
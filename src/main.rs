@@ -1,37 +1,278 @@
 use macroquad::prelude::*;
-use macroquad::audio::{load_sound, play_sound, stop_sound, PlaySoundParams};
+use std::time::Instant;
+use macroquad::audio::{load_sound, load_sound_from_bytes, play_sound, set_sound_volume, stop_sound, PlaySoundParams};
+use highscores::{HighScoreEntry, HighScoreScreen, LevelScore, NameEntry};
 use grid::draw_grid;
 use snake::Snake;
-use food::Food;
+use food::{Food, FoodKind, GOLDEN_FOOD_POINTS, GOLDEN_INVINCIBILITY, POISON_COLOR, POISON_PENALTY, POISON_SHRINK};
+use input::{KeyboardInput, PlayerCommand};
 use cpu_snake::CpuSnakeManager;
 use effects::draw_moving_snakes;
-use level::LevelTracker;
-use themes::get_theme;
+use breakdown::{calculate_level_completion_score, LevelBreakdown};
+use camera::{FollowCamera, MAX_ZOOM_PREFERENCE, MIN_ZOOM_PREFERENCE, ZOOM_PREFERENCE_STEP};
+use continues::Continues;
+use controls::{ControlScheme, KeyPreset};
+use console::Console;
+use credits::Credits;
+use danger::DangerWarning;
+use death::DeathSequence;
+use difficulty::Difficulty;
+use display::DisplaySettings;
+use level::{LevelTracker, SpecialMechanic, BOSS_LEVEL};
+use particles::ParticleSystem;
+use pause::{Pause, PauseAction};
+use perf::{PerfOverlay, System};
+use pet::{tier_for_level, Pet};
+use popups::ScorePopups;
+use presence::Presence;
+use profiles::{ProfileList, ProfilePicker};
+use objectives::ObjectiveTracker;
+use onboarding::draw_controls_splash;
+use save::SaveData;
+use score::Score;
+use skins::SnakeSkin;
+use spectate::{CrashChoice, SpectateState, Spectator};
+use stats::BoardSnapshot;
+use summary::RunSummary;
+use tension::{heartbeat_wav, Tension};
+use latency::{move_click_wav, LatencyCalibration, MoveSound};
+use launch::LaunchOptions;
+use sfx::{MusicDucker, SoundEffects};
+use music::GameMusic;
+use cues::AudioCues;
+use mixer::VolumeMenu;
+use milestones::Milestones;
+use minimap::{draw_minimap, MINIMAP_WIDTH};
+use touch::TouchInput;
+use boost::{Stamina, SMASH_COST};
+use slowmo::SlowMotion;
+use timestep::FixedTimestep;
+use versus::Versus;
+use themes::{get_theme, set_palette};
+use timeattack::{food_sequence, TimeAttack};
+use locale::set_language;
+use hud::{hud_scale, set_large_hud};
+use transition::LevelIntro;
+use history::{HistoryScreen, RunEnd, RunMode, RunRecord};
+use toast::Toast;
+use tutorial::Tutorial;
+use feedback::{log_event, FeedbackForm};
+use arena::Arena;
+use autopilot::{AttractMode, IDLE_TIME};
 
+#[macro_use]
+mod locale;
+mod arena;
+mod assist;
+mod autopilot;
+mod bench;
+mod boss;
+mod boost;
+mod bots;
+mod breakdown;
+mod camera;
+// The debug console and the test hooks it shares commands with, in debug builds or with the
+// console feature (test-hooks turns it on too). Other builds get a stand-in that never opens.
+#[cfg(any(feature = "console", debug_assertions))]
+mod console;
+#[cfg(not(any(feature = "console", debug_assertions)))]
+mod console {
+    pub struct Console {
+        pub open: bool,
+    }
+
+    impl Console {
+        pub fn new() -> Self {
+            Self { open: false }
+        }
+        pub fn update(&mut self) {}
+        pub fn draw(&self) {}
+        pub fn apply_pending(
+            &self,
+            _snake: &mut crate::snake::Snake,
+            _food: &mut crate::food::Food,
+            _level_tracker: &mut crate::level::LevelTracker,
+            _arena: &mut crate::arena::Arena,
+            _objectives: &mut crate::objectives::ObjectiveTracker,
+            _score: &mut u32,
+        ) {
+        }
+    }
+}
+mod continues;
+mod controls;
+mod credits;
+mod cues;
+mod danger;
+mod defaults;
+mod death;
+mod difficulty;
+mod display;
+mod feedback;
 mod grid;
+mod highscores;
+mod headless;
+mod input;
+mod latency;
+mod launch;
+mod layouts;
+mod hud;
+mod transition;
+mod history;
+#[cfg(any(feature = "console", debug_assertions))]
+mod hooks;
+mod mixer;
+mod milestones;
+mod minimap;
 mod snake;
 mod food;
+mod garden;
+mod gravity;
 mod cpu_snake;
 mod effects;
 mod level;
+mod motion;
+mod music;
+mod objectives;
+mod onboarding;
+mod particles;
+mod pause;
+mod perf;
+mod pet;
+mod popups;
+mod presence;
+mod profiles;
+mod rng;
+mod save;
+mod score;
+mod sfx;
+mod skins;
+mod slowmo;
+mod spectate;
+mod stats;
+mod summary;
+mod teleporters;
+mod tension;
+mod touch;
+mod timestep;
+mod versus;
 mod themes;
+mod timeattack;
+mod toast;
+mod trail;
+mod tutorial;
+mod walls;
+mod zones;
+
+fn main() {
+    // `--headless [difficulty]` runs the rules over stdin/stdout without opening a window
+    let args: Vec<String> = std::env::args().collect();
+    // `--level`, `--seed`, `--mute` and the rest, listed in src/launch.rs. The seed applies to the modes below too.
+    let launch = LaunchOptions::parse(&args);
+    rng::set_fixed_seed(launch.seed);
+    if let Some(index) = args.iter().position(|arg| arg == "--headless") {
+        let difficulty = args.get(index + 1).map_or(Difficulty::Normal, |key| Difficulty::from_save_key(key));
+        headless::run_stdio(difficulty);
+        return;
+    }
+    // `--bench [ticks]` times the game systems on a long snake and prints the results
+    if let Some(index) = args.iter().position(|arg| arg == "--bench") {
+        let ticks = args.get(index + 1).and_then(|ticks| ticks.parse().ok()).unwrap_or(10_000);
+        bench::run_bench(ticks);
+        return;
+    }
+    // `--exhibition bot_a bot_b [games]` pits two bots against each other and prints the results
+    if let Some(index) = args.iter().position(|arg| arg == "--exhibition") {
+        let name = |offset: usize| args.get(index + offset).map_or("autopilot", |name| name.as_str());
+        let games = args.get(index + 3).and_then(|games| games.parse().ok()).unwrap_or(5);
+        bots::run_exhibition([name(1), name(2)], games, Difficulty::Normal);
+        return;
+    }
+
+    let mut display = DisplaySettings::load();
+    if let Some(fullscreen) = launch.fullscreen {
+        display.fullscreen = fullscreen;
+    }
+    mixer::set_muted(launch.mute);
+    macroquad::Window::from_config(display.window_conf("Vypertron-Snake"), game(display, launch));
+}
 
-#[macroquad::main("Vypertron-Snake")]
-async fn main() {
+async fn game(mut display: DisplaySettings, launch: LaunchOptions) {
+    display.restore_position();
     let mut snake = Snake::new();
+    let mut input = KeyboardInput::new(ControlScheme::Keys(KeyPreset::ArrowsAndWasd));
     let mut cpu_snake_manager = CpuSnakeManager::new();
-    let mut food = Food::new(&snake);
+    let mut arena = Arena::new();
+    let mut pet = Pet::new(1);
+    let mut pause = Pause::new();
+    let mut credits = Credits::new();
+    let mut breakdown = LevelBreakdown::new();
+    let mut level_intro = LevelIntro::new();
+    let mut milestones = Milestones::new();
+    let mut death = DeathSequence::new();
+    let mut tension = Tension::new();
+    let mut danger = DangerWarning::new();
+    let mut perf = PerfOverlay::new();
+    let mut food = Food::new(&snake, |pos| arena.is_occupied(pos));
     let mut level_tracker = LevelTracker::new();
     let mut score = 0;
+    let mut points = Score::new();
+    let mut toast = Toast::new();
+    let mut presence = Presence::new();
+    let mut profiles = ProfileList::load();
+    let mut profile_picker = ProfilePicker::new(&profiles);
+    let mut versus = Versus::new();
+    let mut timestep = FixedTimestep::new();
+    let mut slow_motion = SlowMotion::new();
+    let mut stamina = Stamina::new();
+    let mut follow_camera = FollowCamera::new();
+    let mut touch = TouchInput::new();
+    let mut spectator = Spectator::new();
+    let mut attract = AttractMode::new();
+    let mut feedback_form = FeedbackForm::new();
+    let mut console = Console::new();
+    let mut tutorial = Tutorial::new();
+    let mut objectives = ObjectiveTracker::new();
+    let mut time_attack = TimeAttack::new();
+    let mut continues = Continues::new();
+    let mut name_entry = NameEntry::new();
+    let mut high_score_screen = HighScoreScreen::new();
+    let mut latency_calibration = LatencyCalibration::new();
+    let mut move_sound = MoveSound::new();
+    let mut ducker = MusicDucker::new();
+    let mut volume_menu = VolumeMenu::new();
+    let mut pause_focus = 0;
+    let mut idle_time = 0.0;
+    let mut profile_name = profiles.profiles[profiles.last_used_index()].name.clone();
+    let (mut save_data, save_warning) = SaveData::load(&profiles.profiles[profiles.last_used_index()].save_path());
+    mixer::set_levels(save_data.volume);
+    set_palette(save_data.palette);
+    motion::set_reduced_motion(save_data.reduced_motion);
+    set_large_hud(save_data.large_hud);
+    set_language(save_data.language);
+    let mut particles = ParticleSystem::new(save_data.effects.particle_cap());
+    let mut popups = ScorePopups::new(save_data.effects.popup_cap());
+    if let Some(warning) = save_warning {
+        toast.show(warning);
+    }
+    let mut showing_controls = false;
+    let mut showing_stats = false;
+    let mut restart_pending = false;
+    let mut launch_level = launch.level;
+    let mut history_screen = HistoryScreen::new();
+    // What ended the run, kept from the crash until the player leaves the crash screen
+    let mut crash_cause = RunEnd::Tail;
+    #[cfg(feature = "test-hooks")]
+    hooks::listen_stdin();
 
-    // Load the snake head texture
-    let snake_head_texture = match load_texture("assets/snake_head.png").await {
-        Ok(texture) => Some(texture),
-        Err(_) => {
-            println!("Warning: Could not load snake head image. Game will continue without it.");
-            None
-        }
-    };
+    locale::load().await;
+    level::load();
+
+    // Load the snake head texture, falling back to the small built-in one
+    let snake_head_texture = defaults::load_texture_or("assets/snake_head.png", defaults::SNAKE_HEAD).await;
+    let ui_beep = defaults::load_sound_or("assets/ui_beep.wav", defaults::UI_BEEP).await;
+
+    let snake_skin = SnakeSkin::generate();
 
     // Load music files
     let title_music = match load_sound("assets/Snake_title.wav").await {
@@ -45,43 +286,60 @@ async fn main() {
         }
     };
 
-    let game_music = match load_sound("assets/snake_game.wav").await {
-        Ok(sound) => {
-            println!("Game music loaded successfully!");
-            Some(sound)
-        },
+    let mut game_music = GameMusic::load().await;
+    let mut audio_cues = AudioCues::load().await;
+
+    // Heartbeat layer for when the snake gets boxed in, generated rather than loaded
+    let heartbeat = match load_sound_from_bytes(&heartbeat_wav()).await {
+        Ok(sound) => Some(sound),
+        Err(e) => {
+            println!("Warning: Could not create heartbeat sound: {:?}", e);
+            None
+        }
+    };
+
+    let move_click = match load_sound_from_bytes(&move_click_wav()).await {
+        Ok(sound) => Some(sound),
         Err(e) => {
-            println!("Warning: Could not load game music: {:?}", e);
+            println!("Warning: Could not create move sound: {:?}", e);
             None
         }
     };
 
+    let mut sound_effects = SoundEffects::load().await;
+
     // Start playing title music
     let mut title_music_playing = false;
-    let mut game_music_playing = false;
 
     loop {
+        // Buttons only steer during a game, elsewhere the screen is left free for taps
+        let touch_dir = touch.update(save_data.swipe_sensitivity, level_tracker.in_game);
+
         match level_tracker.in_game {
             false => {
                 // Start title music if not already playing
-                if !title_music_playing && !game_music_playing {
+                if !title_music_playing && !game_music.is_playing() {
                     if let Some(music) = &title_music {
                         println!("Playing title music...");
                         play_sound(
                             music,
                             PlaySoundParams {
                                 looped: true,
-                                volume: 0.7,  // Adjust volume as needed
+                                volume: mixer::music_volume(0.7),  // Adjust volume as needed
                             },
                         );
                         title_music_playing = true;
                     }
                 }
 
+                presence.update("In the menus", "");
+
                 clear_background(BLACK);
                 
                 // Draw animated background effects
-                draw_moving_snakes();
+                if !motion::reduced_motion() {
+                    draw_moving_snakes();
+                }
                 
                 // Calculate center position for title
                 let title = "VYPERTRON SNAKE";
@@ -104,141 +362,1078 @@ async fn main() {
                 }
                 draw_text(title, title_x, title_y, title_size, GREEN);
                 
-                // Draw snake head image centered under the title (if loaded)
-                if let Some(texture) = &snake_head_texture {
-                    let img_scale = 0.4; // Adjust this to make the image bigger or smaller
-                    let img_width = texture.width() * img_scale;
-                    let img_height = texture.height() * img_scale;
-                    let img_x = (screen_width() - img_width) / 2.0;
-                    let img_y = title_y + 50.0;
-                    
-                    // Add a subtle pulsing effect to the image
-                    let img_pulse = ((get_time() * 2.0).sin() * 0.05 + 1.0) as f32;
-                    draw_texture_ex(
-                        texture,
-                        img_x,
-                        img_y,
-                        WHITE,
-                        DrawTextureParams {
-                            dest_size: Some(vec2(img_width * img_pulse, img_height * img_pulse)),
-                            ..Default::default()
-                        }
-                    );
-                }
+                // Draw snake head image centered under the title, at a fixed size
+                // so the small built-in fallback fills the same space
+                let img_width = 410.0; // Adjust this to make the image bigger or smaller
+                let img_height = img_width * snake_head_texture.height() / snake_head_texture.width();
+                let img_x = (screen_width() - img_width) / 2.0;
+                let img_y = title_y + 50.0;
+                
+                // Add a subtle pulsing effect to the image
+                let img_pulse = motion::pulse(2.0, 0.05, 1.0);
+                draw_texture_ex(
+                    &snake_head_texture,
+                    img_x,
+                    img_y,
+                    WHITE,
+                    DrawTextureParams {
+                        dest_size: Some(vec2(img_width * img_pulse, img_height * img_pulse)),
+                        ..Default::default()
+                    }
+                );
                 
                 // Draw start prompt (also centered)
-                let prompt = "Press SPACE to start";
+                let prompt = t!("title-start");
                 let prompt_size = 32.0;
-                let prompt_width = measure_text(prompt, None, prompt_size as u16, 1.0).width;
+                let prompt_width = measure_text(&prompt, None, prompt_size as u16, 1.0).width;
                 let prompt_x = (screen_width() - prompt_width) / 2.0;
                 let prompt_y = title_y + 450.0; // Moved further down to clear the snake image
                 
                 // Pulsing effect for prompt
-                let pulse = (get_time() * 4.0).sin() * 0.3 + 0.7;
+                let pulse = motion::pulse(4.0, 0.3, 0.7);
                 draw_text(
-                    prompt, 
+                    &prompt, 
                     prompt_x, 
                     prompt_y, 
                     prompt_size, 
-                    Color::new(0.8, 0.8, 0.8, pulse as f32)
+                    Color::new(0.8, 0.8, 0.8, pulse)
                 );
                 
                 // Draw last score if game over
                 if score > 0 {
-                    let score_text = format!("Last Score: {}", score);
+                    let score_text = t!("title-last-score", score = score);
                     let score_width = measure_text(&score_text, None, 24, 1.0).width;
                     let score_x = (screen_width() - score_width) / 2.0;
                     draw_text(&score_text, score_x, prompt_y + 50.0, 24.0, YELLOW);
+
+                    let points_text = t!("title-points", points = points.value, combo = points.best_streak);
+                    let points_width = measure_text(&points_text, None, 24, 1.0).width;
+                    let points_x = (screen_width() - points_width) / 2.0;
+                    draw_text(&points_text, points_x, prompt_y + 80.0, 24.0, YELLOW);
+                }
+
+                if save_data.checkpoint_level > 0 {
+                    let checkpoint_text =
+                        t!("title-checkpoint", level = save_data.checkpoint_level, left = save_data.continues_left);
+                    let checkpoint_width = measure_text(&checkpoint_text, None, 22, 1.0).width;
+                    draw_text(&checkpoint_text, (screen_width() - checkpoint_width) / 2.0, prompt_y + 110.0, 22.0, SKYBLUE);
+                }
+
+                let profile_text = t!("title-profile", name = profile_name);
+                draw_text(&profile_text, 20.0, 35.0, 24.0, GRAY);
+                draw_text(&t!("title-time-attack"), 20.0, 58.0, 18.0, GRAY);
+                if cfg!(feature = "leaderboard") {
+                    draw_text(&t!("title-high-scores"), 20.0, 78.0, 18.0, GRAY);
+                }
+                draw_text(&t!("title-history"), 20.0, 98.0, 18.0, GRAY);
+
+                // Below the swipe setting when that's showing
+                let latency_text = t!("setting-latency", ms = save_data.audio_latency_ms);
+                let latency_width = measure_text(&latency_text, None, 18, 1.0).width;
+                let latency_y = if touch.active { 60.0 } else { 35.0 };
+                draw_text(&latency_text, screen_width() - latency_width - 20.0, latency_y, 18.0, GRAY);
+                let ducking_text = t!("setting-ducking", ducking = t!(&format!("ducking-{}", save_data.music_ducking.save_key())));
+                let ducking_width = measure_text(&ducking_text, None, 18, 1.0).width;
+                draw_text(&ducking_text, screen_width() - ducking_width - 20.0, latency_y + 22.0, 18.0, GRAY);
+                // Only the recorded song is left without the generated tunes
+                if cfg!(feature = "audio-procedural") {
+                    let music_text = t!(
+                        "setting-music",
+                        music = t!(if save_data.generated_music { "music-generated" } else { "music-classic" })
+                    );
+                    let music_width = measure_text(&music_text, None, 18, 1.0).width;
+                    draw_text(&music_text, screen_width() - music_width - 20.0, latency_y + 44.0, 18.0, GRAY);
+                }
+                let volume_text = t!("setting-volume", percent = (save_data.volume.master * 100.0).round());
+                let volume_width = measure_text(&volume_text, None, 18, 1.0).width;
+                draw_text(&volume_text, screen_width() - volume_width - 20.0, latency_y + 66.0, 18.0, GRAY);
+                let palette_text = t!("setting-colors", palette = t!(&format!("palette-{}", save_data.palette.save_key())));
+                let palette_width = measure_text(&palette_text, None, 18, 1.0).width;
+                draw_text(&palette_text, screen_width() - palette_width - 20.0, latency_y + 88.0, 18.0, GRAY);
+                let motion_text = t!(
+                    "setting-motion",
+                    state = t!(if save_data.reduced_motion { "on" } else { "off" })
+                );
+                let motion_width = measure_text(&motion_text, None, 18, 1.0).width;
+                draw_text(&motion_text, screen_width() - motion_width - 20.0, latency_y + 110.0, 18.0, GRAY);
+                let cues_text = t!("setting-cues", state = t!(if save_data.audio_cues { "on" } else { "off" }));
+                let cues_width = measure_text(&cues_text, None, 18, 1.0).width;
+                draw_text(&cues_text, screen_width() - cues_width - 20.0, latency_y + 132.0, 18.0, GRAY);
+                let language_text = t!("setting-language", language = save_data.language.name());
+                let language_width = measure_text(&language_text, None, 18, 1.0).width;
+                draw_text(&language_text, screen_width() - language_width - 20.0, latency_y + 154.0, 18.0, GRAY);
+                let window_text = t!("setting-window", size = display.resolution.save_key());
+                let window_width = measure_text(&window_text, None, 18, 1.0).width;
+                draw_text(&window_text, screen_width() - window_width - 20.0, latency_y + 176.0, 18.0, GRAY);
+                let fullscreen_text = t!("setting-fullscreen", state = t!(if display.fullscreen { "on" } else { "off" }));
+                let fullscreen_width = measure_text(&fullscreen_text, None, 18, 1.0).width;
+                draw_text(&fullscreen_text, screen_width() - fullscreen_width - 20.0, latency_y + 198.0, 18.0, GRAY);
+                let body_text = t!(
+                    "setting-body",
+                    style = t!(if save_data.smooth_body { "body-smooth" } else { "body-classic" })
+                );
+                let body_width = measure_text(&body_text, None, 18, 1.0).width;
+                draw_text(&body_text, screen_width() - body_width - 20.0, latency_y + 220.0, 18.0, GRAY);
+                let hud_text = t!("setting-hud", size = t!(if save_data.large_hud { "hud-large" } else { "hud-normal" }));
+                let hud_width = measure_text(&hud_text, None, 18, 1.0).width;
+                draw_text(&hud_text, screen_width() - hud_width - 20.0, latency_y + 242.0, 18.0, GRAY);
+                let preview_text = t!("setting-preview", state = t!(if save_data.path_preview { "on" } else { "off" }));
+                let preview_width = measure_text(&preview_text, None, 18, 1.0).width;
+                draw_text(&preview_text, screen_width() - preview_width - 20.0, latency_y + 264.0, 18.0, GRAY);
+                let effects_text = t!("setting-effects", limit = t!(&format!("effects-{}", save_data.effects.save_key())));
+                let effects_width = measure_text(&effects_text, None, 18, 1.0).width;
+                draw_text(&effects_text, screen_width() - effects_width - 20.0, latency_y + 286.0, 18.0, GRAY);
+
+                let guide_hint = t!("title-guide");
+                let guide_width = measure_text(&guide_hint, None, 18, 1.0).width;
+                draw_text(&guide_hint, (screen_width() - guide_width) / 2.0, screen_height() - 20.0, 18.0, GRAY);
+
+                let difficulty_text = t!("title-difficulty", difficulty = t!(&format!("difficulty-{}", save_data.difficulty.save_key())));
+                let difficulty_width = measure_text(&difficulty_text, None, 24, 1.0).width;
+                draw_text(
+                    &difficulty_text,
+                    (screen_width() - difficulty_width) / 2.0,
+                    screen_height() - 70.0,
+                    24.0,
+                    save_data.difficulty.color(),
+                );
+
+                let wrap_hint = t!(if save_data.wrap_around { "on" } else { "off" });
+                let camera_hint = t!(if save_data.follow_camera { "camera-follow" } else { "camera-fixed" });
+                let controls_hint = t!(
+                    "title-controls",
+                    controls = t!(&format!("controls-{}", save_data.controls.save_key())),
+                    walls = wrap_hint,
+                    camera = camera_hint
+                );
+                let controls_width = measure_text(&controls_hint, None, 18, 1.0).width;
+                draw_text(&controls_hint, (screen_width() - controls_width) / 2.0, screen_height() - 42.0, 18.0, GRAY);
+
+                if showing_controls {
+                    draw_controls_splash(save_data.controls);
+                }
+
+                // On touch screens a tap stands in for SPACE, apart from on the sensitivity setting
+                let mut title_tap = touch::tap_position();
+                if touch.active {
+                    let swipe_text = t!("title-swipe", sensitivity = t!(&format!("swipe-{}", save_data.swipe_sensitivity.save_key())));
+                    let swipe_width = measure_text(&swipe_text, None, 24, 1.0).width;
+                    let swipe_rect = Rect::new(screen_width() - swipe_width - 20.0, 15.0, swipe_width, 26.0);
+                    draw_text(&swipe_text, swipe_rect.x, 35.0, 24.0, GRAY);
+                    if title_tap.is_some_and(|pos| swipe_rect.contains(pos)) {
+                        save_data.swipe_sensitivity = save_data.swipe_sensitivity.next();
+                        save_data.save();
+                        title_tap = None;
+                    }
+                }
+                let start_pressed = is_key_pressed(KeyCode::Space) || title_tap.is_some();
+
+                // Left alone long enough, the title screen plays a demo until someone touches anything
+                let any_input = get_last_key_pressed().is_some()
+                    || is_mouse_button_pressed(MouseButton::Left)
+                    || touch::tapped();
+                idle_time = if any_input { 0.0 } else { idle_time + get_frame_time() };
+                let title_busy = versus.showing
+                    || time_attack.showing_menu
+                    || time_attack.has_result()
+                    || continues.has_summary()
+                    || name_entry.showing
+                    || high_score_screen.showing
+                    || history_screen.showing
+                    || latency_calibration.showing
+                    || volume_menu.showing
+                    || profile_picker.showing
+                    || showing_stats
+                    || showing_controls;
+                if idle_time >= IDLE_TIME && !attract.showing && !title_busy {
+                    attract.start();
+                }
+
+                let mut new_run = None;
+                // R on the crash screen starts over, once any high score name has been entered
+                // --level N skips the title and starts a run there on the first frame
+                if let Some(level) = launch_level.take() {
+                    continues.start(save_data.difficulty.continues());
+                    new_run = Some(level);
+                }
+                if restart_pending && !name_entry.showing && !high_score_screen.showing {
+                    restart_pending = false;
+                    time_attack.stop();
+                    continues.start(save_data.difficulty.continues());
+                    new_run = Some(1);
+                }
+                if attract.showing {
+                    attract.update(get_frame_time());
+                    attract.draw(&snake_skin, save_data.smooth_body);
+                    if any_input {
+                        attract.stop();
+                    }
+                } else if versus.showing {
+                    versus.update(get_frame_time());
+                    versus.draw();
+                } else if time_attack.has_result() {
+                    time_attack.draw_result();
+                    time_attack.update_result();
+                } else if name_entry.showing {
+                    name_entry.draw();
+                    if let Some(entry) = name_entry.update() {
+                        let row = save_data.high_scores.insert(entry);
+                        save_data.save();
+                        high_score_screen.open(row);
+                    }
+                } else if high_score_screen.showing {
+                    high_score_screen.draw(&save_data.high_scores, &save_data.level_scores);
+                    high_score_screen.update(&save_data.level_scores);
+                } else if history_screen.showing {
+                    history_screen.draw(&save_data.history);
+                    history_screen.update(&save_data.history);
+                } else if continues.has_summary() {
+                    continues.draw_summary();
+                    continues.update_summary();
+                } else if time_attack.showing_menu {
+                    time_attack.draw_menu(&save_data.best_times);
+                    new_run = time_attack.update_menu();
+                } else if volume_menu.showing {
+                    volume_menu.draw(&save_data.volume);
+                    if volume_menu.update(&mut save_data.volume, ui_beep.as_ref()) {
+                        if let Some(music) = &title_music {
+                            set_sound_volume(music, mixer::music_volume(0.7));
+                        }
+                        save_data.save();
+                    }
+                } else if latency_calibration.showing {
+                    latency_calibration.draw();
+                    if let Some(latency_ms) = latency_calibration.update(move_click.as_ref()) {
+                        save_data.audio_latency_ms = latency_ms;
+                        save_data.save();
+                    }
+                } else if profile_picker.showing {
+                    profile_picker.draw(&profiles);
+                    if let Some(index) = profile_picker.update(&mut profiles) {
+                        // Everything in the save file belongs to the chosen profile
+                        let profile = profiles.select(index);
+                        profile_name = profile.name.clone();
+                        let (loaded, warning) = SaveData::load(&profile.save_path());
+                        save_data = loaded;
+                        mixer::set_levels(save_data.volume);
+                        set_palette(save_data.palette);
+                        motion::set_reduced_motion(save_data.reduced_motion);
+                        set_large_hud(save_data.large_hud);
+                        set_language(save_data.language);
+                        particles.set_cap(save_data.effects.particle_cap());
+                        popups.set_cap(save_data.effects.popup_cap());
+                        if let Some(warning) = warning {
+                            toast.show(warning);
+                        }
+                        score = 0;
+                        points.reset();
+                    }
+                } else if is_key_pressed(KeyCode::Tab) && !showing_controls && !showing_stats {
+                    profile_picker.open(&profiles);
+                } else if showing_stats {
+                    save_data.stats.draw_screen();
+                    if is_key_pressed(KeyCode::S) || is_key_pressed(KeyCode::Escape) {
+                        showing_stats = false;
+                    } else if is_key_pressed(KeyCode::E) {
+                        match save_data.export_stats(&profile_name) {
+                            Ok(path) => toast.show(&format!("Statistics exported to {}", path)),
+                            Err(e) => {
+                                println!("Warning: Could not export statistics: {:?}", e);
+                                toast.show("Could not export statistics");
+                            }
+                        }
+                    }
+                } else if is_key_pressed(KeyCode::S) && !showing_controls {
+                    showing_stats = true;
+                } else if is_key_pressed(KeyCode::V) && !showing_controls {
+                    versus.open(save_data.controls);
+                } else if is_key_pressed(KeyCode::T) && !showing_controls {
+                    time_attack.open_menu(save_data.best_level);
+                } else if is_key_pressed(KeyCode::O) {
+                    save_data.controls = save_data.controls.next();
+                    save_data.save();
+                } else if is_key_pressed(KeyCode::D) {
+                    save_data.difficulty = save_data.difficulty.next();
+                    save_data.save();
+                } else if is_key_pressed(KeyCode::W) {
+                    save_data.wrap_around = !save_data.wrap_around;
+                    save_data.save();
+                } else if is_key_pressed(KeyCode::F) {
+                    save_data.follow_camera = !save_data.follow_camera;
+                    save_data.save();
+                } else if is_key_pressed(KeyCode::A) && !showing_controls {
+                    volume_menu.open();
+                } else if is_key_pressed(KeyCode::U) {
+                    save_data.audio_cues = !save_data.audio_cues;
+                    save_data.save();
+                } else if is_key_pressed(KeyCode::R) {
+                    save_data.reduced_motion = !save_data.reduced_motion;
+                    motion::set_reduced_motion(save_data.reduced_motion);
+                    save_data.save();
+                } else if is_key_pressed(KeyCode::C) {
+                    save_data.palette = save_data.palette.next();
+                    set_palette(save_data.palette);
+                    save_data.save();
+                } else if cfg!(feature = "audio-procedural") && is_key_pressed(KeyCode::G) {
+                    save_data.generated_music = !save_data.generated_music;
+                    save_data.save();
+                } else if is_key_pressed(KeyCode::N) {
+                    save_data.language = save_data.language.next();
+                    set_language(save_data.language);
+                    save_data.save();
+                } else if is_key_pressed(KeyCode::M) {
+                    save_data.music_ducking = save_data.music_ducking.next();
+                    save_data.save();
+                } else if is_key_pressed(KeyCode::L) && !showing_controls {
+                    latency_calibration.open(save_data.audio_latency_ms);
+                } else if cfg!(feature = "leaderboard") && is_key_pressed(KeyCode::K) && !showing_controls {
+                    high_score_screen.open(None);
+                } else if is_key_pressed(KeyCode::Y) && !showing_controls {
+                    history_screen.open();
+                } else if is_key_pressed(KeyCode::X) {
+                    display.cycle_resolution();
+                } else if is_key_pressed(KeyCode::B) {
+                    save_data.smooth_body = !save_data.smooth_body;
+                    save_data.save();
+                } else if is_key_pressed(KeyCode::I) {
+                    save_data.large_hud = !save_data.large_hud;
+                    set_large_hud(save_data.large_hud);
+                    save_data.save();
+                } else if is_key_pressed(KeyCode::P) {
+                    save_data.path_preview = !save_data.path_preview;
+                    save_data.save();
+                } else if is_key_pressed(KeyCode::J) {
+                    save_data.effects = save_data.effects.next();
+                    particles.set_cap(save_data.effects.particle_cap());
+                    popups.set_cap(save_data.effects.popup_cap());
+                    save_data.save();
+                } else if is_key_pressed(KeyCode::Enter)
+                    && !display::alt_held()
+                    && save_data.checkpoint_level > 0
+                    && !showing_controls
+                {
+                    time_attack.stop();
+                    continues.start(save_data.continues_left);
+                    new_run = Some(save_data.checkpoint_level);
+                } else {
+                    if is_key_pressed(KeyCode::H) && !showing_controls {
+                        // Reset onboarding so the guide appears before the next game
+                        save_data.onboarding_complete = false;
+                        save_data.tutorial_complete = false;
+                        save_data.save();
+                    }
+
+                    if start_pressed && !save_data.onboarding_complete && !launch.skip_intro && !showing_controls {
+                        // First launch: walk through the controls before dropping into the game
+                        showing_controls = true;
+                    } else if start_pressed {
+                        if let Some(beep) = &ui_beep {
+                            mixer::play_sfx(beep, 1.0);
+                        }
+                        if showing_controls {
+                            showing_controls = false;
+                            save_data.onboarding_complete = true;
+                            save_data.save();
+                        }
+
+                        time_attack.stop();
+                        continues.start(save_data.difficulty.continues());
+                        new_run = Some(1);
+                    }
                 }
 
-                if is_key_pressed(KeyCode::Space) {
+                if let Some(start_level) = new_run {
+                    let seed = rng::start_run();
                     snake = Snake::new();
+                    input = KeyboardInput::new(save_data.controls);
                     cpu_snake_manager = CpuSnakeManager::new();
-                    food = Food::new(&snake);
                     level_tracker.reset();
+                    level_tracker.level = start_level;
                     level_tracker.in_game = true;
+                    log_event(&format!("Started a {} run with seed {}", save_data.difficulty.name(), seed));
+                    arena = Arena::new();
+                    arena.setup(level_tracker.level, &snake);
+                    slow_motion.stop();
+                    stamina.refill();
+                    objectives.start(level_tracker.level, snake.length());
+                    food = Food::new(&snake, |pos| arena.is_occupied(pos));
+                    food.lifetime = save_data.difficulty.food_lifetime();
+                    if time_attack.active {
+                        food.sequence = food_sequence(start_level).into();
+                        food.relocate(&snake, |pos| arena.is_occupied(pos));
+                    } else {
+                        save_data.checkpoint_level = start_level;
+                        save_data.continues_left = continues.left;
+                        save_data.save();
+                    }
+                    pet = Pet::new(save_data.best_level);
+                    pause = Pause::new();
+                    tutorial.stop();
+                    if !save_data.tutorial_complete && !time_attack.active && !launch.skip_intro {
+                        tutorial.start(&snake);
+                    }
+                    credits = Credits::new();
+                    breakdown = LevelBreakdown::new();
+                    level_intro = LevelIntro::new();
+                    particles.clear();
+                    popups.clear();
+                    death = DeathSequence::new();
+                    tension = Tension::new();
+                    danger = DangerWarning::new();
+                    follow_camera = FollowCamera::new();
+                    spectator = Spectator::new();
+                    milestones = Milestones::new();
+                    presence.start_run();
                     score = 0;
-                    
+                    points.reset();
+                    points.percent = save_data.difficulty.score_percent();
+                
                     // Stop title music and start game music
                     if let Some(music) = &title_music {
                         stop_sound(music);  // Stop title music completely
                     }
                     title_music_playing = false;
-                    
-                    if let Some(music) = &game_music {
-                        println!("Playing game music...");
-                        play_sound(
-                            music,
-                            PlaySoundParams {
-                                looped: true,
-                                volume: 0.7,  // Adjust volume as needed
-                            },
-                        );
-                        game_music_playing = true;
-                    }
+                
+                    game_music.play_for_level(level_tracker.level, save_data.generated_music);
                 }
             }
             true => {
                 let theme = get_theme(level_tracker.level.try_into().unwrap());
+
+                let presence_details = if arena.boss.is_some() {
+                    format!("Level {} - Fighting Vypertron", level_tracker.level)
+                } else {
+                    format!("Level {}", level_tracker.level)
+                };
+                presence.update(&presence_details, &format!("Score {}", points.value));
                 
                 // Clear background with theme color
                 clear_background(theme.background);
 
-                // Draw UI elements
-                let level_text = format!("LEVEL {}", level_tracker.level);
-                let level_width = measure_text(&level_text, None, 36, 1.0).width;
+                // Draw UI elements, anchored to the top corners and centre and sized by the HUD scale
+                let hud = hud_scale();
+                let level_text = t!("hud-level", level = level_tracker.level);
+                let level_width = measure_text(&level_text, None, (36.0 * hud) as u16, 1.0).width;
                 let level_x = (screen_width() - level_width) / 2.0;
-                draw_text(&level_text, level_x, 30.0, 36.0, theme.ui_text);
+                draw_text(&level_text, level_x, 30.0 * hud, 36.0 * hud, tension.tint(theme.ui_text));
                 
                 // Draw score (tail counter)
-                let score_text = format!("TAILS: {}", score);
-                draw_text(&score_text, 20.0, 30.0, 24.0, theme.ui_text);
+                let score_text = t!("hud-tails", tails = score);
+                draw_text(&score_text, 20.0 * hud, 30.0 * hud, 24.0 * hud, theme.ui_text);
+                points.draw(&theme);
+                time_attack.draw_timer(level_tracker.level_time, theme.ui_text);
+                if let ControlScheme::Keys(_) = input.controls {
+                    snake.draw_whip_meter(&theme);
+                }
+                stamina.draw(&theme);
+                snake.draw_invincibility_bar(&theme);
                 
-                // Draw speed indicator (using actual speed calculation)
-                let speed_factor = 1.0 + (level_tracker.level as f32 - 1.0).ln().max(0.0) * 0.3;
-                let speed_text = format!("SPEED: {:.1}x", speed_factor);
-                let speed_width = measure_text(&speed_text, None, 24, 1.0).width;
-                draw_text(&speed_text, screen_width() - speed_width - 20.0, 30.0, 24.0, theme.ui_text);
+                // Draw speed indicator, relative to level 1 on Normal
+                let speed_factor = 0.15 / snake.move_delay;
+                let speed_text = t!("hud-speed", speed = format!("{:.1}", speed_factor * snake.speed_multiplier));
+                let speed_width = measure_text(&speed_text, None, (24.0 * hud) as u16, 1.0).width;
+                let speed_color = if snake.speed_multiplier > 1.0 {
+                    ORANGE
+                } else if snake.speed_multiplier < 1.0 {
+                    SKYBLUE
+                } else {
+                    theme.ui_text
+                };
+                draw_text(&speed_text, screen_width() - speed_width - 20.0 * hud, 30.0 * hud, 24.0 * hud, speed_color);
+
+                let difficulty_name = t!(&format!("difficulty-{}", save_data.difficulty.save_key()));
+                let difficulty_width = measure_text(&difficulty_name, None, (20.0 * hud) as u16, 1.0).width;
+                let difficulty_x = (screen_width() - difficulty_width) / 2.0;
+                draw_text(&difficulty_name, difficulty_x, 52.0 * hud, 20.0 * hud, save_data.difficulty.color());
+                objectives.draw(snake.length(), level_tracker.level_time, &theme);
+
+                if let Some(boss) = &arena.boss {
+                    boss.draw_health_bar();
+                }
+                // Zoomed in, the full minimap stands in for the teleporter one
+                let minimap_on = save_data.minimap && save_data.follow_camera;
+                if !minimap_on {
+                    arena.teleporters.draw_minimap(screen_width() - 100.0 * hud, 42.0 * hud, 80.0 * hud);
+                }
+
+                if let ControlScheme::OneButton { .. } = input.controls {
+                    let turn_text = t!(if input.next_turn_right { "hud-next-turn-right" } else { "hud-next-turn-left" });
+                    let turn_width = measure_text(&turn_text, None, (24.0 * hud) as u16, 1.0).width;
+                    let turn_x = (screen_width() - turn_width) / 2.0;
+                    draw_text(&turn_text, turn_x, screen_height() - 15.0 * hud, 24.0 * hud, theme.ui_text);
+                }
+
+                // Keys typed into the console don't reach the game
+                if is_key_pressed(KeyCode::M) && pause.is_running() && !console.open {
+                    save_data.minimap = !save_data.minimap;
+                    save_data.save();
+                }
+
+                // Spectating borrows the follow camera to track a CPU snake
+                let spectate_head = spectator.target_head(&cpu_snake_manager);
+                let following = save_data.follow_camera || spectate_head.is_some();
+                // Without the follow camera, a window too small for the board still shows all of it
+                let fixed_view = !camera::level_fits();
+                let looking = pause.is_looking_around();
+                let camera_on = following || fixed_view || looking;
+                if looking {
+                    // A board that's normally drawn without the camera starts from the whole-board view
+                    if !following && !fixed_view && !follow_camera.is_looking() {
+                        follow_camera.fit_level();
+                    }
+                    follow_camera.look_around(get_frame_time());
+                } else if following {
+                    // Manual zoom on top of the automatic one, kept between runs
+                    let zoom_change = if console.open {
+                        0.0
+                    } else if is_key_pressed(KeyCode::Equal) || is_key_pressed(KeyCode::KpAdd) {
+                        ZOOM_PREFERENCE_STEP
+                    } else if is_key_pressed(KeyCode::Minus) || is_key_pressed(KeyCode::KpSubtract) {
+                        -ZOOM_PREFERENCE_STEP
+                    } else {
+                        0.0
+                    };
+                    if zoom_change != 0.0 {
+                        save_data.zoom_preference = (save_data.zoom_preference + zoom_change)
+                            .clamp(MIN_ZOOM_PREFERENCE, MAX_ZOOM_PREFERENCE);
+                        save_data.save();
+                    }
+                    let (head, length) = match spectate_head {
+                        Some(head) => (head, 1),
+                        None => (snake.head(), snake.length()),
+                    };
+                    follow_camera.update(get_frame_time(), head, length, save_data.zoom_preference);
+                } else if camera_on {
+                    follow_camera.fit_level();
+                } else {
+                    follow_camera.snap_back();
+                }
+                if camera_on {
+                    follow_camera.begin();
+                }
 
                 // Draw grid with theme color
-                draw_grid(theme.grid);
+                snake.wrap = save_data.wrap_around || level_tracker.has_mechanic(SpecialMechanic::WrapAround) || snake.god_mode;
+                draw_grid(theme.grid, snake.wrap);
 
-                // Update snake speed based on level
-                snake.update_speed(level_tracker.level);
+                if camera_on {
+                    follow_camera.end();
+                }
 
-                let delta_time = get_frame_time();
-                snake.update(delta_time);
-                cpu_snake_manager.update(level_tracker.level);
+                pause.update(get_frame_time());
+                // Tapping the board pauses, the pause menu's buttons take taps from there
+                let tap_pause = touch::tapped() && pause.is_running() && !credits.showing && !breakdown.showing && !level_intro.showing;
+                let pause_pressed = is_key_pressed(KeyCode::P) || is_key_pressed(KeyCode::Escape) || tap_pause;
+                if pause_pressed && !spectator.is_active() && !death.is_playing() && !feedback_form.showing && !console.open {
+                    if let Some(beep) = &ui_beep {
+                        mixer::play_sfx(beep, 1.0);
+                    }
+                    if pause.is_running() {
+                        pause.pause();
+                        snake.clear_input();
+                        log_event(&format!("Paused on level {}", level_tracker.level));
+                    } else {
+                        pause.resume();
+                    }
+                }
 
-                // Only check if player snake is dead
-                if snake.is_dead() {
-                    level_tracker.in_game = false;
-                    
-                    // Stop game music completely
-                    if let Some(music) = &game_music {
-                        stop_sound(music);
+                // The form takes the keyboard while it's open, so typing doesn't press the menu's shortcuts
+                let pause_action = if feedback_form.showing { None } else { pause.update_menu() };
+                if save_data.audio_cues && !pause.is_running() && pause.selected() != pause_focus {
+                    audio_cues.focus(pause.selected());
+                }
+                pause_focus = pause.selected();
+                if let (Some(_), Some(beep)) = (pause_action, &ui_beep) {
+                    mixer::play_sfx(beep, 1.0);
+                }
+                // A continue on the crash screen replays the level the same way the pause menu's restart does
+                let continue_taken =
+                    spectator.state == SpectateState::Offering && !time_attack.has_result() && continues.take();
+                let mut restart_level = continue_taken;
+                match pause_action {
+                    Some(PauseAction::Resume) => pause.resume(),
+                    Some(PauseAction::RestartLevel) => restart_level = true,
+                    Some(PauseAction::LookAround) => pause.look_around(),
+                    Some(PauseAction::Feedback) => feedback_form.open(),
+                    Some(PauseAction::CycleControls) => {
+                        save_data.controls = save_data.controls.next();
+                        input = KeyboardInput::new(save_data.controls);
+                        save_data.save();
+                    }
+                    Some(PauseAction::QuitToMenu) => {
+                        level_tracker.in_game = false;
+                        time_attack.stop();
+                        game_music.stop();
+                        if let Some(sound) = &heartbeat {
+                            tension.update_audio(sound, false);
+                        }
+                    }
+                    None => {}
+                }
+
+                if restart_level {
+                    // Fresh board for the current level, with the score from when it started
+                    snake = Snake::new();
+                    input = KeyboardInput::new(save_data.controls);
+                    arena = Arena::new();
+                    arena.setup(level_tracker.level, &snake);
+                    slow_motion.stop();
+                    stamina.refill();
+                    if time_attack.active {
+                        food.sequence = food_sequence(level_tracker.level).into();
+                    }
+                    food.relocate(&snake, |pos| arena.is_occupied(pos));
+                    score -= objectives.foods_eaten();
+                    objectives.start(level_tracker.level, snake.length());
+                    pet = Pet::new(save_data.best_level);
+                    particles.clear();
+                    popups.clear();
+                    death = DeathSequence::new();
+                    tension = Tension::new();
+                    danger = DangerWarning::new();
+                    level_tracker.level_time = 0.0;
+                    points.restart_level();
+                    if continue_taken {
+                        spectator = Spectator::new();
+                        save_data.continues_left = continues.left;
+                        save_data.save();
+                        game_music.play_for_level(level_tracker.level, save_data.generated_music);
+                        log_event(&format!("Continued from level {}", level_tracker.level));
+                    } else {
+                        pause.resume();
+                        log_event(&format!("Restarted level {}", level_tracker.level));
+                    }
+                }
+
+                let run_summary = RunSummary {
+                    level: level_tracker.level,
+                    tails: score,
+                    points: points.value,
+                    length: snake.length(),
+                    best_combo: points.best_streak,
+                    foods_to_next_level: objectives.foods_left(snake.length()),
+                };
+                match feedback_form.update(&run_summary, &save_data) {
+                    Some(Ok(path)) => toast.show(&format!("Thanks! Report saved to {}", path)),
+                    Some(Err(e)) => {
+                        println!("Warning: could not save feedback report: {}", e);
+                        toast.show("Could not save feedback report");
+                    }
+                    None => {}
+                }
+
+                credits.update(get_frame_time());
+                if credits.showing && (is_key_pressed(KeyCode::Space) || touch::tapped()) {
+                    credits.showing = false;
+                    if !launch.skip_intro {
+                        level_intro.start(level_tracker.level);
                     }
-                    game_music_playing = false;
                 }
 
-                if snake.head() == food.position {
-                    snake.grow();
-                    food.relocate(&snake);
-                    score += 1;
+                let between_levels = credits.showing || breakdown.showing || level_intro.showing;
+                // The game holds still while the console is open
+                if pause.is_running() && !between_levels {
+                    console.update();
+                }
+                if pause.is_running() && !between_levels && !spectator.is_active() && !death.body_gone && !console.open {
+                    console.apply_pending(&mut snake, &mut food, &mut level_tracker, &mut arena, &mut objectives, &mut score);
+
+                    // Update snake speed based on level
+                    snake.update_speed(level_tracker.level, save_data.difficulty);
+
+                    // Input is read once per frame and buffered, each snake move takes one command
+                    for command in input.poll(snake.queued_heading()) {
+                        snake.buffer(command);
+                    }
+                    if let Some(dir) = touch_dir {
+                        snake.buffer(PlayerCommand::SetDirection(dir));
+                    }
+
+                    slow_motion.update(get_frame_time());
+                    let boost_held = Stamina::held() && !console.open;
+                    timestep.advance(get_frame_time());
+                    while level_tracker.in_game
+                        && !between_levels
+                        && !spectator.is_active()
+                        && !death.body_gone
+                        && timestep.step()
+                    {
+                        let delta_time = timestep.tick;
+                        level_tracker.update(delta_time);
+                        stamina.update(delta_time, boost_held);
+                        let started = Instant::now();
+                        snake.speed_multiplier = arena.speed_multiplier_at(snake.head()) * stamina.speed_multiplier();
+                        let pull = arena.gravity.pull_on(&snake);
+                        snake.update(delta_time, pull);
+                        if let Some(exit) = arena.teleporters.check(&snake) {
+                            snake.teleport_head(exit);
+                        }
+                        perf.record(System::Movement, started.elapsed());
+                        points.update(delta_time);
+                        pet.update(delta_time);
+                        let started = Instant::now();
+                        arena.update(delta_time, &snake);
+                        perf.record(System::Arena, started.elapsed());
+                        let started = Instant::now();
+                        if level_tracker.has_mechanic(SpecialMechanic::MovingFood) {
+                            food.wander(delta_time, &snake, |pos| arena.is_occupied(pos));
+                        }
+                        food.update_lifetime(delta_time, &snake, |pos| arena.is_occupied(pos));
+                        if food.boxed_in || arena.is_covered(food.position) {
+                            // A wall or the boss slid over the food, or there was nowhere to put it,
+                            // so find it somewhere reachable
+                            food.relocate(&snake, |pos| arena.is_occupied(pos));
+                        }
+                        perf.record(System::Food, started.elapsed());
+                        let started = Instant::now();
+                        cpu_snake_manager.update(delta_time, level_tracker.level);
+                        perf.record(System::CpuSnakes, started.elapsed());
+                        let started = Instant::now();
+                        tension.update(delta_time, &snake, |pos| arena.is_blocked(pos));
+                        let warned = danger.update(delta_time, &snake, |pos| arena.is_blocked(pos));
+                        perf.record(System::Tension, started.elapsed());
+                        // Audio cues already tick for anything close ahead, so the warning tick only plays without them
+                        if warned && save_data.difficulty.danger_warning() && !save_data.audio_cues {
+                            if let Some(sound) = &sound_effects.danger {
+                                mixer::play_sfx(sound, 1.0);
+                            }
+                        }
+                        if let Some(click) = &move_click {
+                            move_sound.update(&snake, save_data.audio_latency_ms, click);
+                        }
+                        if save_data.audio_cues {
+                            audio_cues.update(&snake, food.position, |pos| arena.is_blocked(pos));
+                        }
+
+                        // Boosting smashes through grown plants rather than crashing into them
+                        if stamina.is_boosting() && !snake.is_invincible() && arena.garden.is_blocked(snake.head()) {
+                            let smashed = arena.garden.smash(snake.head());
+                            let lost = points.lose(smashed as u32 * SMASH_COST);
+                            toast.show(&format!("Smashed {} plants! -{}", smashed, lost));
+                            particles.explode(snake.head(), 24, LIME);
+                            camera::add_trauma(0.3);
+                        }
+
+                        // Only check if player snake is dead
+                        // Checking the head every frame also catches walls that move into it
+                        let started = Instant::now();
+                        let iced = save_data.difficulty.trail_is_deadly() && arena.trail.is_at(snake.head());
+                        let crashed = snake.is_dead()
+                            || (!snake.is_invincible() && !snake.god_mode && (arena.is_blocked(snake.head()) || iced));
+                        perf.record(System::Collision, started.elapsed());
+                        if crashed {
+                            // The body blows apart in slow motion, then the crash screen comes up
+                            death.start(&snake, &theme, &mut particles);
+                            crash_cause = RunEnd::for_crash(&snake, &arena);
+                            slow_motion.stop();
+                            tutorial.stop();
+                            log_event(&format!("Crashed on level {} at length {}", level_tracker.level, snake.length()));
+                            camera::add_trauma(0.8);
+                            particles.explode(snake.head(), 60, theme.snake_head);
+                            if let Some(sound) = &sound_effects.impact {
+                                mixer::play_sfx(sound, 1.0);
+                            }
+
+                            if time_attack.active {
+                                time_attack.finish(None, &mut save_data.best_times);
+                                save_data.history.record(RunRecord::new(
+                                    RunMode::TimeAttack,
+                                    save_data.difficulty,
+                                    points.value,
+                                    level_tracker.level,
+                                    crash_cause,
+                                    level_tracker.level_time,
+                                ));
+                            }
+                            save_data
+                                .stats
+                                .record_death(BoardSnapshot::capture(level_tracker.level, &snake, &food, &arena));
+                            save_data.save();
+                            points.break_combo();
                     
-                    // Only advance level every 5 foods
-                    if score % 5 == 0 {
-                        level_tracker.next_level();
-                        // No need to reset CPU snakes - the manager handles this automatically!
+                            // Stop game music completely
+                            game_music.stop();
+                            if let Some(sound) = &heartbeat {
+                                tension.update_audio(sound, false);
+                            }
+                            // Nothing is eaten or grown on the move that crashed
+                            break;
+                        }
+
+                        let eaten_main = food.is_at(snake.head());
+                        let eaten_extra = arena.extra_food.take(snake.head());
+                        if eaten_extra == Some(FoodKind::Poison) {
+                            snake.shrink(POISON_SHRINK);
+                            let lost = points.lose(POISON_PENALTY);
+                            toast.show(&format!("Poison! -{} and {} shorter", lost, POISON_SHRINK));
+                            particles.explode(snake.head(), 16, POISON_COLOR);
+                            if let Some(sound) = &sound_effects.poison {
+                                mixer::play_sfx(sound, 1.0);
+                                ducker.duck();
+                            }
+                            log_event("Ate poison");
+                        }
+                        if eaten_extra == Some(FoodKind::SlowMotion) {
+                            slow_motion.start();
+                            toast.show("Slow motion!");
+                            particles.explode(snake.head(), 16, SKYBLUE);
+                            log_event("Picked up slow motion");
+                        }
+                        let eaten_extra = eaten_extra.filter(FoodKind::is_food);
+                        let eaten_by_snake = eaten_main || eaten_extra.is_some();
+                        let eaten_by_pet = !eaten_by_snake && !food.boxed_in && pet.try_collect(&snake, food.position);
+                        let eaten_at = if eaten_by_snake { snake.head() } else { food.position };
+
+                        if eaten_by_snake {
+                            snake.grow();
+                            let mut gained = points.register_food();
+                            if eaten_extra == Some(FoodKind::Golden) {
+                                points.add(GOLDEN_FOOD_POINTS);
+                                gained += GOLDEN_FOOD_POINTS;
+                                snake.make_invincible(GOLDEN_INVINCIBILITY);
+                                toast.show(&format!("Golden food! +{} and {}s of invincibility", GOLDEN_FOOD_POINTS, GOLDEN_INVINCIBILITY));
+                                popups.spawn(eaten_at, gained, GOLD);
+                            } else {
+                                popups.spawn(eaten_at, gained, theme.food);
+                            }
+                        } else if eaten_by_pet {
+                            let gained = points.register_pet_food();
+                            popups.spawn(eaten_at, gained, theme.food);
+                        }
+
+                        if eaten_by_snake || eaten_by_pet {
+                            // Every food eaten on the boss level lands a hit
+                            if let Some(boss) = &mut arena.boss {
+                                boss.take_hit();
+                                particles.explode(boss.position, 40, RED);
+                                if let Some(sound) = &sound_effects.impact {
+                                    mixer::play_sfx(sound, 1.0);
+                                    ducker.duck();
+                                }
+                            }
+                            particles.explode(eaten_at, 16, theme.food);
+
+                            arena.garden.plant_seed(eaten_at, save_data.difficulty.seed_chance());
+                            if (eaten_main || eaten_by_pet) && !food.relocate(&snake, |pos| arena.is_occupied(pos)) {
+                                log_event("No room left for the next food");
+                            }
+                            score += 1;
+                            objectives.record_food(eaten_extra);
+                        }
+
+                        save_data.stats.record_length(snake.length());
+                        if let Some(milestone) = milestones.check(snake.length()) {
+                            points.add(milestone.bonus);
+                            popups.spawn(snake.head(), milestone.bonus, GOLD);
+                            particles.explode(snake.head(), 80, GOLD);
+                            toast.show(&format!("Length {}! +{} bonus", milestone.length, milestone.bonus));
+                            if let Some(sound) = &sound_effects.fanfare {
+                                mixer::play_sfx(sound, 1.0);
+                                ducker.duck();
+                            }
+                            log_event(&format!("Reached length {}", milestone.length));
+                        }
+
+                        let level_done = objectives.is_complete(snake.length(), level_tracker.level_time);
+                        if level_done && time_attack.active {
+                            time_attack.finish(Some(level_tracker.level_time), &mut save_data.best_times);
+                            save_data.history.record(RunRecord::new(
+                                RunMode::TimeAttack,
+                                save_data.difficulty,
+                                points.value,
+                                level_tracker.level,
+                                RunEnd::Cleared,
+                                level_tracker.level_time,
+                            ));
+                            save_data.save();
+                            if let Some(sound) = &sound_effects.level_complete {
+                                mixer::play_sfx(sound, 1.0);
+                            }
+                            log_event(&format!("Finished level {} against the clock", level_tracker.level));
+                            level_tracker.in_game = false;
+                            game_music.stop();
+                            if let Some(sound) = &heartbeat {
+                                tension.update_audio(sound, false);
+                            }
+                        } else if level_done {
+                            if let Some(sound) = &sound_effects.level_complete {
+                                mixer::play_sfx(sound, 1.0);
+                                ducker.duck();
+                            }
+                            if level_tracker.level == BOSS_LEVEL && cfg!(feature = "cutscenes") {
+                                credits.start();
+                            } else {
+                                let lines = calculate_level_completion_score(
+                                    points.level_points(),
+                                    level_tracker.level_time,
+                                    snake.length(),
+                                    points.level_best_streak,
+                                );
+                                let bonus = breakdown.start(level_tracker.level, lines);
+                                points.add(bonus);
+                            }
+                            let level_score = LevelScore::new(
+                                &profile_name,
+                                points.level_points(),
+                                save_data.difficulty,
+                                snake.length(),
+                            );
+                            save_data.level_scores.record(level_tracker.level, level_score);
+                            points.start_level();
+
+                            level_tracker.next_level();
+                            arena.setup(level_tracker.level, &snake);
+                            slow_motion.stop();
+                            if game_music.is_playing() {
+                                game_music.play_for_level(level_tracker.level, save_data.generated_music);
+                            }
+                            log_event(&format!("Reached level {}", level_tracker.level));
+                            save_data.checkpoint_level = level_tracker.level;
+                            save_data.save();
+                            // No need to reset CPU snakes - the manager handles this automatically!
+
+                            if level_tracker.level > save_data.best_level {
+                                let old_tier = tier_for_level(save_data.best_level);
+                                save_data.best_level = level_tracker.level;
+                                save_data.save();
+
+                                let new_tier = tier_for_level(save_data.best_level);
+                                if new_tier == 1 && old_tier == 0 {
+                                    toast.show("Companion orb unlocked! It joins you next run");
+                                } else if new_tier > old_tier {
+                                    toast.show("Companion orb upgraded! Faster pickups next run");
+                                }
+                            }
+                            objectives.start(level_tracker.level, snake.length());
+                        }
                     }
                 }
 
-                snake.draw(&theme);
+                if pause.is_running() && level_tracker.in_game && tutorial.update(get_frame_time(), &snake, score) {
+                    save_data.tutorial_complete = true;
+                    save_data.save();
+                }
+
+                if pause.is_running() {
+                    particles.update(get_frame_time() * death.time_scale());
+                    popups.update(get_frame_time() * death.time_scale());
+                    // Updated after gameplay so the SPACE that closes it doesn't also reach the snake
+                    // The next level is announced once the breakdown is closed
+                    let breakdown_was_showing = breakdown.showing;
+                    breakdown.update(get_frame_time());
+                    if breakdown_was_showing && !breakdown.showing && !launch.skip_intro {
+                        level_intro.start(level_tracker.level);
+                    }
+                    level_intro.update(get_frame_time());
+                }
+
+                if death.update(get_frame_time()) {
+                    // The run is over, but the CPU snakes can be watched before heading back
+                    spectator.offer(!time_attack.has_result());
+                }
+                if let Some(choice) = spectator.update(get_frame_time(), &mut cpu_snake_manager, level_tracker.level) {
+                    level_tracker.in_game = false;
+                    match choice {
+                        CrashChoice::Restart => restart_pending = true,
+                        CrashChoice::Statistics => showing_stats = true,
+                        CrashChoice::Results | CrashChoice::Menu => {}
+                    }
+                    if !time_attack.has_result() {
+                        // Out of continues, or chose not to use one: the campaign run is over
+                        if choice == CrashChoice::Results {
+                            continues.finish(run_summary.clone());
+                        }
+                        save_data.checkpoint_level = 0;
+                        save_data.history.record(RunRecord::new(
+                            RunMode::Campaign,
+                            save_data.difficulty,
+                            points.value,
+                            level_tracker.level,
+                            crash_cause,
+                            level_tracker.run_time,
+                        ));
+                        save_data.save();
+                        if cfg!(feature = "leaderboard") && save_data.high_scores.qualifies(points.value) {
+                            let entry = HighScoreEntry::new(points.value, save_data.difficulty, level_tracker.level);
+                            name_entry.open(entry, &profile_name);
+                        }
+                    }
+                }
+
+                if let Some(music) = game_music.current() {
+                    ducker.update(get_frame_time(), music, save_data.music_ducking);
+                }
+
+                if level_tracker.in_game {
+                    if let Some(sound) = &heartbeat {
+                        let between_levels = credits.showing || breakdown.showing || level_intro.showing;
+                        let playing = pause.is_running() && !between_levels && !death.body_gone;
+                        tension.update_audio(sound, playing);
+                    }
+                }
+
+                if camera_on {
+                    follow_camera.begin();
+                }
+                arena.draw(&theme);
+                if !death.body_gone {
+                    snake.draw(&theme, &snake_skin, save_data.smooth_body);
+                    if save_data.path_preview {
+                        assist::draw_path_preview(&snake, |pos| arena.is_blocked(pos));
+                    }
+                }
+                pet.draw(&snake, theme.ui_text);
                 food.draw(&theme);
                 cpu_snake_manager.draw();
+                particles.draw();
+                popups.draw();
+                if camera_on {
+                    follow_camera.end();
+                }
+                slow_motion.draw();
+                tension.draw_vignette();
+                if save_data.difficulty.danger_warning() {
+                    danger.draw();
+                }
+                points.draw_combo_meter(&theme);
+                if minimap_on {
+                    let x = screen_width() - MINIMAP_WIDTH - 20.0;
+                    draw_minimap(x, 42.0 * hud_scale(), &arena, &snake, food.position, &cpu_snake_manager, &theme);
+                }
+                if pause.is_running() {
+                    tutorial.draw(input.controls);
+                }
+
+                pause.draw(&run_summary, input.controls);
+                feedback_form.draw();
+                console.draw();
+                credits.draw();
+                breakdown.draw();
+                level_intro.draw(&theme);
+                spectator.draw(&cpu_snake_manager);
+                if spectator.state == SpectateState::Offering && !time_attack.has_result() {
+                    continues.draw_offer(level_tracker.level);
+                }
+                touch.draw_buttons();
+
+                perf.update(get_frame_time());
+                let counts = [
+                    ("snake cells", snake.length()),
+                    ("cpu snakes", cpu_snake_manager.snakes.len()),
+                    ("plants", arena.garden.plants.len()),
+                    ("particles", particles.len()),
+                ];
+                perf.draw(&counts, snake.occupied_cells());
             }
         }
 
+        camera::update_shake(get_frame_time());
+        display.update(get_frame_time());
+        level::watch(get_frame_time());
+        sound_effects.watch(get_frame_time()).await;
+        toast.update(get_frame_time());
+        toast.draw();
+
         next_frame().await;
     }
 }
@@ -0,0 +1,30 @@
+// Snake lengths worth celebrating, and the bonus points each is worth
+const MILESTONES: [(usize, u32); 4] = [(10, 100), (25, 250), (50, 500), (100, 1000)];
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct LengthMilestone {
+    pub length: usize,
+    pub bonus: u32,
+}
+
+// Which milestones the current run has passed, so each one only fires once
+pub struct Milestones {
+    next: usize, // Index into MILESTONES
+}
+
+impl Milestones {
+    pub fn new() -> Self {
+        Self { next: 0 }
+    }
+
+    // The milestone reached at this length, if there's a new one. Restarting a level
+    // shrinks the snake, but milestones already passed this run stay passed.
+    pub fn check(&mut self, length: usize) -> Option<LengthMilestone> {
+        let (target, bonus) = *MILESTONES.get(self.next)?;
+        if length < target {
+            return None;
+        }
+        self.next += 1;
+        Some(LengthMilestone { length: target, bonus })
+    }
+}
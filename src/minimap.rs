@@ -0,0 +1,50 @@
+use macroquad::prelude::*;
+use crate::arena::Arena;
+use crate::cpu_snake::CpuSnakeManager;
+use crate::grid::{GRID_HEIGHT, GRID_WIDTH};
+use crate::snake::{Segment, Snake};
+use crate::themes::Theme;
+
+pub const MINIMAP_WIDTH: f32 = 120.0;
+
+// The whole board in a corner, a few pixels per cell, for when the follow camera
+// has zoomed in past the edges of the level
+pub fn draw_minimap(
+    x: f32,
+    y: f32,
+    arena: &Arena,
+    snake: &Snake,
+    food: Segment,
+    cpu_snakes: &CpuSnakeManager,
+    theme: &Theme,
+) {
+    let scale = MINIMAP_WIDTH / GRID_WIDTH as f32;
+    let height = GRID_HEIGHT as f32 * scale;
+    let cell = |position: Segment, color: Color| {
+        draw_rectangle(x + position.x as f32 * scale, y + position.y as f32 * scale, scale, scale, color);
+    };
+
+    draw_rectangle(x, y, MINIMAP_WIDTH, height, Color::new(0.0, 0.0, 0.0, 0.6));
+
+    for cell_y in 0..GRID_HEIGHT {
+        for cell_x in 0..GRID_WIDTH {
+            let position = Segment { x: cell_x, y: cell_y };
+            if arena.is_blocked(position) {
+                cell(position, GRAY);
+            }
+        }
+    }
+    for cpu_snake in &cpu_snakes.snakes {
+        for segment in &cpu_snake.body {
+            cell(*segment, Color::new(0.6, 0.6, 0.6, 0.6));
+        }
+    }
+    cell(food, theme.food);
+    for segment in snake.body.iter().skip(1) {
+        cell(*segment, theme.snake_body);
+    }
+    cell(snake.head(), theme.snake_head);
+
+    arena.teleporters.draw_links(x, y, scale);
+    draw_rectangle_lines(x, y, MINIMAP_WIDTH, height, 1.0, GRAY);
+}
@@ -0,0 +1,160 @@
+use macroquad::prelude::*;
+use macroquad::audio::{play_sound, PlaySoundParams, Sound};
+use lazy_static::lazy_static;
+use std::sync::Mutex;
+
+const VOLUME_STEP: f32 = 0.1;
+
+// Every sound plays at its own base volume times the master level and its bus level
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct VolumeLevels {
+    pub master: f32,
+    pub music: f32,
+    pub sfx: f32,
+}
+
+impl VolumeLevels {
+    pub fn new() -> Self {
+        Self {
+            master: 1.0,
+            music: 1.0,
+            sfx: 1.0,
+        }
+    }
+}
+
+lazy_static! {
+    // Shared so anything that plays a sound can scale it without the settings being passed around
+    static ref LEVELS: Mutex<VolumeLevels> = Mutex::new(VolumeLevels::new());
+    // Set by --mute, silences everything without touching the saved levels
+    static ref MUTED: Mutex<bool> = Mutex::new(false);
+}
+
+pub fn set_levels(levels: VolumeLevels) {
+    *LEVELS.lock().unwrap() = levels;
+}
+
+pub fn set_muted(muted: bool) {
+    *MUTED.lock().unwrap() = muted;
+}
+
+fn master(levels: &VolumeLevels) -> f32 {
+    if *MUTED.lock().unwrap() { 0.0 } else { levels.master }
+}
+
+pub fn music_volume(base: f32) -> f32 {
+    let levels = LEVELS.lock().unwrap();
+    base * master(&levels) * levels.music
+}
+
+pub fn sfx_volume(base: f32) -> f32 {
+    let levels = LEVELS.lock().unwrap();
+    base * master(&levels) * levels.sfx
+}
+
+pub fn play_sfx(sound: &Sound, base: f32) {
+    play_sound(sound, PlaySoundParams { looped: false, volume: sfx_volume(base) });
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Bus {
+    Master,
+    Music,
+    Sfx,
+}
+
+const BUSES: [Bus; 3] = [Bus::Master, Bus::Music, Bus::Sfx];
+
+// Title screen sliders for the three volume levels
+pub struct VolumeMenu {
+    pub showing: bool,
+    selected: usize,
+}
+
+impl VolumeMenu {
+    pub fn new() -> Self {
+        Self {
+            showing: false,
+            selected: 0,
+        }
+    }
+
+    pub fn open(&mut self) {
+        self.showing = true;
+        self.selected = 0;
+    }
+
+    // Returns true when a level changed, so the caller can rescale what's playing and save
+    pub fn update(&mut self, levels: &mut VolumeLevels, preview: Option<&Sound>) -> bool {
+        if is_key_pressed(KeyCode::Escape) || is_key_pressed(KeyCode::Enter) {
+            self.showing = false;
+            return false;
+        }
+
+        if is_key_pressed(KeyCode::Up) {
+            self.selected = (self.selected + BUSES.len() - 1) % BUSES.len();
+        } else if is_key_pressed(KeyCode::Down) {
+            self.selected = (self.selected + 1) % BUSES.len();
+        }
+
+        let change = if is_key_pressed(KeyCode::Left) {
+            -VOLUME_STEP
+        } else if is_key_pressed(KeyCode::Right) {
+            VOLUME_STEP
+        } else {
+            return false;
+        };
+
+        let bus = BUSES[self.selected];
+        let level = match bus {
+            Bus::Master => &mut levels.master,
+            Bus::Music => &mut levels.music,
+            Bus::Sfx => &mut levels.sfx,
+        };
+        *level = ((*level + change) * 10.0).round().clamp(0.0, 10.0) / 10.0;
+        set_levels(*levels);
+
+        // Music is already playing to judge by, sound effects need a blip
+        if bus != Bus::Music {
+            if let Some(sound) = preview {
+                play_sfx(sound, 1.0);
+            }
+        }
+        true
+    }
+
+    pub fn draw(&self, levels: &VolumeLevels) {
+        if !self.showing {
+            return;
+        }
+
+        draw_rectangle(0.0, 0.0, screen_width(), screen_height(), Color::new(0.0, 0.0, 0.0, 0.85));
+        let center_y = screen_height() / 2.0;
+        draw_centered("VOLUME", center_y - 120.0, 48.0, SKYBLUE);
+
+        let bar_width = 240.0;
+        for (i, bus) in BUSES.iter().enumerate() {
+            let (name, level) = match bus {
+                Bus::Master => ("Master", levels.master),
+                Bus::Music => ("Music", levels.music),
+                Bus::Sfx => ("Sound Effects", levels.sfx),
+            };
+            let y = center_y - 50.0 + i as f32 * 50.0;
+            let color = if i == self.selected { WHITE } else { GRAY };
+            let bar_x = screen_width() / 2.0 - 20.0;
+
+            let name_width = measure_text(name, None, 28, 1.0).width;
+            draw_text(name, bar_x - name_width - 20.0, y, 28.0, color);
+            draw_rectangle(bar_x, y - 18.0, bar_width * level, 20.0, if i == self.selected { GREEN } else { DARKGREEN });
+            draw_rectangle_lines(bar_x, y - 18.0, bar_width, 20.0, 2.0, color);
+            draw_text(&format!("{}%", (level * 100.0).round()), bar_x + bar_width + 12.0, y, 24.0, color);
+        }
+
+        draw_centered("UP/DOWN - Choose   LEFT/RIGHT - Adjust   ENTER - Done", center_y + 130.0, 22.0, LIGHTGRAY);
+    }
+}
+
+fn draw_centered(text: &str, y: f32, size: f32, color: Color) {
+    let width = measure_text(text, None, size as u16, 1.0).width;
+    draw_text(text, (screen_width() - width) / 2.0, y, size, color);
+}
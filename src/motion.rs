@@ -0,0 +1,25 @@
+use macroquad::prelude::*;
+use lazy_static::lazy_static;
+use std::sync::Mutex;
+
+lazy_static! {
+    // Read every frame by the shake, particles, and pulsing text, so switching it takes effect at once
+    static ref REDUCED_MOTION: Mutex<bool> = Mutex::new(false);
+}
+
+pub fn set_reduced_motion(reduced: bool) {
+    *REDUCED_MOTION.lock().unwrap() = reduced;
+}
+
+pub fn reduced_motion() -> bool {
+    *REDUCED_MOTION.lock().unwrap()
+}
+
+// Gently pulsing value for prompts and highlights, which holds at `base` with reduced motion on
+pub fn pulse(speed: f64, depth: f64, base: f64) -> f32 {
+    if reduced_motion() {
+        base as f32
+    } else {
+        ((get_time() * speed).sin() * depth + base) as f32
+    }
+}
@@ -0,0 +1,225 @@
+use macroquad::audio::{load_sound, play_sound, stop_sound, PlaySoundParams, Sound};
+#[cfg(feature = "audio-procedural")]
+use macroquad::audio::load_sound_from_bytes;
+#[cfg(feature = "audio-procedural")]
+use std::f32::consts::TAU;
+use crate::mixer::music_volume;
+use crate::sfx::MUSIC_VOLUME;
+#[cfg(feature = "audio-procedural")]
+use crate::tension::encode_wav;
+
+// Level themes, same as themes::get_theme (level % 10)
+const THEME_COUNT: usize = 10;
+
+// The generated tunes below are only compiled in with the `audio-procedural` feature.
+// Without it every level plays the recorded game song.
+#[cfg(feature = "audio-procedural")]
+const SAMPLE_RATE: u32 = 22050;
+// Eighth notes in one loop of a tune
+#[cfg(feature = "audio-procedural")]
+const STEPS: usize = 32;
+
+// Semitones above the root
+#[cfg(feature = "audio-procedural")]
+const MAJOR: [i32; 5] = [0, 2, 4, 7, 9];
+#[cfg(feature = "audio-procedural")]
+const MINOR: [i32; 5] = [0, 3, 5, 7, 10];
+#[cfg(feature = "audio-procedural")]
+const DORIAN: [i32; 5] = [0, 2, 3, 7, 9];
+#[cfg(feature = "audio-procedural")]
+const PHRYGIAN: [i32; 5] = [0, 1, 5, 7, 8];
+#[cfg(feature = "audio-procedural")]
+const WHOLE_TONE: [i32; 5] = [0, 2, 4, 6, 8];
+
+#[cfg(feature = "audio-procedural")]
+#[derive(Clone, Copy)]
+enum Wave {
+    Sine,
+    Triangle,
+    Square,
+}
+
+#[cfg(feature = "audio-procedural")]
+struct Tune {
+    root: f32, // Hz
+    scale: [i32; 5],
+    tempo: f32, // Beats per minute
+    wave: Wave,
+}
+
+// One tune per theme, in the same order as themes::get_theme
+#[cfg(feature = "audio-procedural")]
+const TUNES: [Tune; THEME_COUNT] = [
+    // Monochrome master
+    Tune { root: 110.0, scale: PHRYGIAN, tempo: 132.0, wave: Wave::Square },
+    // Classic green
+    Tune { root: 130.81, scale: MAJOR, tempo: 120.0, wave: Wave::Triangle },
+    // Sunset orange
+    Tune { root: 146.83, scale: DORIAN, tempo: 104.0, wave: Wave::Sine },
+    // Cyberpunk purple
+    Tune { root: 123.47, scale: MINOR, tempo: 140.0, wave: Wave::Square },
+    // Arctic ice
+    Tune { root: 164.81, scale: WHOLE_TONE, tempo: 92.0, wave: Wave::Sine },
+    // Royal gold
+    Tune { root: 116.54, scale: MAJOR, tempo: 112.0, wave: Wave::Triangle },
+    // Neon pink
+    Tune { root: 138.59, scale: DORIAN, tempo: 128.0, wave: Wave::Square },
+    // Matrix green
+    Tune { root: 103.83, scale: MINOR, tempo: 136.0, wave: Wave::Triangle },
+    // Fire and ice
+    Tune { root: 155.56, scale: PHRYGIAN, tempo: 120.0, wave: Wave::Sine },
+    // Desert sand
+    Tune { root: 98.0, scale: PHRYGIAN, tempo: 100.0, wave: Wave::Triangle },
+];
+
+#[cfg(feature = "audio-procedural")]
+fn oscillator(wave: Wave, phase: f32) -> f32 {
+    match wave {
+        Wave::Sine => (phase * TAU).sin(),
+        Wave::Triangle => 4.0 * (phase.fract() - 0.5).abs() - 1.0,
+        // Softened so it doesn't buzz over the sound effects
+        Wave::Square => (phase * TAU).sin().signum() * 0.5,
+    }
+}
+
+#[cfg(feature = "audio-procedural")]
+fn note_frequency(tune: &Tune, degree: usize, octave: i32) -> f32 {
+    let semitones = tune.scale[degree % tune.scale.len()] + 12 * octave;
+    tune.root * 2f32.powf(semitones as f32 / 12.0)
+}
+
+// A seamless loop: a melody picked from the tune's scale over a root and fifth bass line
+// and a soft kick on every beat. The same theme always gets the same melody.
+#[cfg(feature = "audio-procedural")]
+fn tune_wav(theme: usize) -> Vec<u8> {
+    let tune = &TUNES[theme];
+    let step_length = 30.0 / tune.tempo;
+    let step_samples = (SAMPLE_RATE as f32 * step_length) as usize;
+
+    // Melody degrees, None for a rest
+    let mut seed = 0x2545_f491u32.wrapping_mul(theme as u32 + 1);
+    let melody: Vec<Option<(usize, i32)>> = (0..STEPS)
+        .map(|_| {
+            seed ^= seed << 13;
+            seed ^= seed >> 17;
+            seed ^= seed << 5;
+            if seed.is_multiple_of(4) {
+                None
+            } else {
+                Some(((seed >> 8) as usize % 5, 2 + (seed >> 16) as i32 % 2))
+            }
+        })
+        .collect();
+
+    let mut samples = Vec::with_capacity(step_samples * STEPS);
+    for (step, note) in melody.iter().enumerate() {
+        // Root for the first half of each bar, fifth for the second
+        let bass = note_frequency(tune, if step % 8 < 4 { 0 } else { 3 }, 0);
+        let lead = note.map(|(degree, octave)| note_frequency(tune, degree, octave));
+
+        for i in 0..step_samples {
+            let t = i as f32 / SAMPLE_RATE as f32;
+            let since_beat = t + if step % 2 == 1 { step_length } else { 0.0 };
+            let absolute = (step * step_samples + i) as f32 / SAMPLE_RATE as f32;
+
+            let bass_value = oscillator(Wave::Sine, absolute * bass) * 0.35;
+            let lead_value = lead.map_or(0.0, |frequency| {
+                let envelope = (t / 0.005).min(1.0) * (-t * 7.0).exp();
+                oscillator(tune.wave, t * frequency) * envelope * 0.3
+            });
+            let kick = (since_beat * (60.0 - since_beat * 40.0) * TAU).sin() * (-since_beat * 20.0).exp() * 0.4;
+
+            let value = bass_value + lead_value + kick;
+            samples.push((value.clamp(-1.0, 1.0) * i16::MAX as f32 * 0.7) as i16);
+        }
+    }
+
+    encode_wav(SAMPLE_RATE, &samples)
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Track {
+    Song,
+    Theme(usize),
+}
+
+// The recorded game song plus a generated tune for each level theme
+pub struct GameMusic {
+    song: Option<Sound>,
+    themes: Vec<Option<Sound>>,
+    playing: Option<Track>,
+}
+
+impl GameMusic {
+    pub async fn load() -> Self {
+        let song = match load_sound("assets/snake_game.wav").await {
+            Ok(sound) => {
+                println!("Game music loaded successfully!");
+                Some(sound)
+            },
+            Err(e) => {
+                println!("Warning: Could not load game music: {:?}", e);
+                None
+            }
+        };
+
+        #[cfg(feature = "audio-procedural")]
+        let themes = generate_themes().await;
+        #[cfg(not(feature = "audio-procedural"))]
+        let themes = Vec::new();
+
+        Self { song, themes, playing: None }
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.playing.is_some()
+    }
+
+    // Starts the right music for the level, switching tracks if the theme changed
+    pub fn play_for_level(&mut self, level: usize, generated: bool) {
+        let theme = level % THEME_COUNT;
+        let track = if generated && self.themes.get(theme).is_some_and(Option::is_some) { Track::Theme(theme) } else { Track::Song };
+        if self.playing == Some(track) {
+            return;
+        }
+
+        self.stop();
+        if let Some(sound) = self.sound(track) {
+            play_sound(sound, PlaySoundParams { looped: true, volume: music_volume(MUSIC_VOLUME) });
+            self.playing = Some(track);
+        }
+    }
+
+    pub fn stop(&mut self) {
+        if let Some(sound) = self.current() {
+            stop_sound(sound);
+        }
+        self.playing = None;
+    }
+
+    pub fn current(&self) -> Option<&Sound> {
+        self.playing.and_then(|track| self.sound(track))
+    }
+
+    fn sound(&self, track: Track) -> Option<&Sound> {
+        match track {
+            Track::Song => self.song.as_ref(),
+            Track::Theme(theme) => self.themes.get(theme).and_then(Option::as_ref),
+        }
+    }
+}
+
+#[cfg(feature = "audio-procedural")]
+async fn generate_themes() -> Vec<Option<Sound>> {
+    let mut themes = Vec::with_capacity(THEME_COUNT);
+    for theme in 0..THEME_COUNT {
+        themes.push(match load_sound_from_bytes(&tune_wav(theme)).await {
+            Ok(sound) => Some(sound),
+            Err(e) => {
+                println!("Warning: Could not create music for theme {}: {:?}", theme, e);
+                None
+            }
+        });
+    }
+    themes
+}
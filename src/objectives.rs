@@ -0,0 +1,130 @@
+use macroquad::prelude::*;
+use crate::food::FoodKind;
+use crate::hud::hud_scale;
+use crate::level::{foods_to_clear, BOSS_LEVEL};
+use crate::themes::Theme;
+
+// Extra length the growth levels ask for, beyond what the snake starts them with
+const GROWTH_TARGET: usize = 8;
+const SURVIVAL_TIME: f32 = 45.0;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Objective {
+    EatFood(u32),
+    ReachLength(usize),
+    SurviveSeconds(f32),
+    EatGoldenFoods(u32),
+}
+
+// Every objective on the list has to be met to clear the level
+pub fn objectives_for(level: usize, start_length: usize) -> Vec<Objective> {
+    // Foods that clear a level when it asks for nothing else
+    let foods = foods_to_clear(level);
+    if level == BOSS_LEVEL {
+        return vec![Objective::EatFood(foods)];
+    }
+
+    match level % 10 {
+        // Golden food shows up on the levels with several foods out at once
+        7 => vec![Objective::EatFood(foods), Objective::EatGoldenFoods(1)],
+        // Food that wanders off is worth chasing for the length alone
+        8 => vec![Objective::ReachLength(start_length + GROWTH_TARGET)],
+        // Hold out against the gravity wells as well as eating
+        9 => vec![Objective::EatFood(foods), Objective::SurviveSeconds(SURVIVAL_TIME)],
+        _ => vec![Objective::EatFood(foods)],
+    }
+}
+
+// Progress on the current level's objectives. Time on the level comes from the LevelTracker.
+pub struct ObjectiveTracker {
+    objectives: Vec<Objective>,
+    foods: u32,
+    golden_foods: u32,
+}
+
+impl ObjectiveTracker {
+    pub fn new() -> Self {
+        Self {
+            objectives: objectives_for(1, 0),
+            foods: 0,
+            golden_foods: 0,
+        }
+    }
+
+    pub fn start(&mut self, level: usize, snake_length: usize) {
+        self.objectives = objectives_for(level, snake_length);
+        self.foods = 0;
+        self.golden_foods = 0;
+    }
+
+    // `kind` is None for the main food, which counts as a normal one
+    pub fn record_food(&mut self, kind: Option<FoodKind>) {
+        self.foods += 1;
+        if kind == Some(FoodKind::Golden) {
+            self.golden_foods += 1;
+        }
+    }
+
+    pub fn foods_eaten(&self) -> u32 {
+        self.foods
+    }
+
+    fn is_met(&self, objective: Objective, length: usize, level_time: f32) -> bool {
+        match objective {
+            Objective::EatFood(target) => self.foods >= target,
+            Objective::ReachLength(target) => length >= target,
+            Objective::SurviveSeconds(target) => level_time >= target,
+            Objective::EatGoldenFoods(target) => self.golden_foods >= target,
+        }
+    }
+
+    pub fn is_complete(&self, length: usize, level_time: f32) -> bool {
+        self.objectives.iter().all(|objective| self.is_met(*objective, length, level_time))
+    }
+
+    // Food still to eat for the food or length objectives, for the pause screen summary
+    pub fn foods_left(&self, length: usize) -> u32 {
+        self.objectives
+            .iter()
+            .map(|objective| match *objective {
+                Objective::EatFood(target) => target.saturating_sub(self.foods),
+                Objective::ReachLength(target) => target.saturating_sub(length) as u32,
+                _ => 0,
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    // One line under the level number, with finished objectives in green
+    pub fn draw(&self, length: usize, level_time: f32, theme: &Theme) {
+        let parts: Vec<(String, bool)> = self
+            .objectives
+            .iter()
+            .map(|objective| {
+                let text = match *objective {
+                    Objective::EatFood(target) => t!("objective-food", eaten = self.foods.min(target), target = target),
+                    Objective::ReachLength(target) => t!("objective-length", length = length.min(target), target = target),
+                    Objective::SurviveSeconds(target) => {
+                        t!("objective-survive", seconds = (target - level_time).max(0.0).ceil())
+                    }
+                    Objective::EatGoldenFoods(target) => {
+                        t!("objective-golden", eaten = self.golden_foods.min(target), target = target)
+                    }
+                };
+                (text, self.is_met(*objective, length, level_time))
+            })
+            .collect();
+
+        let hud = hud_scale();
+        let gap = 24.0 * hud;
+        let widths: Vec<f32> = parts
+            .iter()
+            .map(|(text, _)| measure_text(text, None, (18.0 * hud) as u16, 1.0).width)
+            .collect();
+        let mut x = (screen_width() - widths.iter().sum::<f32>() - gap * (parts.len() - 1) as f32) / 2.0;
+        for ((text, met), width) in parts.iter().zip(widths) {
+            draw_text(text, x, 72.0 * hud, 18.0 * hud, if *met { GREEN } else { theme.ui_text });
+            x += width + gap;
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use macroquad::prelude::*;
+use crate::motion;
+use crate::controls::ControlScheme;
+
+// Controls splash shown over the title screen on the very first launch
+pub fn draw_controls_splash(controls: ControlScheme) {
+    let panel_width = 520.0;
+    let panel_height = 320.0;
+    let panel_x = (screen_width() - panel_width) / 2.0;
+    let panel_y = (screen_height() - panel_height) / 2.0;
+
+    draw_rectangle(0.0, 0.0, screen_width(), screen_height(), Color::new(0.0, 0.0, 0.0, 0.6));
+    draw_rectangle(panel_x, panel_y, panel_width, panel_height, Color::new(0.05, 0.1, 0.05, 0.95));
+    draw_rectangle_lines(panel_x, panel_y, panel_width, panel_height, 2.0, GREEN);
+
+    let heading = "HOW TO PLAY";
+    let heading_width = measure_text(heading, None, 40, 1.0).width;
+    draw_text(heading, (screen_width() - heading_width) / 2.0, panel_y + 50.0, 40.0, GREEN);
+
+    let lines = controls.help_lines().iter().chain(&[
+        "Eat the food to grow and score",
+        "Eat quickly to build a combo multiplier",
+        "Avoid the walls and your own tail",
+        "Every 5 foods takes you to the next level",
+    ]);
+    for (i, line) in lines.enumerate() {
+        draw_text(line, panel_x + 40.0, panel_y + 100.0 + i as f32 * 32.0, 26.0, WHITE);
+    }
+
+    let prompt = "Press SPACE to begin";
+    let prompt_width = measure_text(prompt, None, 28, 1.0).width;
+    let pulse = motion::pulse(4.0, 0.3, 0.7);
+    draw_text(
+        prompt,
+        (screen_width() - prompt_width) / 2.0,
+        panel_y + panel_height - 25.0,
+        28.0,
+        Color::new(0.8, 0.8, 0.8, pulse),
+    );
+}
@@ -0,0 +1,245 @@
+use macroquad::prelude::*;
+use ::rand::Rng;
+use crate::grid::{CELL_SIZE, get_offset};
+use crate::motion::reduced_motion;
+use crate::snake::Segment;
+use crate::rng;
+
+// With reduced motion, bursts are a quarter the size and only this many particles are on screen at once
+const REDUCED_MAX_PARTICLES: usize = 40;
+// Fraction of velocity kept each second
+const DRAG: f32 = 0.2;
+// Chunks each snake segment breaks into when it explodes
+const DEBRIS_PER_SEGMENT: usize = 4;
+
+// How many particles and score popups can be on screen at once. Their pools are made this
+// big up front, so a flurry of explosions can't tank the frame rate or allocate mid-game.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum EffectsLimit {
+    Low,
+    Medium,
+    High,
+}
+
+impl EffectsLimit {
+    pub fn next(&self) -> EffectsLimit {
+        match self {
+            EffectsLimit::Low => EffectsLimit::Medium,
+            EffectsLimit::Medium => EffectsLimit::High,
+            EffectsLimit::High => EffectsLimit::Low,
+        }
+    }
+
+    pub fn particle_cap(&self) -> usize {
+        match self {
+            EffectsLimit::Low => 150,
+            EffectsLimit::Medium => 300,
+            EffectsLimit::High => 600,
+        }
+    }
+
+    pub fn popup_cap(&self) -> usize {
+        match self {
+            EffectsLimit::Low => 8,
+            EffectsLimit::Medium => 16,
+            EffectsLimit::High => 32,
+        }
+    }
+
+    pub fn save_key(&self) -> &'static str {
+        match self {
+            EffectsLimit::Low => "low",
+            EffectsLimit::Medium => "medium",
+            EffectsLimit::High => "high",
+        }
+    }
+
+    pub fn from_save_key(key: &str) -> EffectsLimit {
+        match key {
+            "low" => EffectsLimit::Low,
+            "medium" => EffectsLimit::Medium,
+            _ => EffectsLimit::High,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Particle {
+    pub position: Vec2, // In cells, so particles stay attached to the grid
+    pub velocity: Vec2,
+    pub life: f32,
+    pub max_life: f32,
+    pub start_color: Color,
+    pub end_color: Color,
+    pub start_size: f32,
+    pub end_size: f32,
+}
+
+impl Particle {
+    // An empty pool slot
+    fn unused() -> Self {
+        Self {
+            position: Vec2::ZERO,
+            velocity: Vec2::ZERO,
+            life: 0.0,
+            max_life: 1.0,
+            start_color: BLANK,
+            end_color: BLANK,
+            start_size: 0.0,
+            end_size: 0.0,
+        }
+    }
+}
+
+// A fixed pool of particles. The first `active` are live, and a dead one is swapped
+// to the end of them so its slot gets reused by the next burst.
+pub struct ParticleSystem {
+    pool: Vec<Particle>,
+    active: usize,
+}
+
+impl ParticleSystem {
+    pub fn new(cap: usize) -> Self {
+        Self {
+            pool: vec![Particle::unused(); cap],
+            active: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.active
+    }
+
+    pub fn clear(&mut self) {
+        self.active = 0;
+    }
+
+    // Grows or shrinks the pool when the effects limit changes, dropping any live particles past the new size
+    pub fn set_cap(&mut self, cap: usize) {
+        self.pool.resize(cap, Particle::unused());
+        self.active = self.active.min(cap);
+    }
+
+    // Room left for new particles
+    fn free(&self) -> usize {
+        let cap = if reduced_motion() { REDUCED_MAX_PARTICLES.min(self.pool.len()) } else { self.pool.len() };
+        cap.saturating_sub(self.active)
+    }
+
+    fn spawn(&mut self, particle: Particle) {
+        self.pool[self.active] = particle;
+        self.active += 1;
+    }
+
+    // Burst of `count` particles flying out from the center of a cell
+    pub fn explode(&mut self, cell: Segment, count: usize, color: Color) {
+        let mut rng = rng::effects();
+        let center = vec2(cell.x as f32 + 0.5, cell.y as f32 + 0.5);
+        let count = if reduced_motion() { count / 4 } else { count };
+        let count = count.min(self.free());
+
+        for _ in 0..count {
+            let angle = rng.gen_range(0.0..std::f32::consts::TAU);
+            let speed = rng.gen_range(2.0..10.0);
+            let life = rng.gen_range(0.4..1.0);
+
+            self.spawn(Particle {
+                position: center,
+                velocity: vec2(angle.cos(), angle.sin()) * speed,
+                life,
+                max_life: life,
+                start_color: color,
+                end_color: Color::new(color.r * 0.5, color.g * 0.5, color.b * 0.5, 0.0),
+                start_size: rng.gen_range(0.15..0.35),
+                end_size: 0.02,
+            });
+        }
+    }
+
+    // Chunks of a snake segment, thrown away from `center` harder the closer they started to it
+    pub fn debris(&mut self, cell: Segment, center: Segment, color: Color) {
+        let mut rng = rng::effects();
+        let position = vec2(cell.x as f32 + 0.5, cell.y as f32 + 0.5);
+        let away = position - vec2(center.x as f32 + 0.5, center.y as f32 + 0.5);
+        let push = if away.length() > 0.0 { away.normalize() * (12.0 / away.length().sqrt()) } else { Vec2::ZERO };
+        let count = if reduced_motion() { 1 } else { DEBRIS_PER_SEGMENT };
+        let count = count.min(self.free());
+
+        for _ in 0..count {
+            let angle = rng.gen_range(0.0..std::f32::consts::TAU);
+            let scatter = vec2(angle.cos(), angle.sin()) * rng.gen_range(1.0..5.0);
+            let life = rng.gen_range(1.0..1.8);
+
+            self.spawn(Particle {
+                position: position + scatter * 0.05,
+                velocity: push + scatter,
+                life,
+                max_life: life,
+                start_color: color,
+                end_color: Color::new(color.r * 0.3, color.g * 0.3, color.b * 0.3, 0.0),
+                start_size: rng.gen_range(0.25..0.45),
+                end_size: 0.05,
+            });
+        }
+    }
+
+    pub fn update(&mut self, delta_time: f32) {
+        let drag = DRAG.powf(delta_time);
+
+        // Particles only ever slow down, so once one leaves the screen it's gone for good
+        let view = view_rect();
+        let mut index = 0;
+        while index < self.active {
+            let particle = &mut self.pool[index];
+            particle.position += particle.velocity * delta_time;
+            particle.velocity *= drag;
+            particle.life -= delta_time;
+
+            if particle.life > 0.0 && view.contains(particle.position) {
+                index += 1;
+            } else {
+                // The last live particle takes this slot and gets updated next
+                self.active -= 1;
+                self.pool.swap(index, self.active);
+            }
+        }
+        // Switching reduced motion on mid-burst trims what's already flying
+        if reduced_motion() {
+            self.active = self.active.min(REDUCED_MAX_PARTICLES);
+        }
+    }
+
+    pub fn draw(&self) {
+        let offset = get_offset();
+        let view = view_rect();
+        for particle in self.pool[..self.active].iter().filter(|particle| view.contains(particle.position)) {
+            // 0 when freshly spawned, 1 when about to disappear
+            let t = 1.0 - particle.life / particle.max_life;
+            let color = Color::new(
+                particle.start_color.r + (particle.end_color.r - particle.start_color.r) * t,
+                particle.start_color.g + (particle.end_color.g - particle.start_color.g) * t,
+                particle.start_color.b + (particle.end_color.b - particle.start_color.b) * t,
+                particle.start_color.a + (particle.end_color.a - particle.start_color.a) * t,
+            );
+            let size = particle.start_size + (particle.end_size - particle.start_size) * t;
+
+            draw_circle(
+                offset.x + particle.position.x * CELL_SIZE,
+                offset.y + particle.position.y * CELL_SIZE,
+                size * CELL_SIZE,
+                color,
+            );
+        }
+    }
+}
+
+// The visible screen in cell units, with a one cell margin for particle radius
+fn view_rect() -> Rect {
+    let offset = get_offset();
+    Rect::new(
+        -offset.x / CELL_SIZE - 1.0,
+        -offset.y / CELL_SIZE - 1.0,
+        screen_width() / CELL_SIZE + 2.0,
+        screen_height() / CELL_SIZE + 2.0,
+    )
+}
@@ -0,0 +1,223 @@
+use macroquad::prelude::*;
+use crate::controls::ControlScheme;
+use crate::rng::seed;
+use crate::summary::RunSummary;
+
+// Away this long and the player gets a recap before the countdown
+const LONG_ABSENCE: f64 = 600.0;
+// A frame gap this long means the window was minimised or the machine slept
+const SUSPEND_GAP: f64 = 1.0;
+const COUNTDOWN: f32 = 3.0;
+const BUTTON_WIDTH: f32 = 360.0;
+const BUTTON_HEIGHT: f32 = 46.0;
+const BUTTON_SPACING: f32 = 58.0;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum PauseMode {
+    Running,
+    Paused,
+    LookAround, // Paused, with the menu put away so the board can be panned and zoomed
+    WelcomeBack,
+    Countdown(f32),
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum PauseAction {
+    Resume,
+    RestartLevel,
+    LookAround,
+    CycleControls,
+    Feedback,
+    QuitToMenu,
+}
+
+const BUTTONS: [PauseAction; 6] = [
+    PauseAction::Resume,
+    PauseAction::RestartLevel,
+    PauseAction::LookAround,
+    PauseAction::CycleControls,
+    PauseAction::Feedback,
+    PauseAction::QuitToMenu,
+];
+
+pub struct Pause {
+    pub mode: PauseMode,
+    paused_at: f64,
+    last_frame: f64,
+    selected: usize,
+}
+
+impl Pause {
+    pub fn new() -> Self {
+        Self {
+            mode: PauseMode::Running,
+            paused_at: 0.0,
+            last_frame: get_time(),
+            selected: 0,
+        }
+    }
+
+    // Which menu button has focus
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.mode == PauseMode::Running
+    }
+
+    pub fn pause(&mut self) {
+        self.mode = PauseMode::Paused;
+        self.paused_at = get_time();
+        self.selected = 0;
+    }
+
+    pub fn resume(&mut self) {
+        self.mode = match self.mode {
+            PauseMode::Paused | PauseMode::LookAround if get_time() - self.paused_at > LONG_ABSENCE => {
+                PauseMode::WelcomeBack
+            }
+            PauseMode::Paused | PauseMode::LookAround | PauseMode::WelcomeBack => PauseMode::Countdown(COUNTDOWN),
+            mode => mode,
+        };
+    }
+
+    pub fn update(&mut self, delta_time: f32) {
+        // Treat a long gap between frames as the game being paused from the moment it stopped
+        let now = get_time();
+        if self.is_running() && now - self.last_frame > SUSPEND_GAP {
+            self.mode = PauseMode::Paused;
+            self.paused_at = self.last_frame;
+        }
+        self.last_frame = now;
+
+        if let PauseMode::Countdown(remaining) = self.mode {
+            let remaining = remaining - delta_time;
+            self.mode = if remaining <= 0.0 {
+                PauseMode::Running
+            } else {
+                PauseMode::Countdown(remaining)
+            };
+        }
+    }
+
+    // Handles the pause menu buttons, by mouse, arrow keys, or their shortcut keys
+    pub fn update_menu(&mut self) -> Option<PauseAction> {
+        if self.mode == PauseMode::LookAround {
+            if is_key_pressed(KeyCode::L) || is_key_pressed(KeyCode::Enter) {
+                self.mode = PauseMode::Paused;
+            }
+            return None;
+        }
+        if self.mode != PauseMode::Paused {
+            return None;
+        }
+
+        if is_key_pressed(KeyCode::Up) {
+            self.selected = (self.selected + BUTTONS.len() - 1) % BUTTONS.len();
+        } else if is_key_pressed(KeyCode::Down) {
+            self.selected = (self.selected + 1) % BUTTONS.len();
+        }
+
+        let (mouse_x, mouse_y) = mouse_position();
+        for (i, action) in BUTTONS.iter().enumerate() {
+            let (x, y) = button_position(i);
+            let hovered = mouse_x >= x && mouse_x <= x + BUTTON_WIDTH && mouse_y >= y && mouse_y <= y + BUTTON_HEIGHT;
+            if hovered {
+                self.selected = i;
+                if is_mouse_button_pressed(MouseButton::Left) {
+                    return Some(*action);
+                }
+            }
+        }
+
+        if is_key_pressed(KeyCode::Enter) {
+            Some(BUTTONS[self.selected])
+        } else if is_key_pressed(KeyCode::R) {
+            Some(PauseAction::RestartLevel)
+        } else if is_key_pressed(KeyCode::L) {
+            Some(PauseAction::LookAround)
+        } else if is_key_pressed(KeyCode::C) {
+            Some(PauseAction::CycleControls)
+        } else if is_key_pressed(KeyCode::F) {
+            Some(PauseAction::Feedback)
+        } else if is_key_pressed(KeyCode::Q) {
+            Some(PauseAction::QuitToMenu)
+        } else {
+            None
+        }
+    }
+
+    pub fn look_around(&mut self) {
+        if self.mode == PauseMode::Paused {
+            self.mode = PauseMode::LookAround;
+        }
+    }
+
+    pub fn is_looking_around(&self) -> bool {
+        self.mode == PauseMode::LookAround
+    }
+
+    pub fn draw(&self, summary: &RunSummary, controls: ControlScheme) {
+        if self.is_running() {
+            return;
+        }
+        // The board stays uncovered to be looked at, with just a reminder of the keys
+        if self.mode == PauseMode::LookAround {
+            let help = t!("pause-look-help");
+            let width = measure_text(&help, None, 22, 1.0).width;
+            draw_rectangle(0.0, screen_height() - 40.0, screen_width(), 40.0, Color::new(0.0, 0.0, 0.0, 0.7));
+            draw_text(&help, (screen_width() - width) / 2.0, screen_height() - 14.0, 22.0, WHITE);
+            return;
+        }
+
+        draw_rectangle(0.0, 0.0, screen_width(), screen_height(), Color::new(0.0, 0.0, 0.0, 0.5));
+
+        match self.mode {
+            PauseMode::Paused => {
+                draw_centered(&t!("pause-title"), button_position(0).1 - 40.0, 64.0, WHITE);
+
+                for (i, action) in BUTTONS.iter().enumerate() {
+                    let label = match action {
+                        PauseAction::Resume => t!("pause-resume"),
+                        PauseAction::RestartLevel => t!("pause-restart"),
+                        PauseAction::LookAround => t!("pause-look"),
+                        PauseAction::CycleControls => {
+                            t!("pause-controls", controls = t!(&format!("controls-{}", controls.save_key())))
+                        }
+                        PauseAction::Feedback => t!("pause-feedback"),
+                        PauseAction::QuitToMenu => t!("pause-quit"),
+                    };
+                    let (x, y) = button_position(i);
+                    let selected = i == self.selected;
+                    let fill = if selected { Color::new(0.2, 0.5, 0.2, 0.95) } else { Color::new(0.1, 0.1, 0.1, 0.9) };
+                    draw_rectangle(x, y, BUTTON_WIDTH, BUTTON_HEIGHT, fill);
+                    draw_rectangle_lines(x, y, BUTTON_WIDTH, BUTTON_HEIGHT, 2.0, if selected { GREEN } else { GRAY });
+
+                    let size = if label.chars().count() > 24 { 20.0 } else { 28.0 };
+                    draw_centered(&label, y + BUTTON_HEIGHT / 2.0 + size / 3.0, size, WHITE);
+                }
+                draw_centered(&t!("pause-seed", seed = seed()), screen_height() - 30.0, 20.0, GRAY);
+            }
+            PauseMode::WelcomeBack => {
+                summary.draw_panel("WELCOME BACK!");
+                draw_centered(&t!("pause-continue"), screen_height() / 2.0 + 190.0, 28.0, LIGHTGRAY);
+            }
+            PauseMode::Countdown(remaining) => {
+                let text = format!("{}", remaining.ceil() as u32);
+                draw_centered(&text, screen_height() / 2.0, 96.0, WHITE);
+            }
+            PauseMode::Running | PauseMode::LookAround => {}
+        }
+    }
+}
+
+fn button_position(index: usize) -> (f32, f32) {
+    let top = screen_height() / 2.0 - BUTTON_SPACING * (BUTTONS.len() - 1) as f32 / 2.0;
+    ((screen_width() - BUTTON_WIDTH) / 2.0, top + index as f32 * BUTTON_SPACING)
+}
+
+fn draw_centered(text: &str, y: f32, size: f32, color: Color) {
+    let width = measure_text(text, None, size as u16, 1.0).width;
+    draw_text(text, (screen_width() - width) / 2.0, y, size, color);
+}
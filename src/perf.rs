@@ -0,0 +1,132 @@
+use macroquad::prelude::*;
+use std::collections::VecDeque;
+use std::time::Duration;
+use crate::grid::{GRID_HEIGHT, GRID_WIDTH};
+
+// Frames of history in the frame time graph
+const GRAPH_FRAMES: usize = 120;
+// How much of each new frame goes into the smoothed system timings
+const SMOOTHING: f32 = 0.1;
+// Frame time the graph is scaled to, 30 fps
+const GRAPH_CEILING_MS: f32 = 33.3;
+const GRAPH_HEIGHT: f32 = 36.0;
+const LINE_HEIGHT: f32 = 18.0;
+
+// The parts of a game tick that get timed, for the overlay and `--bench`
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum System {
+    Movement,
+    Collision,
+    Arena,
+    Food,
+    CpuSnakes,
+    Tension,
+}
+
+pub const SYSTEMS: [System; 6] = [
+    System::Movement,
+    System::Collision,
+    System::Arena,
+    System::Food,
+    System::CpuSnakes,
+    System::Tension,
+];
+
+impl System {
+    pub fn name(&self) -> &'static str {
+        match self {
+            System::Movement => "movement",
+            System::Collision => "collision",
+            System::Arena => "arena",
+            System::Food => "food",
+            System::CpuSnakes => "cpu snakes",
+            System::Tension => "tension",
+        }
+    }
+
+    fn index(&self) -> usize {
+        SYSTEMS.iter().position(|system| system == self).unwrap_or(0)
+    }
+}
+
+// F3 during a game: frame rate, a frame time graph, what's on the board, and how long
+// each part of the game tick took this frame
+pub struct PerfOverlay {
+    pub showing: bool,
+    frame_times: VecDeque<f32>, // Milliseconds, newest last
+    this_frame: [f32; SYSTEMS.len()],
+    smoothed: [f32; SYSTEMS.len()],
+}
+
+impl PerfOverlay {
+    pub fn new() -> Self {
+        Self {
+            showing: false,
+            frame_times: VecDeque::with_capacity(GRAPH_FRAMES),
+            this_frame: [0.0; SYSTEMS.len()],
+            smoothed: [0.0; SYSTEMS.len()],
+        }
+    }
+
+    // Adds to the system's time for this frame, a frame can run several ticks
+    pub fn record(&mut self, system: System, time: Duration) {
+        self.this_frame[system.index()] += time.as_secs_f32() * 1000.0;
+    }
+
+    // Call once a frame, after the game ticks
+    pub fn update(&mut self, frame_time: f32) {
+        if is_key_pressed(KeyCode::F3) {
+            self.showing = !self.showing;
+        }
+
+        if self.frame_times.len() == GRAPH_FRAMES {
+            self.frame_times.pop_front();
+        }
+        self.frame_times.push_back(frame_time * 1000.0);
+
+        for (smoothed, time) in self.smoothed.iter_mut().zip(self.this_frame) {
+            *smoothed += (time - *smoothed) * SMOOTHING;
+        }
+        self.this_frame = [0.0; SYSTEMS.len()];
+    }
+
+    // `counts` are whatever's worth counting on the board, `occupied` the grid cells the snake covers
+    pub fn draw(&self, counts: &[(&str, usize)], occupied: usize) {
+        if !self.showing {
+            return;
+        }
+
+        let average = self.frame_times.iter().sum::<f32>() / self.frame_times.len().max(1) as f32;
+        let worst = self.frame_times.iter().copied().fold(0.0, f32::max);
+        let cells = (GRID_WIDTH * GRID_HEIGHT) as usize;
+        let mut lines = Vec::new();
+        for (name, count) in counts {
+            lines.push((format!("{}: {}", name, count), LIGHTGRAY));
+        }
+        let percent = occupied as f32 / cells as f32 * 100.0;
+        lines.push((format!("grid occupancy: {} / {} ({:.1}%)", occupied, cells, percent), LIGHTGRAY));
+        lines.push(("tick time per frame:".to_string(), WHITE));
+        for system in SYSTEMS {
+            lines.push((format!("  {}: {:.3} ms", system.name(), self.smoothed[system.index()]), LIGHTGRAY));
+        }
+
+        let (x, width) = (10.0, 260.0);
+        let height = 80.0 + lines.len() as f32 * LINE_HEIGHT;
+        let y = screen_height() - height - 10.0;
+        draw_rectangle(x, y, width, height, Color::new(0.0, 0.0, 0.0, 0.75));
+        draw_text(&format!("FPS {}   frame {:.1} ms (worst {:.1})", get_fps(), average, worst), x + 10.0, y + 20.0, 18.0, WHITE);
+
+        // Frame time graph, one bar per frame, red when slower than 60 fps
+        let graph_bottom = y + 66.0;
+        let bar = (width - 20.0) / GRAPH_FRAMES as f32;
+        for (i, time) in self.frame_times.iter().enumerate() {
+            let bar_height = (time / GRAPH_CEILING_MS).min(1.0) * GRAPH_HEIGHT;
+            let color = if *time > 16.7 { RED } else { GREEN };
+            draw_rectangle(x + 10.0 + i as f32 * bar, graph_bottom - bar_height, bar, bar_height, color);
+        }
+
+        for (i, (text, color)) in lines.iter().enumerate() {
+            draw_text(text, x + 10.0, graph_bottom + 20.0 + i as f32 * LINE_HEIGHT, 18.0, *color);
+        }
+    }
+}
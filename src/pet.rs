@@ -0,0 +1,85 @@
+use macroquad::prelude::*;
+use crate::grid::{CELL_SIZE, get_offset};
+use crate::snake::{Segment, Snake};
+
+// Best level needed for each tier: unlock, then two upgrades
+const TIER_LEVELS: [usize; 3] = [5, 10, 15];
+// Seconds between pickups for each tier
+const TIER_COOLDOWNS: [f32; 3] = [8.0, 5.0, 3.0];
+const ORBIT_RADIUS: f32 = 1.5; // In cells
+const ORBIT_SPEED: f32 = 3.0;
+
+// Companion orb that circles the snake head and grabs nearby food
+pub struct Pet {
+    pub tier: usize, // 0 while still locked
+    pub cooldown: f32,
+    angle: f32,
+}
+
+impl Pet {
+    pub fn new(best_level: usize) -> Self {
+        Self {
+            tier: tier_for_level(best_level),
+            cooldown: 0.0,
+            angle: 0.0,
+        }
+    }
+
+    pub fn is_unlocked(&self) -> bool {
+        self.tier > 0
+    }
+
+    pub fn update(&mut self, delta_time: f32) {
+        self.angle += ORBIT_SPEED * delta_time;
+        self.cooldown = (self.cooldown - delta_time).max(0.0);
+    }
+
+    fn orbit_offset(&self) -> Vec2 {
+        vec2(self.angle.cos(), self.angle.sin()) * ORBIT_RADIUS
+    }
+
+    pub fn cell(&self, snake: &Snake) -> Segment {
+        let head = snake.head();
+        let offset = self.orbit_offset();
+        Segment {
+            x: head.x + offset.x.round() as i32,
+            y: head.y + offset.y.round() as i32,
+        }
+    }
+
+    // Returns true if the orb grabbed the food, starting its cooldown
+    pub fn try_collect(&mut self, snake: &Snake, food: Segment) -> bool {
+        if !self.is_unlocked() || self.cooldown > 0.0 {
+            return false;
+        }
+
+        let cell = self.cell(snake);
+        if (cell.x - food.x).abs() <= 1 && (cell.y - food.y).abs() <= 1 {
+            self.cooldown = TIER_COOLDOWNS[self.tier - 1];
+            return true;
+        }
+
+        false
+    }
+
+    pub fn draw(&self, snake: &Snake, color: Color) {
+        if !self.is_unlocked() {
+            return;
+        }
+
+        let offset = get_offset();
+        let head = snake.head();
+        let orbit = self.orbit_offset();
+        let x = offset.x + (head.x as f32 + 0.5 + orbit.x) * CELL_SIZE;
+        let y = offset.y + (head.y as f32 + 0.5 + orbit.y) * CELL_SIZE;
+
+        // Dim while recharging
+        let alpha = if self.cooldown > 0.0 { 0.35 } else { 1.0 };
+        draw_circle(x, y, CELL_SIZE * 0.45, Color::new(color.r, color.g, color.b, 0.25 * alpha));
+        draw_circle(x, y, CELL_SIZE * 0.25 + self.tier as f32, Color::new(color.r, color.g, color.b, alpha));
+    }
+}
+
+pub fn tier_for_level(best_level: usize) -> usize {
+    TIER_LEVELS.iter().filter(|&&level| best_level >= level).count()
+}
@@ -0,0 +1,112 @@
+use macroquad::prelude::*;
+use std::fmt::Write;
+use crate::grid::{CELL_SIZE, get_offset};
+use crate::motion::reduced_motion;
+use crate::snake::Segment;
+
+// Seconds a popup stays up
+const LIFETIME: f32 = 1.0;
+// Cells a popup climbs over its life
+const RISE: f32 = 1.5;
+// Popups this young push a new one further up, so a quick combo doesn't print on top of itself
+const STACK_WINDOW: f32 = 0.4;
+const STACK_SPACING: f32 = 0.8;
+
+#[derive(Clone)]
+struct Popup {
+    position: Vec2, // In cells, like particles, so it stays on the board under the camera
+    text: String,
+    color: Color,
+    age: f32,
+}
+
+impl Popup {
+    fn unused() -> Self {
+        Self {
+            position: Vec2::ZERO,
+            text: String::with_capacity(8),
+            color: BLANK,
+            age: LIFETIME,
+        }
+    }
+}
+
+// "+150" over the food that was just eaten. Like the particles, popups live in a fixed
+// pool with the live ones first, and each slot keeps its text buffer between uses.
+pub struct ScorePopups {
+    pool: Vec<Popup>,
+    active: usize,
+}
+
+impl ScorePopups {
+    pub fn new(cap: usize) -> Self {
+        Self {
+            pool: vec![Popup::unused(); cap],
+            active: 0,
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.active = 0;
+    }
+
+    pub fn set_cap(&mut self, cap: usize) {
+        self.pool.resize(cap, Popup::unused());
+        self.active = self.active.min(cap);
+    }
+
+    pub fn spawn(&mut self, cell: Segment, points: u32, color: Color) {
+        if self.pool.is_empty() {
+            return;
+        }
+        let stacked = self.pool[..self.active].iter().filter(|popup| popup.age < STACK_WINDOW).count();
+        // With the pool full, the oldest popup makes way
+        let index = if self.active < self.pool.len() {
+            self.active += 1;
+            self.active - 1
+        } else {
+            (0..self.active)
+                .max_by(|a, b| self.pool[*a].age.total_cmp(&self.pool[*b].age))
+                .unwrap_or(0)
+        };
+
+        let popup = &mut self.pool[index];
+        popup.position = vec2(cell.x as f32 + 0.5, cell.y as f32 - stacked as f32 * STACK_SPACING);
+        popup.text.clear();
+        let _ = write!(popup.text, "+{}", points);
+        popup.color = color;
+        popup.age = 0.0;
+    }
+
+    pub fn update(&mut self, delta_time: f32) {
+        let mut index = 0;
+        while index < self.active {
+            self.pool[index].age += delta_time;
+            if self.pool[index].age < LIFETIME {
+                index += 1;
+            } else {
+                self.active -= 1;
+                self.pool.swap(index, self.active);
+            }
+        }
+    }
+
+    // Drawn inside the camera, along with the board
+    pub fn draw(&self) {
+        let offset = get_offset();
+        for popup in &self.pool[..self.active] {
+            let t = popup.age / LIFETIME;
+            // With reduced motion they fade where they appeared
+            let rise = if reduced_motion() { 0.0 } else { RISE * t };
+            let width = measure_text(&popup.text, None, 22, 1.0).width;
+            let color = Color::new(popup.color.r, popup.color.g, popup.color.b, 1.0 - t * t);
+            draw_text(
+                &popup.text,
+                offset.x + popup.position.x * CELL_SIZE - width / 2.0,
+                offset.y + (popup.position.y - rise) * CELL_SIZE,
+                22.0,
+                color,
+            );
+        }
+    }
+}
@@ -0,0 +1,89 @@
+// Discord rich presence. Only compiled in with the `discord` feature, and even
+// then it quietly does nothing if Discord isn't running or no app id is set.
+#[cfg(feature = "discord")]
+use discord_rich_presence::{activity, DiscordIpc, DiscordIpcClient};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// Discord application id to publish under, read at startup
+#[cfg(feature = "discord")]
+const APP_ID_ENV: &str = "VYPERTRON_DISCORD_APP_ID";
+
+pub struct Presence {
+    #[cfg(feature = "discord")]
+    client: Option<DiscordIpcClient>,
+    details: String,
+    state: String,
+    started_at: i64,
+}
+
+impl Presence {
+    pub fn new() -> Self {
+        Self {
+            #[cfg(feature = "discord")]
+            client: connect(),
+            details: String::new(),
+            state: String::new(),
+            started_at: unix_millis(),
+        }
+    }
+
+    // Restart the elapsed time shown on the profile, called when a run begins
+    pub fn start_run(&mut self) {
+        self.started_at = unix_millis();
+        self.details.clear(); // Force the next update through
+    }
+
+    // Cheap to call every frame, only publishes when the text changes
+    pub fn update(&mut self, details: &str, state: &str) {
+        if self.details == details && self.state == state {
+            return;
+        }
+        self.details = details.to_string();
+        self.state = state.to_string();
+        self.publish();
+    }
+
+    #[cfg(feature = "discord")]
+    fn publish(&mut self) {
+        let Some(client) = &mut self.client else {
+            return;
+        };
+
+        let payload = activity::Activity::new()
+            .details(self.details.as_str())
+            .state(self.state.as_str())
+            .timestamps(activity::Timestamps::new().start(self.started_at));
+
+        if let Err(e) = client.set_activity(payload) {
+            // Discord probably closed, stop trying rather than erroring every update
+            println!("Warning: Lost connection to Discord: {:?}", e);
+            self.client = None;
+        }
+    }
+
+    #[cfg(not(feature = "discord"))]
+    fn publish(&mut self) {}
+}
+
+#[cfg(feature = "discord")]
+fn connect() -> Option<DiscordIpcClient> {
+    let app_id = std::env::var(APP_ID_ENV).ok()?;
+    let mut client = DiscordIpcClient::new(app_id);
+
+    match client.connect() {
+        Ok(()) => {
+            println!("Connected to Discord for rich presence");
+            Some(client)
+        }
+        Err(e) => {
+            println!("Warning: Discord rich presence unavailable: {:?}", e);
+            None
+        }
+    }
+}
+
+fn unix_millis() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_millis() as i64)
+}
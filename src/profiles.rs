@@ -0,0 +1,234 @@
+use macroquad::prelude::*;
+use std::fs;
+use crate::save::{backup_path, save_path_for, BACKUP_COUNT};
+
+const PROFILES_PATH: &str = "vypertron_profiles.txt";
+const MAX_NAME_LENGTH: usize = 12;
+const MAX_PROFILES: usize = 8;
+
+pub struct Profile {
+    pub id: u32,
+    pub name: String,
+}
+
+impl Profile {
+    pub fn save_path(&self) -> String {
+        save_path_for(self.id)
+    }
+}
+
+// Index of local player profiles, each with its own save file
+pub struct ProfileList {
+    pub profiles: Vec<Profile>,
+    pub last_used: u32,
+}
+
+impl ProfileList {
+    pub fn load() -> Self {
+        let mut list = Self {
+            profiles: Vec::new(),
+            last_used: 0,
+        };
+
+        if let Ok(contents) = fs::read_to_string(PROFILES_PATH) {
+            for line in contents.lines() {
+                if let Some((key, value)) = line.split_once('=') {
+                    match key.trim() {
+                        "profile" => {
+                            let parsed = value
+                                .split_once('|')
+                                .and_then(|(id, name)| Some((id.parse().ok()?, name.to_string())));
+                            if let Some((id, name)) = parsed {
+                                list.profiles.push(Profile { id, name });
+                            }
+                        }
+                        "last_used" => list.last_used = value.trim().parse().unwrap_or(0),
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        // Saves from before profiles existed become the first profile
+        if list.profiles.is_empty() {
+            list.profiles.push(Profile { id: 0, name: "Player".to_string() });
+            list.last_used = 0;
+        }
+
+        list
+    }
+
+    pub fn save(&self) {
+        let mut contents = String::new();
+        for profile in &self.profiles {
+            contents.push_str(&format!("profile={}|{}\n", profile.id, profile.name));
+        }
+        contents.push_str(&format!("last_used={}\n", self.last_used));
+
+        if let Err(e) = fs::write(PROFILES_PATH, contents) {
+            println!("Warning: Could not write profile list: {:?}", e);
+        }
+    }
+
+    pub fn last_used_index(&self) -> usize {
+        self.profiles
+            .iter()
+            .position(|profile| profile.id == self.last_used)
+            .unwrap_or(0)
+    }
+
+    pub fn select(&mut self, index: usize) -> &Profile {
+        self.last_used = self.profiles[index].id;
+        self.save();
+        &self.profiles[index]
+    }
+
+    pub fn create(&mut self, name: &str) -> usize {
+        let id = self.profiles.iter().map(|profile| profile.id + 1).max().unwrap_or(0);
+        self.profiles.push(Profile { id, name: name.to_string() });
+        self.save();
+        self.profiles.len() - 1
+    }
+
+    pub fn rename(&mut self, index: usize, name: &str) {
+        self.profiles[index].name = name.to_string();
+        self.save();
+    }
+
+    // Removes the profile along with its save file and backups
+    pub fn delete(&mut self, index: usize) {
+        let profile = self.profiles.remove(index);
+        let path = profile.save_path();
+        let _ = fs::remove_file(&path);
+        for i in 1..=BACKUP_COUNT {
+            let _ = fs::remove_file(backup_path(&path, i));
+        }
+        self.save();
+    }
+}
+
+enum PickerMode {
+    Browsing,
+    Naming { text: String, renaming: Option<usize> },
+    ConfirmDelete,
+}
+
+// Profile picker shown over the title screen
+pub struct ProfilePicker {
+    pub showing: bool,
+    selected: usize,
+    mode: PickerMode,
+}
+
+impl ProfilePicker {
+    pub fn new(profiles: &ProfileList) -> Self {
+        Self {
+            showing: true,
+            selected: profiles.last_used_index(),
+            mode: PickerMode::Browsing,
+        }
+    }
+
+    pub fn open(&mut self, profiles: &ProfileList) {
+        self.showing = true;
+        self.selected = profiles.last_used_index();
+        self.mode = PickerMode::Browsing;
+    }
+
+    // Returns the index of the profile the player picked, if any
+    pub fn update(&mut self, profiles: &mut ProfileList) -> Option<usize> {
+        match &mut self.mode {
+            PickerMode::Browsing => {
+                let count = profiles.profiles.len();
+                if is_key_pressed(KeyCode::Up) {
+                    self.selected = (self.selected + count - 1) % count;
+                } else if is_key_pressed(KeyCode::Down) {
+                    self.selected = (self.selected + 1) % count;
+                } else if is_key_pressed(KeyCode::Enter) {
+                    self.showing = false;
+                    return Some(self.selected);
+                } else if is_key_pressed(KeyCode::N) && count < MAX_PROFILES {
+                    clear_typed_chars();
+                    self.mode = PickerMode::Naming { text: String::new(), renaming: None };
+                } else if is_key_pressed(KeyCode::R) {
+                    clear_typed_chars();
+                    let text = profiles.profiles[self.selected].name.clone();
+                    self.mode = PickerMode::Naming { text, renaming: Some(self.selected) };
+                } else if is_key_pressed(KeyCode::D) && count > 1 {
+                    self.mode = PickerMode::ConfirmDelete;
+                }
+            }
+            PickerMode::Naming { text, renaming } => {
+                while let Some(c) = get_char_pressed() {
+                    if (c.is_ascii_alphanumeric() || c == ' ') && text.len() < MAX_NAME_LENGTH {
+                        text.push(c);
+                    }
+                }
+                if is_key_pressed(KeyCode::Backspace) {
+                    text.pop();
+                }
+
+                if is_key_pressed(KeyCode::Enter) && !text.trim().is_empty() {
+                    let name = text.trim().to_string();
+                    match renaming {
+                        Some(index) => profiles.rename(*index, &name),
+                        None => self.selected = profiles.create(&name),
+                    }
+                    self.mode = PickerMode::Browsing;
+                } else if is_key_pressed(KeyCode::Escape) {
+                    self.mode = PickerMode::Browsing;
+                }
+            }
+            PickerMode::ConfirmDelete => {
+                if is_key_pressed(KeyCode::Y) {
+                    profiles.delete(self.selected);
+                    self.selected = self.selected.min(profiles.profiles.len() - 1);
+                    self.mode = PickerMode::Browsing;
+                } else if is_key_pressed(KeyCode::N) || is_key_pressed(KeyCode::Escape) {
+                    self.mode = PickerMode::Browsing;
+                }
+            }
+        }
+
+        None
+    }
+
+    pub fn draw(&self, profiles: &ProfileList) {
+        draw_rectangle(0.0, 0.0, screen_width(), screen_height(), Color::new(0.0, 0.0, 0.0, 0.9));
+
+        let heading = "CHOOSE PROFILE";
+        let heading_width = measure_text(heading, None, 48, 1.0).width;
+        draw_text(heading, (screen_width() - heading_width) / 2.0, 100.0, 48.0, GREEN);
+
+        for (i, profile) in profiles.profiles.iter().enumerate() {
+            let selected = i == self.selected;
+            let name = match &self.mode {
+                PickerMode::Naming { text, renaming: Some(index) } if *index == i => format!("{}_", text),
+                _ => profile.name.clone(),
+            };
+            let line = if selected { format!("> {} <", name) } else { name };
+            let color = if selected { YELLOW } else { WHITE };
+            let line_width = measure_text(&line, None, 32, 1.0).width;
+            draw_text(&line, (screen_width() - line_width) / 2.0, 170.0 + i as f32 * 40.0, 32.0, color);
+        }
+
+        let new_y = 170.0 + profiles.profiles.len() as f32 * 40.0;
+        if let PickerMode::Naming { text, renaming: None } = &self.mode {
+            let line = format!("New profile: {}_", text);
+            let line_width = measure_text(&line, None, 32, 1.0).width;
+            draw_text(&line, (screen_width() - line_width) / 2.0, new_y, 32.0, GREEN);
+        }
+
+        let prompt = match self.mode {
+            PickerMode::Browsing => "ENTER - Play   N - New   R - Rename   D - Delete",
+            PickerMode::Naming { .. } => "Type a name, ENTER to confirm, ESC to cancel",
+            PickerMode::ConfirmDelete => "Delete this profile and all of its progress? Y / N",
+        };
+        let prompt_width = measure_text(prompt, None, 24, 1.0).width;
+        draw_text(prompt, (screen_width() - prompt_width) / 2.0, screen_height() - 30.0, 24.0, LIGHTGRAY);
+    }
+}
+
+fn clear_typed_chars() {
+    while get_char_pressed().is_some() {}
+}
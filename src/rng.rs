@@ -0,0 +1,55 @@
+use lazy_static::lazy_static;
+use ::rand::rngs::StdRng;
+use ::rand::{Rng, SeedableRng, thread_rng};
+use std::sync::{Mutex, MutexGuard};
+use crate::highscores::today;
+
+// Randomness for a run comes from one seed, so the same seed plays out the same food,
+// walls and CPU snakes again. Gameplay and cosmetic effects draw from separate streams
+// seeded from it, so a burst of particles can't shift where the next food lands.
+lazy_static! {
+    // Set from --seed, used for every run instead of a fresh one
+    static ref FIXED_SEED: Mutex<Option<u64>> = Mutex::new(None);
+    static ref SEED: Mutex<u64> = Mutex::new(0);
+    static ref GAMEPLAY: Mutex<StdRng> = Mutex::new(StdRng::seed_from_u64(0));
+    static ref EFFECTS: Mutex<StdRng> = Mutex::new(StdRng::seed_from_u64(0));
+}
+
+// Picked so the effects stream doesn't start where the gameplay one does
+const EFFECTS_STREAM: u64 = 0x9e37_79b9_7f4a_7c15;
+
+// A number, or `daily` for a seed everyone shares until midnight (UTC), e.g. 20261016
+pub fn parse_seed(text: &str) -> Option<u64> {
+    if text == "daily" {
+        return today().replace('-', "").parse().ok();
+    }
+    text.parse().ok()
+}
+
+pub fn set_fixed_seed(seed: Option<u64>) {
+    *FIXED_SEED.lock().unwrap() = seed;
+    start_run();
+}
+
+// Reseeds both streams for a new run and returns the seed it's using
+pub fn start_run() -> u64 {
+    let seed = FIXED_SEED.lock().unwrap().unwrap_or_else(|| thread_rng().gen());
+    *SEED.lock().unwrap() = seed;
+    *GAMEPLAY.lock().unwrap() = StdRng::seed_from_u64(seed);
+    *EFFECTS.lock().unwrap() = StdRng::seed_from_u64(seed ^ EFFECTS_STREAM);
+    seed
+}
+
+// Shown on the pause screen so a run can be reported and played again
+pub fn seed() -> u64 {
+    *SEED.lock().unwrap()
+}
+
+// Hold these only as long as needed, taking one while it's already held would hang
+pub fn gameplay() -> MutexGuard<'static, StdRng> {
+    GAMEPLAY.lock().unwrap()
+}
+
+pub fn effects() -> MutexGuard<'static, StdRng> {
+    EFFECTS.lock().unwrap()
+}
@@ -0,0 +1,345 @@
+use std::fs;
+use crate::controls::{ControlScheme, KeyPreset};
+use crate::difficulty::Difficulty;
+use crate::locale::Language;
+use crate::mixer::VolumeLevels;
+use crate::particles::EffectsLimit;
+use crate::sfx::MusicDucking;
+use crate::snake::Segment;
+use crate::themes::Palette;
+use crate::highscores::{HighScoreTable, LevelLeaderboard};
+use crate::history::RunHistory;
+use crate::timeattack::BestTimes;
+use crate::stats::GameStatistics;
+use crate::touch::SwipeSensitivity;
+
+const SAVE_PATH: &str = "vypertron_save.txt";
+// Number of previous good saves kept around in case the main one gets damaged
+pub const BACKUP_COUNT: usize = 3;
+// Bump whenever the exported statistics JSON changes shape
+const STATS_SCHEMA_VERSION: u32 = 2;
+
+// Persistent player data, stored as simple key=value lines followed by a checksum
+pub struct SaveData {
+    path: String,
+    pub onboarding_complete: bool,
+    pub tutorial_complete: bool, // Level 1 tips seen through to the end
+    pub best_level: usize,
+    pub checkpoint_level: usize, // Level the unfinished campaign run is on, 0 if there isn't one
+    pub continues_left: u32,
+    pub controls: ControlScheme,
+    pub wrap_around: bool, // Modifier that opens the walls on every level
+    pub difficulty: Difficulty,
+    pub follow_camera: bool,
+    pub zoom_preference: f32, // Multiplier on the follow camera's automatic zoom
+    pub minimap: bool, // Shown while the follow camera is on
+    pub swipe_sensitivity: SwipeSensitivity,
+    pub audio_latency_ms: u32, // How early predictable sounds are played to make up for output lag
+    pub music_ducking: MusicDucking,
+    pub generated_music: bool, // Each level theme gets its own generated tune instead of the game song
+    pub volume: VolumeLevels,
+    pub palette: Palette,
+    pub reduced_motion: bool, // No screen shake or pulsing, and smaller explosions
+    pub audio_cues: bool,
+    pub language: Language,
+    pub smooth_body: bool, // Rounded, tapering snake instead of the square sprites
+    pub large_hud: bool, // Bigger text and bars during a game
+    pub path_preview: bool, // Assist overlay showing where the snake is headed
+    pub effects: EffectsLimit, // Particle and popup pool sizes
+    pub best_times: BestTimes,
+    pub high_scores: HighScoreTable,
+    pub history: RunHistory,
+    pub level_scores: LevelLeaderboard,
+    pub stats: GameStatistics,
+}
+
+impl SaveData {
+    pub fn new(path: &str) -> Self {
+        Self {
+            path: path.to_string(),
+            onboarding_complete: false,
+            tutorial_complete: false,
+            best_level: 1,
+            checkpoint_level: 0,
+            continues_left: 0,
+            controls: ControlScheme::Keys(KeyPreset::ArrowsAndWasd),
+            wrap_around: false,
+            difficulty: Difficulty::Normal,
+            follow_camera: false,
+            zoom_preference: 1.0,
+            minimap: true,
+            swipe_sensitivity: SwipeSensitivity::Medium,
+            audio_latency_ms: 0,
+            music_ducking: MusicDucking::Light,
+            generated_music: true,
+            volume: VolumeLevels::new(),
+            palette: Palette::Standard,
+            reduced_motion: false,
+            audio_cues: false,
+            language: Language::English,
+            smooth_body: true,
+            large_hud: false,
+            path_preview: false,
+            effects: EffectsLimit::High,
+            best_times: BestTimes::new(),
+            high_scores: HighScoreTable::new(),
+            history: RunHistory::new(),
+            level_scores: LevelLeaderboard::new(),
+            stats: GameStatistics::new(),
+        }
+    }
+
+    // Returns the loaded data plus a warning for the player if the save had to be recovered
+    pub fn load(path: &str) -> (Self, Option<&'static str>) {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(_) => return (Self::new(path), None), // No save yet, this is the first launch
+        };
+
+        if let Some(data) = Self::parse(path, &contents) {
+            return (data, None);
+        }
+
+        println!("Warning: Save file is damaged, looking for a backup...");
+        for i in 1..=BACKUP_COUNT {
+            let restored = fs::read_to_string(backup_path(path, i))
+                .ok()
+                .and_then(|contents| Self::parse(path, &contents));
+
+            if let Some(data) = restored {
+                println!("Restored save from backup {}", i);
+                data.save();
+                return (data, Some("Save data was damaged - restored from backup"));
+            }
+        }
+
+        (Self::new(path), Some("Save data was damaged and could not be recovered"))
+    }
+
+    pub fn save(&self) {
+        // Only rotate the current save into the backups if it's still a good one
+        let current_is_valid = fs::read_to_string(&self.path)
+            .ok()
+            .and_then(|contents| Self::parse(&self.path, &contents))
+            .is_some();
+
+        if current_is_valid {
+            for i in (1..BACKUP_COUNT).rev() {
+                let _ = fs::rename(backup_path(&self.path, i), backup_path(&self.path, i + 1));
+            }
+            let _ = fs::copy(&self.path, backup_path(&self.path, 1));
+        }
+
+        if let Err(e) = fs::write(&self.path, self.serialize()) {
+            println!("Warning: Could not write save file: {:?}", e);
+        }
+    }
+
+    fn serialize(&self) -> String {
+        let mut body = format!(
+            "onboarding_complete={}\ntutorial_complete={}\nbest_level={}\ncheckpoint_level={}\ncontinues_left={}\ncontrols={}\nwrap_around={}\ndifficulty={}\nfollow_camera={}\nzoom_preference={:.2}\nminimap={}\nswipe_sensitivity={}\naudio_latency_ms={}\nmusic_ducking={}\ngenerated_music={}\nmaster_volume={:.1}\nmusic_volume={:.1}\nsfx_volume={:.1}\npalette={}\nreduced_motion={}\naudio_cues={}\nlanguage={}\nsmooth_body={}\nlarge_hud={}\npath_preview={}\neffects={}\n",
+            self.onboarding_complete,
+            self.tutorial_complete,
+            self.best_level,
+            self.checkpoint_level,
+            self.continues_left,
+            self.controls.save_key(),
+            self.wrap_around,
+            self.difficulty.save_key(),
+            self.follow_camera,
+            self.zoom_preference,
+            self.minimap,
+            self.swipe_sensitivity.save_key(),
+            self.audio_latency_ms,
+            self.music_ducking.save_key(),
+            self.generated_music,
+            self.volume.master,
+            self.volume.music,
+            self.volume.sfx,
+            self.palette.save_key(),
+            self.reduced_motion,
+            self.audio_cues,
+            self.language.save_key(),
+            self.smooth_body,
+            self.large_hud,
+            self.path_preview,
+            self.effects.save_key()
+        );
+        self.best_times.write_lines(&mut body);
+        self.high_scores.write_lines(&mut body);
+        self.history.write_lines(&mut body);
+        self.level_scores.write_lines(&mut body);
+        self.stats.write_lines(&mut body);
+        format!("{}checksum={:016x}\n", body, checksum(&body))
+    }
+
+    // Parses a save, rejecting it if the checksum is missing or doesn't match
+    fn parse(path: &str, contents: &str) -> Option<Self> {
+        let checksum_start = contents.rfind("checksum=")?;
+        let (body, checksum_line) = contents.split_at(checksum_start);
+        let expected = u64::from_str_radix(checksum_line["checksum=".len()..].trim(), 16).ok()?;
+        if checksum(body) != expected {
+            return None;
+        }
+
+        let mut data = Self::new(path);
+        for line in body.lines() {
+            if let Some((key, value)) = line.split_once('=') {
+                let key = key.trim();
+                let value = value.trim();
+                match key {
+                    "onboarding_complete" => data.onboarding_complete = value == "true",
+                    "tutorial_complete" => data.tutorial_complete = value == "true",
+                    "best_level" => data.best_level = value.parse().unwrap_or(1),
+                    "checkpoint_level" => data.checkpoint_level = value.parse().unwrap_or(0),
+                    "continues_left" => data.continues_left = value.parse().unwrap_or(0),
+                    "controls" => data.controls = ControlScheme::from_save_key(value),
+                    "wrap_around" => data.wrap_around = value == "true",
+                    "difficulty" => data.difficulty = Difficulty::from_save_key(value),
+                    "follow_camera" => data.follow_camera = value == "true",
+                    "zoom_preference" => data.zoom_preference = value.parse().unwrap_or(1.0),
+                    "minimap" => data.minimap = value == "true",
+                    "swipe_sensitivity" => data.swipe_sensitivity = SwipeSensitivity::from_save_key(value),
+                    "audio_latency_ms" => data.audio_latency_ms = value.parse().unwrap_or(0),
+                    "music_ducking" => data.music_ducking = MusicDucking::from_save_key(value),
+                    "generated_music" => data.generated_music = value == "true",
+                    "master_volume" => data.volume.master = value.parse::<f32>().unwrap_or(1.0).clamp(0.0, 1.0),
+                    "music_volume" => data.volume.music = value.parse::<f32>().unwrap_or(1.0).clamp(0.0, 1.0),
+                    "sfx_volume" => data.volume.sfx = value.parse::<f32>().unwrap_or(1.0).clamp(0.0, 1.0),
+                    "palette" => data.palette = Palette::from_save_key(value),
+                    "reduced_motion" => data.reduced_motion = value == "true",
+                    "audio_cues" => data.audio_cues = value == "true",
+                    "language" => data.language = Language::from_save_key(value),
+                    "smooth_body" => data.smooth_body = value == "true",
+                    "large_hud" => data.large_hud = value == "true",
+                    "path_preview" => data.path_preview = value == "true",
+                    "effects" => data.effects = EffectsLimit::from_save_key(value),
+                    "best_time" => data.best_times.read_line(value),
+                    "high_score" => data.high_scores.read_line(value),
+                    "run" => data.history.read_line(value),
+                    "level_score" => data.level_scores.read_line(value),
+                    _ => {
+                        // Anything unknown is either statistics or from a newer version
+                        data.stats.read_line(key, value);
+                    }
+                }
+            }
+        }
+
+        Some(data)
+    }
+
+    // Where `export_stats` writes, next to the save file
+    pub fn export_path(&self) -> String {
+        match self.path.strip_suffix(".txt") {
+            Some(stem) => format!("{}_stats.json", stem),
+            None => format!("{}_stats.json", self.path),
+        }
+    }
+
+    // Writes progress as JSON for overlays, spreadsheets, and other tools. The shape is
+    // documented in the README and versioned by `schema_version`.
+    pub fn export_stats(&self, profile_name: &str) -> Result<String, std::io::Error> {
+        let path = self.export_path();
+        fs::write(&path, self.stats_json(profile_name))?;
+        Ok(path)
+    }
+
+    fn stats_json(&self, profile_name: &str) -> String {
+        let cell = |cell: &Segment| format!("[{}, {}]", cell.x, cell.y);
+        let cells = |cells: &[Segment]| cells.iter().map(cell).collect::<Vec<_>>().join(", ");
+
+        let deaths: Vec<String> = self
+            .stats
+            .death_snapshots
+            .iter()
+            .map(|snapshot| {
+                format!(
+                    "    {{\"level\": {}, \"length\": {}, \"food\": {}, \"snake\": [{}], \"obstacles\": [{}]}}",
+                    snapshot.level,
+                    snapshot.snake.len(),
+                    cell(&snapshot.food),
+                    cells(&snapshot.snake),
+                    cells(&snapshot.obstacles),
+                )
+            })
+            .collect();
+
+        let level_scores: Vec<String> = self
+            .level_scores
+            .iter()
+            .map(|(level, scores)| {
+                let entries: Vec<String> = scores
+                    .iter()
+                    .map(|entry| {
+                        format!(
+                            "{{\"name\": \"{}\", \"score\": {}, \"length\": {}, \"difficulty\": \"{}\", \"date\": \"{}\"}}",
+                            json_escape(&entry.name),
+                            entry.score,
+                            entry.length,
+                            entry.difficulty.save_key(),
+                            json_escape(&entry.date),
+                        )
+                    })
+                    .collect();
+                format!("    {{\"level\": {}, \"scores\": [{}]}}", level, entries.join(", "))
+            })
+            .collect();
+
+        let best_times: Vec<String> = self
+            .best_times
+            .iter()
+            .map(|(level, seconds)| format!("    {{\"level\": {}, \"seconds\": {:.2}}}", level, seconds))
+            .collect();
+
+        format!(
+            "{{\n  \"schema_version\": {},\n  \"profile\": \"{}\",\n  \"best_level\": {},\n  \"longest_snake\": {},\n  \"settings\": {{\"difficulty\": \"{}\", \"controls\": \"{}\", \"wrap_around\": {}}},\n  \"level_scores\": [\n{}\n  ],\n  \"best_times\": [\n{}\n  ],\n  \"deaths\": [\n{}\n  ]\n}}\n",
+            STATS_SCHEMA_VERSION,
+            json_escape(profile_name),
+            self.best_level,
+            self.stats.longest_snake,
+            self.difficulty.save_key(),
+            self.controls.save_key(),
+            self.wrap_around,
+            level_scores.join(",\n"),
+            best_times.join(",\n"),
+            deaths.join(",\n"),
+        )
+    }
+}
+
+pub fn json_escape(text: &str) -> String {
+    let mut escaped = String::new();
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// The first profile keeps the original save file so older saves carry over
+pub fn save_path_for(profile_id: u32) -> String {
+    if profile_id == 0 {
+        SAVE_PATH.to_string()
+    } else {
+        format!("vypertron_save_{}.txt", profile_id)
+    }
+}
+
+pub fn backup_path(path: &str, index: usize) -> String {
+    format!("{}.bak{}", path, index)
+}
+
+// FNV-1a, plenty to catch truncated or hand-mangled files
+fn checksum(text: &str) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in text.bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
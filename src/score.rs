@@ -1,12 +1,35 @@
 use macroquad::prelude::*;
+use crate::hud::hud_scale;
+use crate::motion;
+use crate::themes::Theme;
+
+// Points awarded for a single food before the combo multiplier
+const FOOD_POINTS: u32 = 10;
+// Seconds the player has to eat the next food to keep the combo going
+const COMBO_WINDOW: f32 = 3.0;
+const MAX_MULTIPLIER: u32 = 5;
 
 pub struct Score {
     pub value: u32,
+    pub streak: u32,
+    pub best_streak: u32,
+    combo_timer: f32,
+    level_start: u32,
+    pub level_best_streak: u32,
+    pub percent: u32, // Difficulty scaling applied to every food
 }
 
 impl Score {
     pub fn new() -> Self {
-        Self { value: 0 }
+        Self {
+            value: 0,
+            streak: 0,
+            best_streak: 0,
+            combo_timer: 0.0,
+            level_start: 0,
+            level_best_streak: 0,
+            percent: 100,
+        }
     }
 
     pub fn add(&mut self, amount: u32) {
@@ -15,10 +38,107 @@ impl Score {
 
     pub fn reset(&mut self) {
         self.value = 0;
+        self.streak = 0;
+        self.best_streak = 0;
+        self.combo_timer = 0.0;
+        self.level_start = 0;
+        self.level_best_streak = 0;
+    }
+
+    // Marks the start of a new level for the level breakdown
+    pub fn start_level(&mut self) {
+        self.level_start = self.value;
+        self.level_best_streak = 0;
+    }
+
+    // Takes the score back to where it was when the level started
+    pub fn restart_level(&mut self) {
+        self.value = self.level_start;
+        self.level_best_streak = 0;
+        self.break_combo();
+    }
+
+    pub fn level_points(&self) -> u32 {
+        self.value - self.level_start
     }
 
-    pub fn draw(&self) {
-        let text = format!("Score: {}", self.value);
-        draw_text(&text, 20.0, 40.0, 32.0, YELLOW);
+    pub fn multiplier(&self) -> u32 {
+        self.streak.clamp(1, MAX_MULTIPLIER)
+    }
+
+    // Call when food is eaten, returns the points that were awarded
+    pub fn register_food(&mut self) -> u32 {
+        if self.combo_timer > 0.0 {
+            self.streak += 1;
+        } else {
+            self.streak = 1;
+        }
+        self.best_streak = self.best_streak.max(self.streak);
+        self.level_best_streak = self.level_best_streak.max(self.streak);
+        self.combo_timer = COMBO_WINDOW;
+
+        let points = FOOD_POINTS * self.multiplier() * self.percent / 100;
+        self.add(points);
+        points
+    }
+
+    // Food picked up by the companion orb is worth half and doesn't touch the combo
+    pub fn register_pet_food(&mut self) -> u32 {
+        let points = FOOD_POINTS * self.multiplier() * self.percent / 200;
+        self.add(points);
+        points
+    }
+
+    // Takes points away and ends the combo, never going below where the level started.
+    // Returns the points actually lost.
+    pub fn lose(&mut self, amount: u32) -> u32 {
+        let lost = amount.min(self.level_points());
+        self.value -= lost;
+        self.break_combo();
+        lost
+    }
+
+    pub fn update(&mut self, delta_time: f32) {
+        if self.combo_timer > 0.0 {
+            self.combo_timer -= delta_time;
+            if self.combo_timer <= 0.0 {
+                self.break_combo();
+            }
+        }
+    }
+
+    pub fn break_combo(&mut self) {
+        self.streak = 0;
+        self.combo_timer = 0.0;
+    }
+
+    pub fn draw(&self, theme: &Theme) {
+        let text = format!("SCORE: {}", self.value);
+        let hud = hud_scale();
+        draw_text(&text, 20.0 * hud, 55.0 * hud, 24.0 * hud, theme.ui_text);
+    }
+
+    pub fn draw_combo_meter(&self, theme: &Theme) {
+        if self.streak < 2 {
+            return;
+        }
+
+        // Text grows with the multiplier and pulses faster as it climbs
+        let multiplier = self.multiplier();
+        let pulse = motion::pulse(4.0 + multiplier as f64, 0.1, 1.0);
+        let hud = hud_scale();
+        let size = (24.0 + multiplier as f32 * 2.0) * pulse * hud;
+
+        let text = format!("COMBO x{}", multiplier);
+        let text_width = measure_text(&text, None, size as u16, 1.0).width;
+        let x = screen_width() - text_width - 20.0 * hud;
+        draw_text(&text, x, 60.0 * hud, size, theme.food);
+
+        // Bar showing how much of the combo window is left
+        let bar_width = 120.0 * hud;
+        let remaining = (self.combo_timer / COMBO_WINDOW).clamp(0.0, 1.0);
+        let bar_x = screen_width() - bar_width - 20.0 * hud;
+        draw_rectangle_lines(bar_x, 68.0 * hud, bar_width, 8.0 * hud, 1.0, theme.ui_text);
+        draw_rectangle(bar_x, 68.0 * hud, bar_width * remaining, 8.0 * hud, theme.food);
     }
 }
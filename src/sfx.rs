@@ -0,0 +1,347 @@
+use macroquad::audio::{load_sound_from_bytes, set_sound_volume, Sound};
+use std::collections::HashMap;
+use std::fs;
+#[cfg(debug_assertions)]
+use std::time::SystemTime;
+use crate::mixer::music_volume;
+use crate::tension::encode_wav;
+
+pub const MUSIC_VOLUME: f32 = 0.7;
+// How long the music stays down after a hit, then how long it takes to come back
+const DUCK_HOLD: f32 = 0.25;
+const DUCK_RESTORE: f32 = 0.5;
+const SAMPLE_RATE: u32 = 22050;
+// Sound designers can retune the generated effects here without rebuilding
+const RECIPES_PATH: &str = "assets/sfx/recipes.txt";
+// Built-in copy, used for any sound the file is missing
+const BUILT_IN_RECIPES: &str = include_str!("../assets/sfx/recipes.txt");
+// Seconds between checks for an edited recipe file
+#[cfg(debug_assertions)]
+const RELOAD_CHECK: f32 = 0.5;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum MusicDucking {
+    Off,
+    Light,
+    Strong,
+}
+
+impl MusicDucking {
+    pub fn next(&self) -> MusicDucking {
+        match self {
+            MusicDucking::Off => MusicDucking::Light,
+            MusicDucking::Light => MusicDucking::Strong,
+            MusicDucking::Strong => MusicDucking::Off,
+        }
+    }
+
+    // Share of the music volume left while ducked
+    fn level(&self) -> f32 {
+        match self {
+            MusicDucking::Off => 1.0,
+            MusicDucking::Light => 0.6,
+            MusicDucking::Strong => 0.3,
+        }
+    }
+
+    pub fn save_key(&self) -> &'static str {
+        match self {
+            MusicDucking::Off => "off",
+            MusicDucking::Light => "light",
+            MusicDucking::Strong => "strong",
+        }
+    }
+
+    pub fn from_save_key(key: &str) -> MusicDucking {
+        match key {
+            "off" => MusicDucking::Off,
+            "strong" => MusicDucking::Strong,
+            _ => MusicDucking::Light,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum DuckState {
+    Idle,
+    Held(f32),      // Seconds left at the ducked volume
+    Restoring(f32), // Seconds into fading back up
+}
+
+// Dips the music under big sound effects so they cut through, then eases it back
+pub struct MusicDucker {
+    state: DuckState,
+}
+
+impl MusicDucker {
+    pub fn new() -> Self {
+        Self { state: DuckState::Idle }
+    }
+
+    pub fn duck(&mut self) {
+        self.state = DuckState::Held(DUCK_HOLD);
+    }
+
+    pub fn update(&mut self, delta_time: f32, music: &Sound, ducking: MusicDucking) {
+        let full = music_volume(MUSIC_VOLUME);
+        let ducked = full * ducking.level();
+        self.state = match self.state {
+            DuckState::Idle => return,
+            DuckState::Held(left) if left > delta_time => {
+                set_sound_volume(music, ducked);
+                DuckState::Held(left - delta_time)
+            }
+            DuckState::Held(_) => DuckState::Restoring(0.0),
+            DuckState::Restoring(elapsed) => {
+                let progress = ((elapsed + delta_time) / DUCK_RESTORE).min(1.0);
+                set_sound_volume(music, ducked + (full - ducked) * progress);
+                if progress >= 1.0 {
+                    DuckState::Idle
+                } else {
+                    DuckState::Restoring(elapsed + delta_time)
+                }
+            }
+        };
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Wave {
+    Sine,
+    Square,
+    Triangle,
+    Saw,
+    Noise,
+}
+
+impl Wave {
+    fn from_name(name: &str) -> Option<Wave> {
+        match name {
+            "sine" => Some(Wave::Sine),
+            "square" => Some(Wave::Square),
+            "triangle" => Some(Wave::Triangle),
+            "saw" => Some(Wave::Saw),
+            "noise" => Some(Wave::Noise),
+            _ => None,
+        }
+    }
+
+    // `phase` counts whole cycles, `noise` is this sample's white noise
+    fn sample(&self, phase: f32, noise: f32) -> f32 {
+        let cycle = phase.fract();
+        match self {
+            Wave::Sine => (phase * std::f32::consts::TAU).sin(),
+            Wave::Square => if cycle < 0.5 { 1.0 } else { -1.0 },
+            Wave::Triangle => 1.0 - 4.0 * (cycle - 0.5).abs(),
+            Wave::Saw => cycle * 2.0 - 1.0,
+            Wave::Noise => noise,
+        }
+    }
+}
+
+// One waveform in a sound, starting at `at` seconds and dying away at `decay`
+#[derive(Clone, PartialEq, Debug)]
+struct Layer {
+    wave: Wave,
+    frequency: f32,
+    slide_to: f32,
+    at: f32,
+    decay: f32,
+    volume: f32,
+}
+
+impl Layer {
+    // `wave key=value ...`, as written after `layer =`
+    fn parse(text: &str) -> Option<Layer> {
+        let mut words = text.split_whitespace();
+        let wave = Wave::from_name(words.next()?)?;
+        let mut layer = Layer { wave, frequency: 0.0, slide_to: f32::NAN, at: 0.0, decay: 0.0, volume: 1.0 };
+        for word in words {
+            let (key, value) = word.split_once('=')?;
+            let value: f32 = value.parse().ok()?;
+            match key {
+                "freq" => layer.frequency = value,
+                "to" => layer.slide_to = value,
+                "at" => layer.at = value,
+                "decay" => layer.decay = value,
+                "volume" => layer.volume = value,
+                _ => return None,
+            }
+        }
+        if layer.slide_to.is_nan() {
+            layer.slide_to = layer.frequency;
+        }
+        Some(layer)
+    }
+}
+
+// A procedural sound effect: layers mixed together for `length` seconds
+#[derive(Clone, PartialEq, Debug)]
+struct SoundRecipe {
+    length: f32,
+    gain: f32,
+    layers: Vec<Layer>,
+}
+
+impl SoundRecipe {
+    fn render(&self) -> Vec<u8> {
+        let length = (SAMPLE_RATE as f32 * self.length) as u32;
+        let mut noise: u32 = 0x9e37_79b9;
+
+        let samples: Vec<i16> = (0..length)
+            .map(|i| {
+                let t = i as f32 / SAMPLE_RATE as f32;
+                noise ^= noise << 13;
+                noise ^= noise >> 17;
+                noise ^= noise << 5;
+                let white = noise as f32 / u32::MAX as f32 * 2.0 - 1.0;
+
+                let value: f32 = self
+                    .layers
+                    .iter()
+                    .map(|layer| {
+                        let since = t - layer.at;
+                        if since < 0.0 {
+                            return 0.0;
+                        }
+                        // Pitch slides evenly from `frequency` to `slide_to` by the end of the sound
+                        let span = (self.length - layer.at).max(f32::EPSILON);
+                        let phase = layer.frequency * since
+                            + (layer.slide_to - layer.frequency) * since * since / (2.0 * span);
+                        layer.wave.sample(phase, white) * layer.volume * (-since * layer.decay).exp()
+                    })
+                    .sum();
+                (value.clamp(-1.0, 1.0) * i16::MAX as f32 * self.gain) as i16
+            })
+            .collect();
+        encode_wav(SAMPLE_RATE, &samples)
+    }
+}
+
+// `[name]` sections of `key = value` lines, with `;` comments and blank lines skipped
+fn parse_recipes(source: &str) -> HashMap<String, SoundRecipe> {
+    let mut recipes = HashMap::new();
+    let mut current: Option<(String, SoundRecipe)> = None;
+
+    for line in source.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with(';') {
+            continue;
+        }
+        if let Some(name) = line.strip_prefix('[').and_then(|rest| rest.strip_suffix(']')) {
+            if let Some((name, recipe)) = current.take() {
+                recipes.insert(name, recipe);
+            }
+            current = Some((name.trim().to_string(), SoundRecipe { length: 0.5, gain: 0.5, layers: Vec::new() }));
+            continue;
+        }
+
+        let parsed = line.split_once('=').zip(current.as_mut()).and_then(|((key, value), (_, recipe))| {
+            let value = value.trim();
+            match key.trim() {
+                "length" => recipe.length = value.parse::<f32>().ok().filter(|length| *length > 0.0)?,
+                "gain" => recipe.gain = value.parse::<f32>().ok()?.clamp(0.0, 1.0),
+                "layer" => recipe.layers.push(Layer::parse(value)?),
+                _ => return None,
+            }
+            Some(())
+        });
+        if parsed.is_none() {
+            println!("Warning: Skipping malformed sound recipe line: {}", line);
+        }
+    }
+    if let Some((name, recipe)) = current {
+        recipes.insert(name, recipe);
+    }
+    recipes
+}
+
+// The sound effects built from recipes, rebuilt when the recipe file changes in debug builds
+pub struct SoundEffects {
+    pub impact: Option<Sound>,
+    pub level_complete: Option<Sound>,
+    pub fanfare: Option<Sound>,
+    pub poison: Option<Sound>,
+    pub danger: Option<Sound>,
+    recipes: HashMap<String, SoundRecipe>,
+    #[cfg(debug_assertions)]
+    modified: Option<SystemTime>,
+    #[cfg(debug_assertions)]
+    check_timer: f32,
+}
+
+impl SoundEffects {
+    pub async fn load() -> Self {
+        let mut effects = Self {
+            impact: None,
+            level_complete: None,
+            fanfare: None,
+            poison: None,
+            danger: None,
+            recipes: HashMap::new(),
+            #[cfg(debug_assertions)]
+            modified: None,
+            #[cfg(debug_assertions)]
+            check_timer: 0.0,
+        };
+        effects.build().await;
+        effects
+    }
+
+    // Reads the recipe file over the built-in recipes, and remakes any sound whose recipe changed
+    async fn build(&mut self) {
+        #[cfg(debug_assertions)]
+        {
+            self.modified = modified_time();
+        }
+        let mut recipes = parse_recipes(BUILT_IN_RECIPES);
+        match fs::read_to_string(RECIPES_PATH) {
+            Ok(source) => recipes.extend(parse_recipes(&source)),
+            Err(_) => println!("Warning: Could not load {}, using the built-in fallback", RECIPES_PATH),
+        }
+
+        for (name, sound) in [
+            ("impact", &mut self.impact),
+            ("level_complete", &mut self.level_complete),
+            ("fanfare", &mut self.fanfare),
+            ("poison", &mut self.poison),
+            ("danger", &mut self.danger),
+        ] {
+            let recipe = &recipes[name];
+            if sound.is_some() && self.recipes.get(name) == Some(recipe) {
+                continue;
+            }
+            *sound = match load_sound_from_bytes(&recipe.render()).await {
+                Ok(sound) => Some(sound),
+                Err(e) => {
+                    println!("Warning: Could not create {} sound: {:?}", name, e);
+                    None
+                }
+            };
+        }
+        self.recipes = recipes;
+    }
+
+    // Debug builds pick up edits to the recipe file while the game is running
+    #[cfg(debug_assertions)]
+    pub async fn watch(&mut self, delta_time: f32) {
+        self.check_timer += delta_time;
+        if self.check_timer < RELOAD_CHECK {
+            return;
+        }
+        self.check_timer = 0.0;
+
+        if modified_time() != self.modified {
+            println!("Reloading {}", RECIPES_PATH);
+            self.build().await;
+        }
+    }
+
+    #[cfg(not(debug_assertions))]
+    pub async fn watch(&mut self, _delta_time: f32) {}
+}
+
+#[cfg(debug_assertions)]
+fn modified_time() -> Option<SystemTime> {
+    fs::metadata(RECIPES_PATH).and_then(|metadata| metadata.modified()).ok()
+}
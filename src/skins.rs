@@ -0,0 +1,128 @@
+use macroquad::prelude::*;
+use std::f32::consts::{FRAC_PI_2, PI};
+use crate::snake::{Direction, Segment};
+
+const SPRITE_SIZE: u16 = 16;
+
+// Greyscale snake sprites built at startup and tinted with the theme colors.
+// Every sprite is drawn facing right: the head looks right, the body runs
+// left-right, the tail connects on its right side and the corner joins the
+// left and bottom edges.
+pub struct SnakeSkin {
+    pub head: Texture2D,
+    pub body: Texture2D,
+    pub corner: Texture2D,
+    pub tail: Texture2D,
+}
+
+impl SnakeSkin {
+    pub fn generate() -> Self {
+        Self {
+            head: make_texture(head_pixel),
+            body: make_texture(body_pixel),
+            corner: make_texture(corner_pixel),
+            tail: make_texture(tail_pixel),
+        }
+    }
+}
+
+fn make_texture(pixel: fn(f32, f32) -> Option<f32>) -> Texture2D {
+    let mut image = Image::gen_image_color(SPRITE_SIZE, SPRITE_SIZE, BLANK);
+    for y in 0..SPRITE_SIZE {
+        for x in 0..SPRITE_SIZE {
+            if let Some(shade) = pixel(x as f32 + 0.5, y as f32 + 0.5) {
+                image.set_pixel(x as u32, y as u32, Color::new(shade, shade, shade, 1.0));
+            }
+        }
+    }
+
+    let texture = Texture2D::from_image(&image);
+    texture.set_filter(FilterMode::Nearest);
+    texture
+}
+
+// Scales show up as a lighter diagonal pattern, edges are darker
+fn scale_shade(x: f32, y: f32, edge: bool) -> f32 {
+    if edge {
+        0.65
+    } else if (x as i32 + y as i32) % 4 == 0 {
+        0.8
+    } else {
+        1.0
+    }
+}
+
+fn body_pixel(x: f32, y: f32) -> Option<f32> {
+    if !(2.0..14.0).contains(&y) {
+        return None;
+    }
+    Some(scale_shade(x, y, !(3.0..13.0).contains(&y)))
+}
+
+fn corner_pixel(x: f32, y: f32) -> Option<f32> {
+    // Quarter ring around the bottom-left corner
+    let distance = (x * x + (16.0 - y) * (16.0 - y)).sqrt();
+    if !(2.0..14.0).contains(&distance) {
+        return None;
+    }
+    Some(scale_shade(x, y, !(3.0..13.0).contains(&distance)))
+}
+
+fn head_pixel(x: f32, y: f32) -> Option<f32> {
+    let in_neck = x < 8.0 && (2.0..14.0).contains(&y);
+    let distance = ((x - 8.0).powi(2) + (y - 8.0).powi(2)).sqrt();
+    if !in_neck && distance > 6.5 {
+        return None;
+    }
+
+    // Eyes
+    if (10.0..12.0).contains(&x) && ((4.0..6.0).contains(&y) || (10.0..12.0).contains(&y)) {
+        return Some(0.0);
+    }
+    Some(scale_shade(x, y, distance > 5.5 && x >= 8.0))
+}
+
+fn tail_pixel(x: f32, y: f32) -> Option<f32> {
+    // Tapers from a point on the left to full width on the right
+    let half_width = 1.0 + x / 16.0 * 5.0;
+    if (y - 8.0).abs() > half_width {
+        return None;
+    }
+    Some(scale_shade(x, y, (y - 8.0).abs() > half_width - 1.0))
+}
+
+// Direction from one cell to a neighbouring cell, None if they aren't touching
+pub fn direction_between(from: Segment, to: Segment) -> Option<Direction> {
+    match (to.x - from.x, to.y - from.y) {
+        (0, -1) => Some(Direction::Up),
+        (0, 1) => Some(Direction::Down),
+        (-1, 0) => Some(Direction::Left),
+        (1, 0) => Some(Direction::Right),
+        _ => None,
+    }
+}
+
+// Rotation that turns a right-facing sprite to face `dir`
+pub fn rotation_for(dir: Direction) -> f32 {
+    match dir {
+        Direction::Right => 0.0,
+        Direction::Down => FRAC_PI_2,
+        Direction::Left => PI,
+        Direction::Up => PI + FRAC_PI_2,
+    }
+}
+
+// Rotation for the corner sprite given the two sides it connects
+pub fn corner_rotation(a: Direction, b: Direction) -> f32 {
+    let has = |dir: Direction| a == dir || b == dir;
+
+    if has(Direction::Left) && has(Direction::Down) {
+        0.0
+    } else if has(Direction::Up) && has(Direction::Left) {
+        FRAC_PI_2
+    } else if has(Direction::Right) && has(Direction::Up) {
+        PI
+    } else {
+        PI + FRAC_PI_2
+    }
+}
@@ -0,0 +1,55 @@
+use macroquad::prelude::*;
+use crate::timestep::set_time_scale;
+
+// Real seconds the power-up lasts
+const DURATION: f32 = 5.0;
+// Game time passes at this fraction of real time while it's on
+const SCALE: f32 = 0.5;
+// Real seconds the tint takes to fade at the end
+const FADE: f32 = 1.0;
+
+// The slow motion power-up. It slows the game ticks, so the snake, walls and everything
+// else on the board move at half speed, while input is still read every frame.
+pub struct SlowMotion {
+    left: f32,
+}
+
+impl SlowMotion {
+    pub fn new() -> Self {
+        Self { left: 0.0 }
+    }
+
+    pub fn start(&mut self) {
+        self.left = DURATION;
+        set_time_scale(SCALE);
+    }
+
+    pub fn stop(&mut self) {
+        self.left = 0.0;
+        set_time_scale(1.0);
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.left > 0.0
+    }
+
+    // Counts down in real time, so pausing holds it
+    pub fn update(&mut self, frame_time: f32) {
+        if !self.is_active() {
+            return;
+        }
+        self.left -= frame_time;
+        if self.left <= 0.0 {
+            self.stop();
+        }
+    }
+
+    // Washes the board out to grey while it lasts
+    pub fn draw(&self) {
+        if !self.is_active() {
+            return;
+        }
+        let strength = (self.left / FADE).min(1.0);
+        draw_rectangle(0.0, 0.0, screen_width(), screen_height(), Color::new(0.45, 0.45, 0.5, 0.35 * strength));
+    }
+}
@@ -1,7 +1,26 @@
 use macroquad::prelude::*;
-use crate::grid::{GRID_WIDTH, GRID_HEIGHT, CELL_SIZE, get_offset};
+use macroquad::color::hsl_to_rgb;
+use std::collections::VecDeque;
+use crate::difficulty::Difficulty;
+use crate::hud::hud_scale;
+use crate::input::PlayerCommand;
+use crate::level::{speed_curve, speed_multiplier};
+use crate::grid::{GRID_WIDTH, GRID_HEIGHT, CELL_SIZE, get_offset, is_within_grid, wrap_position};
+use crate::motion::reduced_motion;
+use crate::skins::{corner_rotation, direction_between, rotation_for, SnakeSkin};
 use crate::themes::Theme;
 
+// Seconds for the whip meter to refill after use
+const WHIP_RECHARGE: f32 = 4.0;
+// Commands held for upcoming moves, so quick taps between ticks each get a move of their own
+const INPUT_BUFFER_SIZE: usize = 3;
+// Invincibility blinks a warning for this many seconds before it runs out
+const INVINCIBILITY_WARNING: f32 = 2.0;
+// Smooth body thickness as a share of a cell, and how it thins out toward the tail
+const BODY_WIDTH: f32 = 0.8;
+const TAIL_WIDTH: f32 = 0.35; // Share of the full width left at the very tip
+const TAPER_LENGTH: usize = 4; // Cells from the tip over which the body narrows
+
 #[derive(Clone, Copy, PartialEq, Debug)]
 pub struct Segment {
     pub x: i32,
@@ -16,12 +35,59 @@ pub enum Direction {
     Right,
 }
 
+impl Direction {
+    pub fn opposite(&self) -> Direction {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+        }
+    }
+
+    pub fn turned_left(&self) -> Direction {
+        match self {
+            Direction::Up => Direction::Left,
+            Direction::Left => Direction::Down,
+            Direction::Down => Direction::Right,
+            Direction::Right => Direction::Up,
+        }
+    }
+
+    pub fn turned_right(&self) -> Direction {
+        self.turned_left().opposite()
+    }
+
+    pub fn delta(&self) -> (i32, i32) {
+        match self {
+            Direction::Up => (0, -1),
+            Direction::Down => (0, 1),
+            Direction::Left => (-1, 0),
+            Direction::Right => (1, 0),
+        }
+    }
+}
+
 pub struct Snake {
-    pub body: Vec<Segment>,
+    pub body: VecDeque<Segment>, // Head at the front, so a move is one push and one pop
     pub dir: Direction,
-    pub grow_tail: bool,
+    pub steered: bool, // Player changed direction since the last move
+    pub grow_tail: usize, // Segments still to add, one per move
     pub move_timer: f32,
     pub move_delay: f32,
+    pub speed_multiplier: f32, // Temporary speed change, e.g. from a speed zone
+    pub straighten_to: Option<Direction>, // Heading to resume after a one-button sidestep
+    pub scripted_path: VecDeque<Direction>, // Moves forced by test hooks, one per tick
+    input_buffer: VecDeque<PlayerCommand>, // Player commands waiting for a move, one per tick
+    pub wrap: bool, // No walls, leaving one edge comes back in the other
+    pub whip_charge: f32, // 0..1, a whip turn needs a full meter
+    invincible_for: f32, // Seconds left passing through walls and our own body
+    invincible_total: f32,
+    pub god_mode: bool, // Debug console cheat, nothing kills the snake and the edges wrap
+    whip_from: Option<Direction>, // Heading to cut the corner from on the next move
+    pinched: bool, // This move was a whip that tried to squeeze between two of our own segments
+    occupancy: Vec<u16>, // Segments on each grid cell, so collision checks don't walk the body
+    speed_set_for: Option<(usize, Difficulty)>, // Level and difficulty move_delay was last worked out for
 }
 
 impl Snake {
@@ -29,89 +95,374 @@ impl Snake {
         let start_x = GRID_WIDTH / 2;
         let start_y = GRID_HEIGHT / 2;
 
-        Self {
-            body: vec![Segment { x: start_x, y: start_y }],
+        let mut snake = Self {
+            body: VecDeque::new(),
             dir: Direction::Right,
-            grow_tail: false,
+            steered: false,
+            grow_tail: 0,
             move_timer: 0.0,
             move_delay: 0.15,
-        }
+            speed_multiplier: 1.0,
+            straighten_to: None,
+            scripted_path: VecDeque::new(),
+            input_buffer: VecDeque::new(),
+            wrap: false,
+            whip_charge: 1.0,
+            invincible_for: 0.0,
+            invincible_total: 0.0,
+            god_mode: false,
+            whip_from: None,
+            pinched: false,
+            occupancy: vec![0; (GRID_WIDTH * GRID_HEIGHT) as usize],
+            speed_set_for: None,
+        };
+        snake.push_head(Segment { x: start_x, y: start_y });
+        snake
     }
 
-    pub fn update(&mut self, delta_time: f32) {
-        self.handle_input();
+    // `pull` is an outside force (like a gravity well) that bends the snake
+    // on its next move, unless the player has steered since the last one
+    pub fn update(&mut self, delta_time: f32, pull: Option<Direction>) {
+        self.whip_charge = (self.whip_charge + delta_time / WHIP_RECHARGE).min(1.0);
+        self.invincible_for = (self.invincible_for - delta_time).max(0.0);
 
         self.move_timer += delta_time;
-        if self.move_timer >= self.move_delay {
-            self.move_timer = 0.0;
+        let delay = self.move_delay / self.speed_multiplier;
+        if self.move_timer >= delay {
+            // Carry the leftover over so the pace doesn't depend on the tick size
+            self.move_timer = (self.move_timer - delay).min(delay);
+
+            // Checked against the heading of the last move, not the last key pressed
+            if let Some(command) = self.input_buffer.pop_front() {
+                self.apply(command);
+            }
+
+            if let Some(dir) = pull {
+                if !self.steered && dir != self.dir.opposite() {
+                    self.dir = dir;
+                }
+            }
+
+            if let Some(dir) = self.scripted_path.pop_front() {
+                self.dir = dir;
+            }
+
             self.move_snake();
+            self.steered = false;
+
+            if let Some(dir) = self.straighten_to.take() {
+                self.dir = dir;
+            }
         }
     }
 
     fn move_snake(&mut self) {
-        let mut new_head = self.body[0];
+        let head = self.body[0];
+        let (dx, dy) = self.dir.delta();
+        let mut new_head = Segment { x: head.x + dx, y: head.y + dy };
+        self.pinched = false;
+
+        // A whip turn also keeps going the old way, cutting diagonally across the corner
+        if let Some(from) = self.whip_from.take() {
+            let (fx, fy) = from.delta();
+            let corner = Segment { x: head.x + fx, y: head.y + fy };
+            // Can't slip through a gap that our own body closes off on both sides
+            self.pinched = self.is_at(new_head) && self.is_at(corner);
+            new_head.x += fx;
+            new_head.y += fy;
+        }
 
-        match self.dir {
-            Direction::Up => new_head.y -= 1,
-            Direction::Down => new_head.y += 1,
-            Direction::Left => new_head.x -= 1,
-            Direction::Right => new_head.x += 1,
+        if self.wrap {
+            (new_head.x, new_head.y) = wrap_position(new_head.x, new_head.y);
         }
 
-        self.body.insert(0, new_head);
+        self.push_head(new_head);
 
-        if !self.grow_tail {
-            self.body.pop();
+        if self.grow_tail == 0 {
+            self.pop_tail();
         } else {
-            self.grow_tail = false;
+            self.grow_tail -= 1;
         }
     }
 
-    pub fn draw(&self, theme: &Theme) {
-        let offset = get_offset();
+    fn push_head(&mut self, cell: Segment) {
+        if let Some(index) = occupancy_index(cell) {
+            self.occupancy[index] += 1;
+        }
+        self.body.push_front(cell);
+    }
 
-        for (i, segment) in self.body.iter().enumerate() {
-            let color = if i == 0 { 
-                theme.snake_head 
-            } else { 
-                theme.snake_body 
-            };
-
-            draw_rectangle(
-                offset.x + segment.x as f32 * CELL_SIZE,
-                offset.y + segment.y as f32 * CELL_SIZE,
-                CELL_SIZE,
-                CELL_SIZE,
+    fn pop_tail(&mut self) {
+        if let Some(cell) = self.body.pop_back() {
+            if let Some(index) = occupancy_index(cell) {
+                self.occupancy[index] -= 1;
+            }
+        }
+    }
+
+    // Moves the head straight to another cell, e.g. out of a teleporter
+    pub fn teleport_head(&mut self, cell: Segment) {
+        if let Some(old) = self.body.pop_front() {
+            if let Some(index) = occupancy_index(old) {
+                self.occupancy[index] -= 1;
+            }
+        }
+        self.push_head(cell);
+    }
+
+    // `smooth` draws a rounded, tapering body instead of a sprite per cell
+    pub fn draw(&self, theme: &Theme, skin: &SnakeSkin, smooth: bool) {
+        if smooth {
+            self.draw_smooth(theme);
+            self.draw_segment(0, theme, skin);
+            return;
+        }
+
+        for index in 0..self.body.len() {
+            self.draw_segment(index, theme, skin);
+        }
+    }
+
+    fn draw_segment(&self, index: usize, theme: &Theme, skin: &SnakeSkin) {
+        let offset = get_offset();
+        let segment = self.body[index];
+        let color = match self.shimmer(index) {
+            Some(color) => color,
+            None if index == 0 => theme.snake_head,
+            None => theme.snake_body,
+        };
+        let x = offset.x + segment.x as f32 * CELL_SIZE;
+        let y = offset.y + segment.y as f32 * CELL_SIZE;
+
+        match self.sprite_for(index, skin) {
+            Some((texture, rotation)) => draw_texture_ex(
+                texture,
+                x,
+                y,
                 color,
-            );
+                DrawTextureParams {
+                    dest_size: Some(vec2(CELL_SIZE, CELL_SIZE)),
+                    rotation,
+                    ..Default::default()
+                },
+            ),
+            // Flat square when the neighbours aren't touching, e.g. across a teleporter
+            None => draw_rectangle(x, y, CELL_SIZE, CELL_SIZE, color),
         }
     }
 
-    pub fn grow(&mut self) {
-        self.grow_tail = true;
+    // A tube through the middle of each cell, with round joints so corners curve, narrowing
+    // over the last few cells into a point. Drawn tail first so nearer cells overlap.
+    fn draw_smooth(&self, theme: &Theme) {
+        let offset = get_offset();
+        let center = |segment: Segment| {
+            vec2(
+                offset.x + (segment.x as f32 + 0.5) * CELL_SIZE,
+                offset.y + (segment.y as f32 + 0.5) * CELL_SIZE,
+            )
+        };
+        let last = self.body.len() - 1;
+        let width = |index: usize| {
+            let taper = ((last - index) as f32 / TAPER_LENGTH as f32).min(1.0);
+            CELL_SIZE * BODY_WIDTH * (TAIL_WIDTH + (1.0 - TAIL_WIDTH) * taper)
+        };
+
+        for index in (1..=last).rev() {
+            let color = self.shimmer(index).unwrap_or(theme.snake_body);
+            let (from, to) = (center(self.body[index]), center(self.body[index - 1]));
+            // No bridge across a teleporter or wrapped edge
+            if direction_between(self.body[index], self.body[index - 1]).is_some() {
+                draw_line(from.x, from.y, to.x, to.y, width(index), color);
+            }
+            draw_circle(from.x, from.y, width(index) / 2.0, color);
+        }
+    }
+
+    // Rainbow running down the body while invincible, blinking off as it's about to end.
+    // With reduced motion the colours hold still and there's no blinking.
+    fn shimmer(&self, index: usize) -> Option<Color> {
+        if !self.is_invincible() {
+            return None;
+        }
+        let time = get_time() as f32;
+        let ending = self.invincible_for < INVINCIBILITY_WARNING;
+        if ending && !reduced_motion() && (time * 8.0).fract() < 0.5 {
+            return None;
+        }
+        let drift = if reduced_motion() { 0.0 } else { time * 0.8 };
+        Some(hsl_to_rgb((index as f32 * 0.07 + drift).fract(), 0.9, 0.6))
     }
 
-    fn handle_input(&mut self) {
-        let new_dir = self.get_new_direction();
-        if let Some(dir) = new_dir {
-            self.dir = dir;
+    // Countdown bar under the whip meter, red once it's about to run out
+    pub fn draw_invincibility_bar(&self, theme: &Theme) {
+        if !self.is_invincible() {
+            return;
         }
+        let fraction = self.invincible_for / self.invincible_total.max(f32::EPSILON);
+        let ending = self.invincible_for < INVINCIBILITY_WARNING;
+        let color = if ending { RED } else { GOLD };
+        let hud = hud_scale();
+        let text = t!("hud-invincible", seconds = self.invincible_for.ceil());
+        draw_text(&text, 20.0 * hud, 128.0 * hud, 20.0 * hud, color);
+        draw_rectangle_lines(20.0 * hud, 134.0 * hud, 130.0 * hud, 8.0 * hud, 1.0, theme.ui_text);
+        draw_rectangle(20.0 * hud, 134.0 * hud, 130.0 * hud * fraction, 8.0 * hud, color);
     }
 
-    fn get_new_direction(&self) -> Option<Direction> {
-        if is_key_pressed(KeyCode::Up) && self.dir != Direction::Down {
-            Some(Direction::Up)
-        } else if is_key_pressed(KeyCode::Down) && self.dir != Direction::Up {
-            Some(Direction::Down)
-        } else if is_key_pressed(KeyCode::Left) && self.dir != Direction::Right {
-            Some(Direction::Left)
-        } else if is_key_pressed(KeyCode::Right) && self.dir != Direction::Left {
-            Some(Direction::Right)
+    // Picks the head, body, corner or tail sprite for a segment from where its
+    // neighbours are, along with the rotation to draw it at
+    fn sprite_for<'a>(&self, index: usize, skin: &'a SnakeSkin) -> Option<(&'a Texture2D, f32)> {
+        let segment = self.body[index];
+        let toward_head = if index > 0 {
+            Some(direction_between(segment, self.body[index - 1])?)
+        } else {
+            None
+        };
+        let toward_tail = if index + 1 < self.body.len() {
+            Some(direction_between(segment, self.body[index + 1])?)
         } else {
             None
+        };
+
+        match (toward_head, toward_tail) {
+            (None, None) => Some((&skin.head, rotation_for(self.dir))),
+            (None, Some(back)) => Some((&skin.head, rotation_for(back.opposite()))),
+            (Some(front), None) => Some((&skin.tail, rotation_for(front))),
+            (Some(front), Some(back)) if front == back.opposite() => Some((&skin.body, rotation_for(front))),
+            (Some(front), Some(back)) => Some((&skin.corner, corner_rotation(front, back))),
         }
     }
 
+    pub fn grow(&mut self) {
+        self.grow_tail += 1;
+    }
+
+    // Takes cells off the tail, growth still to come goes first. The head always stays.
+    pub fn shrink(&mut self, cells: usize) {
+        let pending = self.grow_tail.min(cells);
+        self.grow_tail -= pending;
+        for _ in pending..cells {
+            if self.body.len() <= 1 {
+                break;
+            }
+            self.pop_tail();
+        }
+    }
+
+    // Queues a command for the next free move. Turns that would do nothing from the
+    // heading the queue already leads to are dropped rather than taking up a move.
+    pub fn buffer(&mut self, command: PlayerCommand) {
+        let heading = self.queued_heading();
+        let redundant = matches!(command, PlayerCommand::SetDirection(dir) if dir == heading || dir == heading.opposite());
+        if !redundant && self.input_buffer.len() < INPUT_BUFFER_SIZE {
+            self.input_buffer.push_back(command);
+        }
+    }
+
+    // Pausing drops anything pressed before it, so nothing fires on resume
+    pub fn clear_input(&mut self) {
+        self.input_buffer.clear();
+    }
+
+    // Heading the snake will have once everything in the buffer has been applied
+    pub fn queued_heading(&self) -> Direction {
+        self.input_buffer.iter().fold(self.dir, |heading, command| match *command {
+            PlayerCommand::TurnLeft => heading.turned_left(),
+            PlayerCommand::TurnRight => heading.turned_right(),
+            PlayerCommand::SetDirection(dir) | PlayerCommand::Sidestep(dir) => dir,
+            PlayerCommand::Whip(_) => heading,
+        })
+    }
+
+    // Cells the head will move through over the next `cells` moves, going by the buffered
+    // commands and straight on after them. Gravity pulls and whip turns aren't predicted.
+    pub fn planned_path(&self, cells: usize) -> Vec<Segment> {
+        let mut commands = self.input_buffer.iter();
+        let mut dir = self.dir;
+        let mut straighten_to = self.straighten_to;
+        let mut head = self.head();
+        let mut path = Vec::with_capacity(cells);
+
+        for _ in 0..cells {
+            if let Some(command) = commands.next() {
+                dir = match *command {
+                    PlayerCommand::TurnLeft => dir.turned_left(),
+                    PlayerCommand::TurnRight => dir.turned_right(),
+                    PlayerCommand::SetDirection(new_dir) if new_dir == dir.opposite() => dir,
+                    PlayerCommand::SetDirection(new_dir) => new_dir,
+                    PlayerCommand::Sidestep(new_dir) => {
+                        straighten_to.get_or_insert(dir);
+                        new_dir
+                    }
+                    PlayerCommand::Whip(_) => dir,
+                };
+            }
+
+            let (dx, dy) = dir.delta();
+            head = Segment { x: head.x + dx, y: head.y + dy };
+            if self.wrap {
+                (head.x, head.y) = wrap_position(head.x, head.y);
+            }
+            path.push(head);
+            if !is_within_grid(head.x, head.y) {
+                break;
+            }
+            if let Some(straight) = straighten_to.take() {
+                dir = straight;
+            }
+        }
+        path
+    }
+
+    // Whether some of today's body will still be on `cell` after `moves` more moves,
+    // the tail having moved off the rest by then
+    pub fn occupies_after(&self, cell: Segment, moves: usize) -> bool {
+        let kept = (self.body.len() + moves.min(self.grow_tail)).saturating_sub(moves);
+        self.body.iter().take(kept).any(|segment| *segment == cell)
+    }
+
+    pub fn apply(&mut self, command: PlayerCommand) {
+        let dir = match command {
+            PlayerCommand::TurnLeft => self.dir.turned_left(),
+            PlayerCommand::TurnRight => self.dir.turned_right(),
+            PlayerCommand::SetDirection(dir) if dir == self.dir.opposite() => return,
+            PlayerCommand::SetDirection(dir) => dir,
+            PlayerCommand::Sidestep(dir) => {
+                if self.straighten_to.is_none() {
+                    self.straighten_to = Some(self.dir);
+                }
+                dir
+            }
+            PlayerCommand::Whip(from) => {
+                if self.whip_charge >= 1.0 {
+                    self.whip_charge = 0.0;
+                    self.whip_from = Some(from);
+                }
+                return;
+            }
+        };
+
+        self.dir = dir;
+        self.steered = true;
+    }
+
+    pub fn draw_whip_meter(&self, theme: &Theme) {
+        let color = if self.whip_charge >= 1.0 { theme.snake_head } else { GRAY };
+        let hud = hud_scale();
+        draw_text("WHIP", 20.0 * hud, 80.0 * hud, 20.0 * hud, color);
+        draw_rectangle_lines(70.0 * hud, 68.0 * hud, 80.0 * hud, 10.0 * hud, 1.0, theme.ui_text);
+        draw_rectangle(70.0 * hud, 68.0 * hud, 80.0 * hud * self.whip_charge, 10.0 * hud, color);
+    }
+
+    pub fn make_invincible(&mut self, seconds: f32) {
+        self.invincible_for = seconds;
+        self.invincible_total = seconds;
+    }
+
+    // Walls and the snake's own body don't count while this lasts, the edge of the board still does
+    pub fn is_invincible(&self) -> bool {
+        self.invincible_for > 0.0
+    }
+
     pub fn is_dead(&self) -> bool {
         let head = self.head();
 
@@ -120,12 +471,22 @@ impl Snake {
             return true;
         }
 
-        // Check self collision - skip the head itself
-        self.body.iter().skip(1).any(|&segment| segment == head)
+        if self.is_invincible() || self.god_mode {
+            return false;
+        }
+
+        // Check self collision - the head itself accounts for one segment on its cell.
+        // A pinched whip counts too, but only until the next move.
+        self.pinched || occupancy_index(head).is_some_and(|index| self.occupancy[index] > 1)
+    }
+
+    // Grid cells with some of the snake on them
+    pub fn occupied_cells(&self) -> usize {
+        self.occupancy.iter().filter(|count| **count > 0).count()
     }
 
     pub fn is_at(&self, position: Segment) -> bool {
-        self.body.contains(&position)
+        occupancy_index(position).is_some_and(|index| self.occupancy[index] > 0)
     }
 
     pub fn head(&self) -> Segment {
@@ -145,34 +506,58 @@ impl Snake {
         let start_y = GRID_HEIGHT / 2;
         
         self.body.clear();
-        self.body.push(Segment { x: start_x, y: start_y });
+        self.occupancy.fill(0);
+        self.push_head(Segment { x: start_x, y: start_y });
         self.dir = Direction::Right;
-        self.grow_tail = false;
+        self.steered = false;
+        self.grow_tail = 0;
         self.move_timer = 0.0;
         self.move_delay = 0.15; // Reset to base speed
+        self.speed_multiplier = 1.0;
+        self.straighten_to = None;
+        self.scripted_path.clear();
+        self.input_buffer.clear();
+        self.whip_charge = 1.0;
+        self.invincible_for = 0.0;
+        self.whip_from = None;
+        self.pinched = false;
+        self.speed_set_for = None;
+    }
+
+    // Starts over somewhere other than the middle, e.g. for the second snake in versus
+    pub fn reset_at(&mut self, cell: Segment, dir: Direction) {
+        self.reset();
+        self.teleport_head(cell);
+        self.dir = dir;
     }
 
-    // New method for updating speed based on level
-    pub fn update_speed(&mut self, level: usize) {
-        // Base delay is 0.15, minimum delay is 0.05 (3x faster)
-        // Using a logarithmic curve for smooth progression
-        let base_delay = 0.15;
+    // New method for updating speed based on level. It's called every frame, but the
+    // levels file is only asked again once the level or difficulty changes.
+    pub fn update_speed(&mut self, level: usize, difficulty: Difficulty) {
+        if self.speed_set_for == Some((level, difficulty)) {
+            return;
+        }
+        self.speed_set_for = Some((level, difficulty));
+
+        // Base delay comes from the difficulty (0.15 on Normal), minimum delay is 0.05
+        let base_delay = difficulty.base_delay();
         let min_delay = 0.05;
         
-        // Calculate speed multiplier using logarithmic scaling
-        // This gives rapid increase early on, then slower increases
-        let speed_factor = 1.0 + (level as f32 - 1.0).ln().max(0.0) * 0.3;
+        // The speed curve comes from the levels file, logarithmic unless it says otherwise.
+        // That gives rapid increase early on, then slower increases
+        let speed_factor = speed_curve(level, difficulty).factor(level);
+        // Some levels are tuned faster or slower in the levels file
+        let speed_factor = speed_factor * speed_multiplier(level);
         
         // Calculate new delay (inverse of speed)
         self.move_delay = (base_delay / speed_factor).max(min_delay);
     }
 }
 
-
-
-
-
-
-
-
-
+fn occupancy_index(cell: Segment) -> Option<usize> {
+    if is_within_grid(cell.x, cell.y) {
+        Some((cell.y * GRID_WIDTH + cell.x) as usize)
+    } else {
+        None
+    }
+}
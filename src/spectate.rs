@@ -0,0 +1,165 @@
+use macroquad::prelude::*;
+use crate::cpu_snake::CpuSnakeManager;
+use crate::snake::Segment;
+
+// Seconds of watching before the round ends and the results show
+const ROUND_TIME: f32 = 20.0;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum SpectateState {
+    Off,
+    Offering, // Player just died, asking whether to watch
+    Watching,
+}
+
+// How the player left the crash screen
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum CrashChoice {
+    Results,
+    Restart,
+    Menu,
+    Statistics,
+}
+
+// After the player dies, lets them follow the CPU snakes until the round ends
+pub struct Spectator {
+    pub state: SpectateState,
+    target: usize,
+    time_left: f32,
+    can_restart: bool,
+}
+
+impl Spectator {
+    pub fn new() -> Self {
+        Self {
+            state: SpectateState::Off,
+            target: 0,
+            time_left: 0.0,
+            can_restart: false,
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.state != SpectateState::Off
+    }
+
+    // `can_restart` offers R to start a new run straight from the crash screen
+    pub fn offer(&mut self, can_restart: bool) {
+        self.state = SpectateState::Offering;
+        self.can_restart = can_restart;
+    }
+
+    // Returns the player's choice once they're done and the game should end
+    pub fn update(&mut self, delta_time: f32, cpu_snakes: &mut CpuSnakeManager, level: usize) -> Option<CrashChoice> {
+        let choice = if is_key_pressed(KeyCode::Escape) {
+            Some(CrashChoice::Menu)
+        } else if is_key_pressed(KeyCode::Space) {
+            Some(CrashChoice::Results)
+        } else {
+            None
+        };
+
+        match self.state {
+            SpectateState::Off => None,
+            SpectateState::Offering => {
+                if is_key_pressed(KeyCode::V) && !cpu_snakes.snakes.is_empty() {
+                    self.state = SpectateState::Watching;
+                    self.target = leader(cpu_snakes);
+                    self.time_left = ROUND_TIME;
+                    None
+                } else if is_key_pressed(KeyCode::R) && self.can_restart {
+                    self.finish(Some(CrashChoice::Restart))
+                } else if is_key_pressed(KeyCode::Tab) {
+                    // Not S, which WASD players may still be pressing to steer
+                    self.finish(Some(CrashChoice::Statistics))
+                } else {
+                    self.finish(choice)
+                }
+            }
+            SpectateState::Watching => {
+                cpu_snakes.update(delta_time, level);
+
+                let count = cpu_snakes.snakes.len();
+                if is_key_pressed(KeyCode::Right) {
+                    self.target = (self.target + 1) % count;
+                } else if is_key_pressed(KeyCode::Left) {
+                    self.target = (self.target + count - 1) % count;
+                }
+
+                self.time_left -= delta_time;
+                if self.time_left <= 0.0 {
+                    self.finish(Some(CrashChoice::Results))
+                } else {
+                    self.finish(choice)
+                }
+            }
+        }
+    }
+
+    fn finish(&mut self, choice: Option<CrashChoice>) -> Option<CrashChoice> {
+        if choice.is_some() {
+            self.state = SpectateState::Off;
+        }
+        choice
+    }
+
+    // Head of the snake being watched, for the camera to follow
+    pub fn target_head(&self, cpu_snakes: &CpuSnakeManager) -> Option<Segment> {
+        if self.state != SpectateState::Watching {
+            return None;
+        }
+        cpu_snakes.snakes.get(self.target).map(|snake| snake.body[0])
+    }
+
+    pub fn draw(&self, cpu_snakes: &CpuSnakeManager) {
+        match self.state {
+            SpectateState::Off => {}
+            SpectateState::Offering => {
+                draw_rectangle(0.0, 0.0, screen_width(), screen_height(), Color::new(0.0, 0.0, 0.0, 0.5));
+                draw_centered("YOU CRASHED", screen_height() / 2.0 - 30.0, 48.0, RED);
+                draw_centered(
+                    "V - Spectate the CPU snakes   SPACE - Results",
+                    screen_height() / 2.0 + 20.0,
+                    24.0,
+                    WHITE,
+                );
+                let shortcuts = if self.can_restart {
+                    "R - Restart   TAB - Statistics   ESC - Main Menu"
+                } else {
+                    "TAB - Statistics   ESC - Main Menu"
+                };
+                draw_centered(shortcuts, screen_height() / 2.0 + 95.0, 20.0, GRAY);
+            }
+            SpectateState::Watching => {
+                let Some(snake) = cpu_snakes.snakes.get(self.target) else {
+                    return;
+                };
+                let heading = format!("SPECTATING CPU {} of {}", self.target + 1, cpu_snakes.snakes.len());
+                draw_centered(&heading, screen_height() - 60.0, 28.0, snake.color_head);
+                let details = format!(
+                    "Length: {}   Moves: {}   Round ends in {:.0}s",
+                    snake.body.len(),
+                    snake.moves,
+                    self.time_left.max(0.0).ceil()
+                );
+                draw_centered(&details, screen_height() - 35.0, 22.0, WHITE);
+                draw_centered("LEFT / RIGHT - Switch snake   SPACE - Results", screen_height() - 12.0, 18.0, GRAY);
+            }
+        }
+    }
+}
+
+// The longest CPU snake, or the one that has travelled furthest when they're tied
+fn leader(cpu_snakes: &CpuSnakeManager) -> usize {
+    cpu_snakes
+        .snakes
+        .iter()
+        .enumerate()
+        .max_by_key(|(_, snake)| (snake.body.len(), snake.moves))
+        .map_or(0, |(index, _)| index)
+}
+
+fn draw_centered(text: &str, y: f32, size: f32, color: Color) {
+    let width = measure_text(text, None, size as u16, 1.0).width;
+    draw_text(text, (screen_width() - width) / 2.0, y, size, color);
+}
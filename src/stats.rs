@@ -0,0 +1,194 @@
+use macroquad::prelude::*;
+use crate::arena::Arena;
+use crate::food::Food;
+use crate::grid::{GRID_WIDTH, GRID_HEIGHT};
+use crate::snake::{Segment, Snake};
+
+const MAX_SNAPSHOTS: usize = 10;
+// Pixels per cell on the statistics screen mini-boards
+const MINI_CELL: f32 = 3.0;
+
+// Compact picture of the board at the moment the player died
+pub struct BoardSnapshot {
+    pub level: usize,
+    pub snake: Vec<Segment>,
+    pub food: Segment,
+    pub obstacles: Vec<Segment>,
+}
+
+impl BoardSnapshot {
+    pub fn capture(level: usize, snake: &Snake, food: &Food, arena: &Arena) -> Self {
+        let mut obstacles = Vec::new();
+        for y in 0..GRID_HEIGHT {
+            for x in 0..GRID_WIDTH {
+                let cell = Segment { x, y };
+                if arena.is_blocked(cell) {
+                    obstacles.push(cell);
+                }
+            }
+        }
+
+        Self {
+            level,
+            snake: snake.body.iter().copied().collect(),
+            food: food.position,
+            obstacles,
+        }
+    }
+
+    // level|food|snake cells|obstacle cells, cells as x,y joined by ;
+    fn encode(&self) -> String {
+        format!(
+            "{}|{}|{}|{}",
+            self.level,
+            encode_cells(&[self.food]),
+            encode_cells(&self.snake),
+            encode_cells(&self.obstacles)
+        )
+    }
+
+    fn decode(text: &str) -> Option<Self> {
+        let mut parts = text.split('|');
+        let level = parts.next()?.parse().ok()?;
+        let food = *decode_cells(parts.next()?)?.first()?;
+        let snake = decode_cells(parts.next()?)?;
+        let obstacles = decode_cells(parts.next()?)?;
+
+        Some(Self { level, snake, food, obstacles })
+    }
+
+    fn draw_mini(&self, x: f32, y: f32) {
+        let width = GRID_WIDTH as f32 * MINI_CELL;
+        let height = GRID_HEIGHT as f32 * MINI_CELL;
+
+        draw_rectangle(x, y, width, height, Color::new(0.08, 0.08, 0.08, 1.0));
+        draw_rectangle_lines(x, y, width, height, 1.0, GRAY);
+
+        let cell = |segment: &Segment, color: Color| {
+            draw_rectangle(
+                x + segment.x as f32 * MINI_CELL,
+                y + segment.y as f32 * MINI_CELL,
+                MINI_CELL,
+                MINI_CELL,
+                color,
+            );
+        };
+
+        for obstacle in &self.obstacles {
+            cell(obstacle, LIGHTGRAY);
+        }
+        cell(&self.food, RED);
+        for (i, segment) in self.snake.iter().enumerate() {
+            cell(segment, if i == 0 { YELLOW } else { GREEN });
+        }
+
+        draw_text(&format!("Level {}", self.level), x, y - 4.0, 18.0, WHITE);
+    }
+}
+
+fn encode_cells(cells: &[Segment]) -> String {
+    cells
+        .iter()
+        .map(|cell| format!("{},{}", cell.x, cell.y))
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+fn decode_cells(text: &str) -> Option<Vec<Segment>> {
+    if text.is_empty() {
+        return Some(Vec::new());
+    }
+
+    text.split(';')
+        .map(|pair| {
+            let (x, y) = pair.split_once(',')?;
+            Some(Segment { x: x.parse().ok()?, y: y.parse().ok()? })
+        })
+        .collect()
+}
+
+pub struct GameStatistics {
+    pub death_snapshots: Vec<BoardSnapshot>, // Oldest first
+    pub longest_snake: usize,
+}
+
+impl GameStatistics {
+    pub fn new() -> Self {
+        Self {
+            death_snapshots: Vec::new(),
+            longest_snake: 0,
+        }
+    }
+
+    pub fn record_death(&mut self, snapshot: BoardSnapshot) {
+        self.death_snapshots.push(snapshot);
+        if self.death_snapshots.len() > MAX_SNAPSHOTS {
+            self.death_snapshots.remove(0);
+        }
+    }
+
+    pub fn record_length(&mut self, length: usize) {
+        self.longest_snake = self.longest_snake.max(length);
+    }
+
+    pub fn write_lines(&self, out: &mut String) {
+        out.push_str(&format!("longest_snake={}\n", self.longest_snake));
+        for snapshot in &self.death_snapshots {
+            out.push_str(&format!("death_snapshot={}\n", snapshot.encode()));
+        }
+    }
+
+    // Picks up statistics keys from a save file line, ignoring anything else
+    pub fn read_line(&mut self, key: &str, value: &str) {
+        match key {
+            "death_snapshot" => {
+                if let Some(snapshot) = BoardSnapshot::decode(value) {
+                    self.record_death(snapshot);
+                }
+            }
+            "longest_snake" => self.longest_snake = value.parse().unwrap_or(0),
+            _ => {}
+        }
+    }
+
+    pub fn draw_screen(&self) {
+        draw_rectangle(0.0, 0.0, screen_width(), screen_height(), Color::new(0.0, 0.0, 0.0, 0.9));
+
+        let heading = "STATISTICS";
+        let heading_width = measure_text(heading, None, 48, 1.0).width;
+        draw_text(heading, (screen_width() - heading_width) / 2.0, 60.0, 48.0, GREEN);
+
+        let subheading = "Your last deaths, newest first";
+        let subheading_width = measure_text(subheading, None, 24, 1.0).width;
+        draw_text(subheading, (screen_width() - subheading_width) / 2.0, 95.0, 24.0, LIGHTGRAY);
+
+        let longest = format!("Longest snake ever: {}", self.longest_snake);
+        let longest_width = measure_text(&longest, None, 20, 1.0).width;
+        draw_text(&longest, (screen_width() - longest_width) / 2.0, 120.0, 20.0, YELLOW);
+
+        if self.death_snapshots.is_empty() {
+            let empty = "No deaths recorded yet";
+            let empty_width = measure_text(empty, None, 28, 1.0).width;
+            draw_text(empty, (screen_width() - empty_width) / 2.0, screen_height() / 2.0, 28.0, GRAY);
+        }
+
+        let columns = 5;
+        let board_width = GRID_WIDTH as f32 * MINI_CELL;
+        let board_height = GRID_HEIGHT as f32 * MINI_CELL;
+        let spacing = 14.0;
+        let total_width = columns as f32 * board_width + (columns - 1) as f32 * spacing;
+        let start_x = (screen_width() - total_width) / 2.0;
+
+        for (i, snapshot) in self.death_snapshots.iter().rev().enumerate() {
+            let column = i % columns;
+            let row = i / columns;
+            let x = start_x + column as f32 * (board_width + spacing);
+            let y = 140.0 + row as f32 * (board_height + 40.0);
+            snapshot.draw_mini(x, y);
+        }
+
+        let prompt = "Press E to export as JSON  -  S or ESC to go back";
+        let prompt_width = measure_text(prompt, None, 24, 1.0).width;
+        draw_text(prompt, (screen_width() - prompt_width) / 2.0, screen_height() - 30.0, 24.0, LIGHTGRAY);
+    }
+}
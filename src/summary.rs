@@ -0,0 +1,39 @@
+use macroquad::prelude::*;
+
+// Snapshot of how the current run is going
+#[derive(Clone)]
+pub struct RunSummary {
+    pub level: usize,
+    pub tails: u32,
+    pub points: u32,
+    pub length: usize,
+    pub best_combo: u32,
+    pub foods_to_next_level: u32,
+}
+
+impl RunSummary {
+    pub fn draw_panel(&self, heading: &str) {
+        let panel_width = 440.0;
+        let panel_height = 300.0;
+        let panel_x = (screen_width() - panel_width) / 2.0;
+        let panel_y = (screen_height() - panel_height) / 2.0;
+
+        draw_rectangle(panel_x, panel_y, panel_width, panel_height, Color::new(0.05, 0.05, 0.1, 0.95));
+        draw_rectangle_lines(panel_x, panel_y, panel_width, panel_height, 2.0, SKYBLUE);
+
+        let heading_width = measure_text(heading, None, 36, 1.0).width;
+        draw_text(heading, (screen_width() - heading_width) / 2.0, panel_y + 45.0, 36.0, SKYBLUE);
+
+        let lines = [
+            format!("Level: {}", self.level),
+            format!("Tails: {}", self.tails),
+            format!("Points: {}", self.points),
+            format!("Length: {}", self.length),
+            format!("Best Combo: x{}", self.best_combo),
+            format!("Next level in {} food", self.foods_to_next_level),
+        ];
+        for (i, line) in lines.iter().enumerate() {
+            draw_text(line, panel_x + 40.0, panel_y + 90.0 + i as f32 * 30.0, 26.0, WHITE);
+        }
+    }
+}
@@ -0,0 +1,230 @@
+use macroquad::prelude::*;
+use ::rand::Rng;
+use crate::grid::{GRID_WIDTH, GRID_HEIGHT, CELL_SIZE, get_offset};
+use crate::snake::{Direction, Segment, Snake};
+use crate::rng;
+
+// Seconds before a network can be used again, so the snake can't bounce straight back
+const COOLDOWN: f32 = 1.0;
+const NETWORK_COLORS: [Color; 3] = [SKYBLUE, MAGENTA, GOLD];
+// Teleporter levels from here on get networks and one-way portals instead of plain pairs
+const NETWORK_LEVEL: usize = 20;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum PortalKind {
+    TwoWay,
+    Entry, // One-way, can only be entered
+    Exit,  // One-way, can only be come out of
+}
+
+impl PortalKind {
+    fn can_enter(&self) -> bool {
+        *self != PortalKind::Exit
+    }
+
+    fn can_exit(&self) -> bool {
+        *self != PortalKind::Entry
+    }
+}
+
+// Each node in a network as (kind, weight). The weight is how likely that node
+// is to be picked as the exit compared to the others.
+type NetworkLayout = &'static [(PortalKind, u32)];
+
+const PAIR: NetworkLayout = &[(PortalKind::TwoWay, 1), (PortalKind::TwoWay, 1)];
+const TRIANGLE: NetworkLayout = &[(PortalKind::TwoWay, 1), (PortalKind::TwoWay, 1), (PortalKind::TwoWay, 2)];
+const ONE_WAY_FORK: NetworkLayout = &[(PortalKind::Entry, 1), (PortalKind::Exit, 3), (PortalKind::Exit, 1)];
+
+fn layouts_for(level: usize) -> Vec<NetworkLayout> {
+    if level < NETWORK_LEVEL {
+        vec![PAIR, PAIR]
+    } else {
+        vec![TRIANGLE, ONE_WAY_FORK]
+    }
+}
+
+pub struct Teleporter {
+    pub position: Segment,
+    pub network: usize,
+    pub kind: PortalKind,
+    pub weight: u32,
+}
+
+pub struct Teleporters {
+    pub portals: Vec<Teleporter>,
+    pub cooldowns: Vec<f32>, // Indexed by network
+}
+
+impl Teleporters {
+    pub fn new() -> Self {
+        Self {
+            portals: Vec::new(),
+            cooldowns: Vec::new(),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.portals.clear();
+        self.cooldowns.clear();
+    }
+
+    // Places the level's networks on cells that `is_free` accepts
+    pub fn place(&mut self, level: usize, is_free: impl Fn(Segment) -> bool) {
+        self.clear();
+        let mut rng = rng::gameplay();
+
+        for (network, layout) in layouts_for(level).into_iter().enumerate() {
+            for &(kind, weight) in layout {
+                // Keep portals off the edges so there's always room to exit
+                let position = loop {
+                    let pos = Segment {
+                        x: rng.gen_range(1..GRID_WIDTH - 1),
+                        y: rng.gen_range(1..GRID_HEIGHT - 1),
+                    };
+                    if is_free(pos) && !self.is_at(pos) {
+                        break pos;
+                    }
+                };
+                self.portals.push(Teleporter { position, network, kind, weight });
+            }
+            self.cooldowns.push(0.0);
+        }
+    }
+
+    pub fn update(&mut self, delta_time: f32) {
+        for cooldown in &mut self.cooldowns {
+            *cooldown = (*cooldown - delta_time).max(0.0);
+        }
+    }
+
+    pub fn is_at(&self, position: Segment) -> bool {
+        self.portals.iter().any(|portal| portal.position == position)
+    }
+
+    // If the snake head is on a ready portal, returns where the head should come out:
+    // one step past an exit of the same network in the direction of travel
+    pub fn check(&mut self, snake: &Snake) -> Option<Segment> {
+        let head = snake.head();
+        let entry = self
+            .portals
+            .iter()
+            .find(|portal| portal.position == head && portal.kind.can_enter())?;
+        let network = entry.network;
+
+        if self.cooldowns[network] > 0.0 {
+            return None;
+        }
+
+        let exits: Vec<&Teleporter> = self
+            .portals
+            .iter()
+            .filter(|portal| portal.network == network && portal.position != head && portal.kind.can_exit())
+            .collect();
+        let total: u32 = exits.iter().map(|portal| portal.weight).sum();
+        if total == 0 {
+            return None;
+        }
+
+        let mut roll = rng::gameplay().gen_range(0..total);
+        let exit = exits
+            .iter()
+            .find(|portal| {
+                if roll < portal.weight {
+                    true
+                } else {
+                    roll -= portal.weight;
+                    false
+                }
+            })?
+            .position;
+
+        self.cooldowns[network] = COOLDOWN;
+
+        let mut out = exit;
+        match snake.dir {
+            Direction::Up => out.y -= 1,
+            Direction::Down => out.y += 1,
+            Direction::Left => out.x -= 1,
+            Direction::Right => out.x += 1,
+        }
+        Some(out)
+    }
+
+    pub fn draw(&self) {
+        let offset = get_offset();
+        let time = get_time() as f32;
+
+        for portal in &self.portals {
+            let color = portal_color(portal);
+            let center_x = offset.x + (portal.position.x as f32 + 0.5) * CELL_SIZE;
+            let center_y = offset.y + (portal.position.y as f32 + 0.5) * CELL_SIZE;
+
+            // Portals on cooldown fade out until they're ready again
+            let ready = if self.cooldowns[portal.network] > 0.0 { 0.3 } else { 1.0 };
+            let pulse = (time * 3.0).sin() * 0.1 + 0.9;
+
+            draw_circle(center_x, center_y, CELL_SIZE * 0.5 * pulse, Color::new(color.r, color.g, color.b, 0.3 * ready));
+            draw_circle_lines(center_x, center_y, CELL_SIZE * 0.45, 2.0, Color::new(color.r, color.g, color.b, ready));
+            // Exits have a hollow middle so they can't be mistaken for a way in
+            if portal.kind != PortalKind::Exit {
+                draw_circle(center_x, center_y, CELL_SIZE * 0.15, Color::new(color.r, color.g, color.b, ready));
+            }
+        }
+    }
+
+    // Small overview of the board with lines from every way in to every way out,
+    // so it's clear where each network can send the snake
+    pub fn draw_minimap(&self, x: f32, y: f32, width: f32) {
+        if self.portals.is_empty() {
+            return;
+        }
+
+        let scale = width / GRID_WIDTH as f32;
+        let height = GRID_HEIGHT as f32 * scale;
+        draw_rectangle(x, y, width, height, Color::new(0.0, 0.0, 0.0, 0.5));
+        draw_rectangle_lines(x, y, width, height, 1.0, GRAY);
+        self.draw_links(x, y, scale);
+    }
+
+    // Portals and the links between them, over a map whose top left corner is at x, y
+    pub fn draw_links(&self, x: f32, y: f32, scale: f32) {
+        let point = |cell: Segment| {
+            vec2(x + (cell.x as f32 + 0.5) * scale, y + (cell.y as f32 + 0.5) * scale)
+        };
+
+        for from in self.portals.iter().filter(|portal| portal.kind.can_enter()) {
+            let color = NETWORK_COLORS[from.network % NETWORK_COLORS.len()];
+            for to in &self.portals {
+                if to.network != from.network || to.position == from.position || !to.kind.can_exit() {
+                    continue;
+                }
+                let start = point(from.position);
+                let end = point(to.position);
+                draw_line(start.x, start.y, end.x, end.y, 1.0, Color::new(color.r, color.g, color.b, 0.6));
+                // Arrowhead dot a little short of the exit end
+                let tip = end + (start - end).normalize_or_zero() * 4.0;
+                draw_circle(tip.x, tip.y, 1.5, color);
+            }
+        }
+
+        for portal in &self.portals {
+            let center = point(portal.position);
+            draw_circle(center.x, center.y, 2.5, portal_color(portal));
+        }
+    }
+}
+
+// One-way exits are drawn in a paler version of their network's colour
+fn portal_color(portal: &Teleporter) -> Color {
+    let color = NETWORK_COLORS[portal.network % NETWORK_COLORS.len()];
+    if portal.kind == PortalKind::Exit {
+        Color::new(
+            color.r + (1.0 - color.r) * 0.6,
+            color.g + (1.0 - color.g) * 0.6,
+            color.b + (1.0 - color.b) * 0.6,
+            color.a,
+        )
+    } else {
+        color
+    }
+}
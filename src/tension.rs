@@ -0,0 +1,225 @@
+use macroquad::prelude::*;
+use macroquad::audio::{play_sound, set_sound_volume, stop_sound, PlaySoundParams, Sound};
+use std::collections::VecDeque;
+use crate::grid::{GRID_WIDTH, GRID_HEIGHT, wrap_position};
+use crate::mixer::sfx_volume;
+use crate::snake::{Segment, Snake};
+
+// Reachable cells beyond the snake's own length before things count as tight
+const SPACE_MARGIN: usize = 15;
+// Cells the flood fill is allowed to visit each frame
+const CELLS_PER_FRAME: usize = 150;
+// How quickly the effect fades in and out, per second
+const FADE_SPEED: f32 = 1.5;
+const HEARTBEAT_RATE: f64 = 1.2;
+
+// Tracks how boxed in the snake is and drives the heartbeat, vignette, and HUD pulse
+pub struct Tension {
+    pub tense: bool,
+    pub intensity: f32,
+    frontier: VecDeque<Segment>,
+    visited: Vec<bool>,
+    reached: usize,
+    searching: bool,
+    last_head: Option<Segment>,
+    heartbeat_playing: bool,
+}
+
+impl Tension {
+    pub fn new() -> Self {
+        Self {
+            tense: false,
+            intensity: 0.0,
+            frontier: VecDeque::new(),
+            visited: vec![false; (GRID_WIDTH * GRID_HEIGHT) as usize],
+            reached: 0,
+            searching: false,
+            last_head: None,
+            heartbeat_playing: false,
+        }
+    }
+
+    pub fn update(&mut self, delta_time: f32, snake: &Snake, is_blocked: impl Fn(Segment) -> bool) {
+        // Start a fresh search every time the snake moves, keeping the old answer until it finishes
+        let head = snake.head();
+        if self.last_head != Some(head) {
+            self.last_head = Some(head);
+            self.visited.fill(false);
+            self.frontier.clear();
+            self.frontier.push_back(head);
+            self.visited[cell_index(head)] = true;
+            self.reached = 0;
+            self.searching = true;
+        }
+
+        if self.searching {
+            self.continue_search(snake, is_blocked);
+        }
+
+        let target = if self.tense { 1.0 } else { 0.0 };
+        if self.intensity < target {
+            self.intensity = (self.intensity + FADE_SPEED * delta_time).min(target);
+        } else {
+            self.intensity = (self.intensity - FADE_SPEED * delta_time).max(target);
+        }
+    }
+
+    // Flood fill from the head, spread across frames and stopping as soon as there's enough room
+    fn continue_search(&mut self, snake: &Snake, is_blocked: impl Fn(Segment) -> bool) {
+        let enough = snake.length() + SPACE_MARGIN;
+
+        for _ in 0..CELLS_PER_FRAME {
+            let cell = match self.frontier.pop_front() {
+                Some(cell) => cell,
+                None => {
+                    self.tense = self.reached < enough;
+                    self.searching = false;
+                    return;
+                }
+            };
+
+            self.reached += 1;
+            if self.reached >= enough {
+                self.tense = false;
+                self.searching = false;
+                return;
+            }
+
+            let neighbours = [
+                Segment { x: cell.x + 1, y: cell.y },
+                Segment { x: cell.x - 1, y: cell.y },
+                Segment { x: cell.x, y: cell.y + 1 },
+                Segment { x: cell.x, y: cell.y - 1 },
+            ];
+            for mut next in neighbours {
+                if snake.wrap {
+                    (next.x, next.y) = wrap_position(next.x, next.y);
+                }
+                if next.x < 0 || next.x >= GRID_WIDTH || next.y < 0 || next.y >= GRID_HEIGHT {
+                    continue;
+                }
+                let index = cell_index(next);
+                if self.visited[index] || snake.is_at(next) || is_blocked(next) {
+                    continue;
+                }
+                self.visited[index] = true;
+                self.frontier.push_back(next);
+            }
+        }
+    }
+
+    // 0..1 beat shape, two quick thumps per heartbeat
+    fn beat(&self) -> f32 {
+        let phase = (get_time() * HEARTBEAT_RATE).fract() as f32;
+        let thump = |start: f32| (1.0 - ((phase - start) / 0.12).abs()).max(0.0);
+        thump(0.05).max(thump(0.3) * 0.7)
+    }
+
+    // Blends a HUD colour toward red in time with the heartbeat
+    pub fn tint(&self, color: Color) -> Color {
+        let amount = self.intensity * (0.4 + self.beat() * 0.6);
+        Color::new(
+            color.r + (1.0 - color.r) * amount,
+            color.g * (1.0 - amount),
+            color.b * (1.0 - amount),
+            color.a,
+        )
+    }
+
+    pub fn update_audio(&mut self, heartbeat: &Sound, running: bool) {
+        let volume = if running { sfx_volume(self.intensity * 0.8) } else { 0.0 };
+
+        if volume > 0.0 && !self.heartbeat_playing {
+            play_sound(heartbeat, PlaySoundParams { looped: true, volume });
+            self.heartbeat_playing = true;
+        } else if volume <= 0.0 && self.heartbeat_playing {
+            stop_sound(heartbeat);
+            self.heartbeat_playing = false;
+        } else if self.heartbeat_playing {
+            set_sound_volume(heartbeat, volume);
+        }
+    }
+
+    pub fn draw_vignette(&self) {
+        if self.intensity <= 0.0 {
+            return;
+        }
+
+        // Stacked borders that get more transparent toward the middle of the screen
+        let strength = self.intensity * (0.5 + self.beat() * 0.5);
+        let steps = 12;
+        for i in 0..steps {
+            let inset = i as f32 * 8.0;
+            let alpha = strength * 0.25 * (1.0 - i as f32 / steps as f32);
+            draw_rectangle_lines(
+                inset,
+                inset,
+                screen_width() - inset * 2.0,
+                screen_height() - inset * 2.0,
+                16.0,
+                Color::new(0.5, 0.0, 0.0, alpha),
+            );
+        }
+    }
+}
+
+fn cell_index(cell: Segment) -> usize {
+    (cell.y * GRID_WIDTH + cell.x) as usize
+}
+
+// Builds a looping heartbeat as a 16-bit mono WAV, one beat per 1/HEARTBEAT_RATE seconds
+pub fn heartbeat_wav() -> Vec<u8> {
+    let sample_rate: u32 = 22050;
+    let length = (sample_rate as f64 / HEARTBEAT_RATE) as u32;
+
+    let mut samples = Vec::with_capacity(length as usize);
+    for i in 0..length {
+        let t = i as f32 / sample_rate as f32;
+        let phase = t * HEARTBEAT_RATE as f32;
+        // Low sine thumps that decay quickly, matching the visual beat
+        let thump = |start: f32, loudness: f32| {
+            let since = (phase - start) / HEARTBEAT_RATE as f32;
+            if since < 0.0 {
+                0.0
+            } else {
+                (since * 55.0 * std::f32::consts::TAU).sin() * (-since * 18.0).exp() * loudness
+            }
+        };
+        let value = thump(0.05, 1.0) + thump(0.3, 0.7);
+        samples.push((value.clamp(-1.0, 1.0) * i16::MAX as f32 * 0.8) as i16);
+    }
+
+    encode_wav(sample_rate, &samples)
+}
+
+// Wraps mono 16-bit samples in a WAV header so macroquad can load them
+pub fn encode_wav(sample_rate: u32, samples: &[i16]) -> Vec<u8> {
+    wav_with_channels(sample_rate, 1, samples)
+}
+
+// Same for stereo, with the left and right samples interleaved
+pub fn encode_stereo_wav(sample_rate: u32, samples: &[i16]) -> Vec<u8> {
+    wav_with_channels(sample_rate, 2, samples)
+}
+
+fn wav_with_channels(sample_rate: u32, channels: u16, samples: &[i16]) -> Vec<u8> {
+    let data_size = samples.len() as u32 * 2;
+    let block_align = channels * 2;
+    let mut wav = Vec::with_capacity(44 + data_size as usize);
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_size).to_le_bytes());
+    wav.extend_from_slice(b"WAVEfmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    wav.extend_from_slice(&channels.to_le_bytes());
+    wav.extend_from_slice(&sample_rate.to_le_bytes());
+    wav.extend_from_slice(&(sample_rate * block_align as u32).to_le_bytes());
+    wav.extend_from_slice(&block_align.to_le_bytes());
+    wav.extend_from_slice(&16u16.to_le_bytes());
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_size.to_le_bytes());
+    for sample in samples {
+        wav.extend_from_slice(&sample.to_le_bytes());
+    }
+    wav
+}
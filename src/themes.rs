@@ -1,4 +1,6 @@
 use macroquad::prelude::*;
+use lazy_static::lazy_static;
+use std::sync::Mutex;
 
 pub struct Theme {
     pub snake_head: Color,
@@ -9,7 +11,79 @@ pub struct Theme {
     pub ui_text: Color,
 }
 
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Palette {
+    Standard,
+    Colorblind,
+    HighContrast,
+}
+
+impl Palette {
+    pub fn next(&self) -> Palette {
+        match self {
+            Palette::Standard => Palette::Colorblind,
+            Palette::Colorblind => Palette::HighContrast,
+            Palette::HighContrast => Palette::Standard,
+        }
+    }
+
+    pub fn save_key(&self) -> &'static str {
+        match self {
+            Palette::Standard => "standard",
+            Palette::Colorblind => "colorblind",
+            Palette::HighContrast => "high_contrast",
+        }
+    }
+
+    pub fn from_save_key(key: &str) -> Palette {
+        match key {
+            "colorblind" => Palette::Colorblind,
+            "high_contrast" => Palette::HighContrast,
+            _ => Palette::Standard,
+        }
+    }
+}
+
+lazy_static! {
+    // Applies to every screen that asks for a theme
+    static ref PALETTE: Mutex<Palette> = Mutex::new(Palette::Standard);
+}
+
+pub fn set_palette(palette: Palette) {
+    *PALETTE.lock().unwrap() = palette;
+}
+
+// Food gets a shape of its own too, so it never relies on color alone
+pub fn shaped_food() -> bool {
+    *PALETTE.lock().unwrap() != Palette::Standard
+}
+
 pub fn get_theme(level: usize) -> Theme {
+    let theme = level_theme(level);
+    match *PALETTE.lock().unwrap() {
+        Palette::Standard => theme,
+        // Blues for the snake against orange and yellow food, from the Okabe-Ito set, which
+        // stay apart for red-green and blue-yellow colorblindness alike. The backgrounds
+        // are dark enough to keep.
+        Palette::Colorblind => Theme {
+            snake_head: Color::new(0.34, 0.71, 0.91, 1.0),
+            snake_body: Color::new(0.0, 0.45, 0.70, 1.0),
+            food: if level.is_multiple_of(2) { Color::new(0.90, 0.60, 0.0, 1.0) } else { Color::new(0.94, 0.89, 0.26, 1.0) },
+            ui_text: Color::new(0.94, 0.94, 0.94, 1.0),
+            ..theme
+        },
+        Palette::HighContrast => Theme {
+            snake_head: WHITE,
+            snake_body: Color::new(0.75, 0.75, 0.75, 1.0),
+            food: Color::new(1.0, 1.0, 0.0, 1.0),
+            grid: Color::new(0.3, 0.3, 0.3, 1.0),
+            background: BLACK,
+            ui_text: WHITE,
+        },
+    }
+}
+
+fn level_theme(level: usize) -> Theme {
     match level % 10 {
         1 => Theme {
             // Classic green snake theme
@@ -0,0 +1,280 @@
+use macroquad::prelude::*;
+use ::rand::rngs::StdRng;
+use ::rand::{Rng, SeedableRng};
+use std::collections::BTreeMap;
+use crate::grid::{GRID_HEIGHT, GRID_WIDTH};
+use crate::hud::hud_scale;
+use crate::level::BOSS_LEVEL;
+use crate::objectives::{objectives_for, Objective};
+use crate::snake::Segment;
+
+// Seconds a level should take a good player, before its own objectives are considered
+const BASE_PAR: f32 = 20.0;
+// Food cells laid out in advance for each level, far more than a level needs
+const SEQUENCE_LENGTH: usize = 64;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Medal {
+    Gold,
+    Silver,
+    Bronze,
+}
+
+impl Medal {
+    fn name(&self) -> String {
+        match self {
+            Medal::Gold => t!("medal-gold"),
+            Medal::Silver => t!("medal-silver"),
+            Medal::Bronze => t!("medal-bronze"),
+        }
+    }
+
+    fn color(&self) -> Color {
+        match self {
+            Medal::Gold => GOLD,
+            Medal::Silver => LIGHTGRAY,
+            Medal::Bronze => Color::new(0.8, 0.5, 0.2, 1.0),
+        }
+    }
+
+    // Slowest time that still earns this medal
+    fn threshold(&self, level: usize) -> f32 {
+        let par = par_time(level);
+        match self {
+            Medal::Gold => par,
+            Medal::Silver => par * 1.5,
+            Medal::Bronze => par * 2.0,
+        }
+    }
+}
+
+const MEDALS: [Medal; 3] = [Medal::Gold, Medal::Silver, Medal::Bronze];
+
+// Gold medal time, stretched on levels that make you wait
+fn par_time(level: usize) -> f32 {
+    let mut par = BASE_PAR;
+    for objective in objectives_for(level, 0) {
+        match objective {
+            Objective::SurviveSeconds(seconds) => par = par.max(seconds + 5.0),
+            Objective::ReachLength(_) | Objective::EatGoldenFoods(_) => par += 10.0,
+            Objective::EatFood(_) => {}
+        }
+    }
+    par
+}
+
+fn medal_for(level: usize, seconds: f32) -> Option<Medal> {
+    MEDALS.into_iter().find(|medal| seconds <= medal.threshold(level))
+}
+
+// The same food cells in the same order every time a level is played against the clock
+pub fn food_sequence(level: usize) -> Vec<Segment> {
+    let mut rng = StdRng::seed_from_u64(level as u64);
+    (0..SEQUENCE_LENGTH)
+        .map(|_| Segment {
+            x: rng.gen_range(0..GRID_WIDTH),
+            y: rng.gen_range(0..GRID_HEIGHT),
+        })
+        .collect()
+}
+
+// Fastest clear of each level in time attack
+pub struct BestTimes {
+    times: BTreeMap<usize, f32>,
+}
+
+impl BestTimes {
+    pub fn new() -> Self {
+        Self { times: BTreeMap::new() }
+    }
+
+    pub fn get(&self, level: usize) -> Option<f32> {
+        self.times.get(&level).copied()
+    }
+
+    // Every level with a recorded time, lowest first
+    pub fn iter(&self) -> impl Iterator<Item = (usize, f32)> + '_ {
+        self.times.iter().map(|(level, seconds)| (*level, *seconds))
+    }
+
+    fn record(&mut self, level: usize, seconds: f32) {
+        if self.get(level).is_none_or(|best| seconds < best) {
+            self.times.insert(level, seconds);
+        }
+    }
+
+    pub fn write_lines(&self, out: &mut String) {
+        for (level, seconds) in &self.times {
+            out.push_str(&format!("best_time={},{:.2}\n", level, seconds));
+        }
+    }
+
+    // `value` is `level,seconds`
+    pub fn read_line(&mut self, value: &str) {
+        let Some((level, seconds)) = value.split_once(',') else {
+            return;
+        };
+        if let (Ok(level), Ok(seconds)) = (level.parse(), seconds.parse()) {
+            self.times.insert(level, seconds);
+        }
+    }
+}
+
+struct TimeAttackResult {
+    level: usize,
+    time: Option<f32>, // None if the snake crashed
+    previous_best: Option<f32>,
+}
+
+// One level against the clock: a level picker on the title screen, the timer
+// during the run, and a results screen after it
+pub struct TimeAttack {
+    pub showing_menu: bool,
+    pub active: bool, // The current run is a time attack
+    level: usize,
+    max_level: usize,
+    result: Option<TimeAttackResult>,
+}
+
+impl TimeAttack {
+    pub fn new() -> Self {
+        Self {
+            showing_menu: false,
+            active: false,
+            level: 1,
+            max_level: 1,
+            result: None,
+        }
+    }
+
+    // Any level reached in the campaign can be played, apart from the boss fight
+    pub fn open_menu(&mut self, best_level: usize) {
+        self.showing_menu = true;
+        self.max_level = best_level.max(1);
+        self.level = self.level.min(self.max_level);
+        if self.level == BOSS_LEVEL {
+            self.level -= 1;
+        }
+    }
+
+    // Returns the level to start once one is picked
+    pub fn update_menu(&mut self) -> Option<usize> {
+        if is_key_pressed(KeyCode::Escape) {
+            self.showing_menu = false;
+        } else if is_key_pressed(KeyCode::Enter) || is_key_pressed(KeyCode::Space) {
+            self.showing_menu = false;
+            self.active = true;
+            return Some(self.level);
+        } else if is_key_pressed(KeyCode::Left) && self.level > 1 {
+            self.level -= 1;
+            if self.level == BOSS_LEVEL {
+                self.level -= 1;
+            }
+        } else if is_key_pressed(KeyCode::Right) && self.level < self.max_level {
+            let next = if self.level + 1 == BOSS_LEVEL { self.level + 2 } else { self.level + 1 };
+            if next <= self.max_level {
+                self.level = next;
+            }
+        }
+        None
+    }
+
+    // Called when the level is cleared (with its time) or the snake crashes (with None)
+    pub fn finish(&mut self, time: Option<f32>, best_times: &mut BestTimes) {
+        let previous_best = best_times.get(self.level);
+        if let Some(time) = time {
+            best_times.record(self.level, time);
+        }
+        self.result = Some(TimeAttackResult {
+            level: self.level,
+            time,
+            previous_best,
+        });
+        self.active = false;
+    }
+
+    pub fn stop(&mut self) {
+        self.active = false;
+    }
+
+    pub fn has_result(&self) -> bool {
+        self.result.is_some()
+    }
+
+    pub fn update_result(&mut self) {
+        if is_key_pressed(KeyCode::Space) || is_key_pressed(KeyCode::Enter) || is_key_pressed(KeyCode::Escape) {
+            self.result = None;
+        }
+    }
+
+    pub fn draw_menu(&self, best_times: &BestTimes) {
+        let center_y = screen_height() / 2.0;
+        draw_panel();
+        draw_centered(&t!("time-attack-title"), center_y - 120.0, 48.0, SKYBLUE);
+        draw_centered(&t!("time-attack-level", level = self.level), center_y - 60.0, 36.0, WHITE);
+
+        let medals: Vec<String> = MEDALS
+            .iter()
+            .map(|medal| format!("{} {:.1}s", medal.name(), medal.threshold(self.level)))
+            .collect();
+        draw_centered(&medals.join("   "), center_y - 15.0, 22.0, LIGHTGRAY);
+
+        let best = match best_times.get(self.level) {
+            Some(best) => t!("time-attack-best", time = format!("{:.2}", best)),
+            None => t!("time-attack-no-best"),
+        };
+        draw_centered(&best, center_y + 25.0, 24.0, YELLOW);
+        draw_centered(&t!("time-attack-menu-help"), center_y + 100.0, 20.0, GRAY);
+    }
+
+    pub fn draw_result(&self) {
+        let Some(result) = &self.result else {
+            return;
+        };
+        let center_y = screen_height() / 2.0;
+        draw_panel();
+        draw_centered(&t!("time-attack-result-title", level = result.level), center_y - 120.0, 40.0, SKYBLUE);
+
+        match result.time {
+            Some(time) => {
+                draw_centered(&t!("time-attack-time", time = format!("{:.2}", time)), center_y - 60.0, 36.0, WHITE);
+                let (medal_text, medal_color) = match medal_for(result.level, time) {
+                    Some(medal) => (medal.name(), medal.color()),
+                    None => (t!("medal-none"), GRAY),
+                };
+                draw_centered(&medal_text, center_y - 15.0, 32.0, medal_color);
+
+                // Negative is faster than the old best
+                let (comparison, color) = match result.previous_best {
+                    Some(best) if time < best => (t!("time-attack-new-best", delta = format!("{:.2}", time - best)), GREEN),
+                    Some(best) => (
+                        t!("time-attack-versus-best", best = format!("{:.2}", best), delta = format!("+{:.2}", time - best)),
+                        ORANGE,
+                    ),
+                    None => (t!("time-attack-first-time"), GREEN),
+                };
+                draw_centered(&comparison, center_y + 30.0, 24.0, color);
+            }
+            None => draw_centered(&t!("time-attack-crashed"), center_y - 40.0, 36.0, RED),
+        }
+        draw_centered(&t!("time-attack-continue"), center_y + 100.0, 20.0, GRAY);
+    }
+
+    // Clock in the corner during a run
+    pub fn draw_timer(&self, level_time: f32, color: Color) {
+        if !self.active {
+            return;
+        }
+        let hud = hud_scale();
+        draw_text(&t!("hud-time", time = format!("{:.1}", level_time)), 20.0 * hud, 104.0 * hud, 24.0 * hud, color);
+    }
+}
+
+fn draw_panel() {
+    draw_rectangle(0.0, 0.0, screen_width(), screen_height(), Color::new(0.0, 0.0, 0.0, 0.85));
+}
+
+fn draw_centered(text: &str, y: f32, size: f32, color: Color) {
+    let width = measure_text(text, None, size as u16, 1.0).width;
+    draw_text(text, (screen_width() - width) / 2.0, y, size, color);
+}
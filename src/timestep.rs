@@ -0,0 +1,70 @@
+use lazy_static::lazy_static;
+use std::sync::Mutex;
+
+// Gameplay runs in fixed-size ticks so it plays the same at any frame rate.
+// Drawing still happens once per frame.
+const DEFAULT_TICK_RATE: f32 = 120.0;
+const TICK_RATE_ENV: &str = "VYPERTRON_TICK_RATE";
+// Longest frame we try to catch up on, so a stall doesn't turn into a burst of ticks
+const MAX_FRAME_TIME: f32 = 0.25;
+
+lazy_static! {
+    // How fast game time passes against real time, below 1.0 for slow motion
+    static ref TIME_SCALE: Mutex<f32> = Mutex::new(1.0);
+}
+
+pub fn set_time_scale(scale: f32) {
+    *TIME_SCALE.lock().unwrap() = scale;
+}
+
+pub fn time_scale() -> f32 {
+    *TIME_SCALE.lock().unwrap()
+}
+
+pub struct FixedTimestep {
+    pub tick: f32, // Seconds per tick
+    accumulator: f32,
+}
+
+impl FixedTimestep {
+    // Ticks per second can be overridden with VYPERTRON_TICK_RATE
+    pub fn new() -> Self {
+        let rate = match std::env::var(TICK_RATE_ENV) {
+            Ok(value) => match value.parse::<f32>() {
+                Ok(rate) if rate >= 10.0 => rate,
+                _ => {
+                    println!("Warning: Ignoring {}={}, using {} ticks per second", TICK_RATE_ENV, value, DEFAULT_TICK_RATE);
+                    DEFAULT_TICK_RATE
+                }
+            },
+            Err(_) => DEFAULT_TICK_RATE,
+        };
+
+        Self {
+            tick: 1.0 / rate,
+            accumulator: 0.0,
+        }
+    }
+
+    // Banks a frame's worth of time, scaled by the time scale. The tick itself stays
+    // the same size, so slow motion means fewer ticks rather than coarser ones.
+    pub fn advance(&mut self, frame_time: f32) {
+        self.accumulator += frame_time.min(MAX_FRAME_TIME) * time_scale();
+    }
+
+    // True while there's a whole tick banked, using it up
+    pub fn step(&mut self) -> bool {
+        if self.accumulator >= self.tick {
+            self.accumulator -= self.tick;
+            true
+        } else {
+            false
+        }
+    }
+
+    // Lets go of anything banked past the next tick, while the game waits on something
+    // else, so it doesn't rush through the backlog afterwards
+    pub fn hold(&mut self) {
+        self.accumulator = self.accumulator.min(self.tick);
+    }
+}
@@ -0,0 +1,44 @@
+use macroquad::prelude::*;
+
+const TOAST_DURATION: f32 = 4.0;
+
+// Short message that slides in at the bottom of the screen and fades out
+pub struct Toast {
+    pub message: String,
+    pub timer: f32,
+}
+
+impl Toast {
+    pub fn new() -> Self {
+        Self {
+            message: String::new(),
+            timer: 0.0,
+        }
+    }
+
+    pub fn show(&mut self, message: &str) {
+        self.message = message.to_string();
+        self.timer = TOAST_DURATION;
+    }
+
+    pub fn update(&mut self, delta_time: f32) {
+        self.timer = (self.timer - delta_time).max(0.0);
+    }
+
+    pub fn draw(&self) {
+        if self.timer <= 0.0 {
+            return;
+        }
+
+        // Fade out over the last second
+        let alpha = self.timer.min(1.0);
+        let text_width = measure_text(&self.message, None, 24, 1.0).width;
+        let box_width = text_width + 40.0;
+        let box_x = (screen_width() - box_width) / 2.0;
+        let box_y = screen_height() - 90.0;
+
+        draw_rectangle(box_x, box_y, box_width, 40.0, Color::new(0.1, 0.1, 0.1, 0.85 * alpha));
+        draw_rectangle_lines(box_x, box_y, box_width, 40.0, 2.0, Color::new(1.0, 0.8, 0.2, alpha));
+        draw_text(&self.message, box_x + 20.0, box_y + 27.0, 24.0, Color::new(1.0, 1.0, 1.0, alpha));
+    }
+}
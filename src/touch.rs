@@ -0,0 +1,190 @@
+use macroquad::prelude::*;
+use lazy_static::lazy_static;
+use std::sync::Mutex;
+use crate::snake::Direction;
+
+// Longest a touch can last, and furthest it can move, and still count as a tap
+const TAP_TIME: f64 = 0.25;
+const TAP_DISTANCE: f32 = 15.0;
+const BUTTON_SIZE: f32 = 64.0;
+const BUTTON_MARGIN: f32 = 20.0;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum SwipeSensitivity {
+    Low,
+    Medium,
+    High,
+}
+
+impl SwipeSensitivity {
+    pub fn next(&self) -> SwipeSensitivity {
+        match self {
+            SwipeSensitivity::Low => SwipeSensitivity::Medium,
+            SwipeSensitivity::Medium => SwipeSensitivity::High,
+            SwipeSensitivity::High => SwipeSensitivity::Low,
+        }
+    }
+
+    // Pixels a finger has to travel before it counts as a swipe
+    fn min_distance(&self) -> f32 {
+        match self {
+            SwipeSensitivity::Low => 80.0,
+            SwipeSensitivity::Medium => 50.0,
+            SwipeSensitivity::High => 30.0,
+        }
+    }
+
+    pub fn save_key(&self) -> &'static str {
+        match self {
+            SwipeSensitivity::Low => "low",
+            SwipeSensitivity::Medium => "medium",
+            SwipeSensitivity::High => "high",
+        }
+    }
+
+    pub fn from_save_key(key: &str) -> SwipeSensitivity {
+        match key {
+            "low" => SwipeSensitivity::Low,
+            "high" => SwipeSensitivity::High,
+            _ => SwipeSensitivity::Medium,
+        }
+    }
+}
+
+lazy_static! {
+    // Where the screen was tapped this frame, shared so any screen can treat a tap like SPACE
+    static ref TAP: Mutex<Option<Vec2>> = Mutex::new(None);
+}
+
+pub fn tapped() -> bool {
+    TAP.lock().unwrap().is_some()
+}
+
+pub fn tap_position() -> Option<Vec2> {
+    *TAP.lock().unwrap()
+}
+
+// Swipes and on-screen buttons for phones and tablets
+pub struct TouchInput {
+    pub active: bool, // A touch has been seen, so the on-screen buttons are worth showing
+    swipe_start: Option<(u64, Vec2, f64)>, // Touch id, where it started, when
+    swiped: bool, // The current touch has already turned the snake, so it isn't a tap
+}
+
+impl TouchInput {
+    pub fn new() -> Self {
+        Self {
+            active: false,
+            swipe_start: None,
+            swiped: false,
+        }
+    }
+
+    // Call once per frame. Returns a direction from a swipe or one of the on-screen
+    // buttons, which only respond while `buttons_shown` is set.
+    pub fn update(&mut self, sensitivity: SwipeSensitivity, buttons_shown: bool) -> Option<Direction> {
+        let mut tap = None;
+        let mut dir = None;
+
+        for touch in touches() {
+            self.active = true;
+            match touch.phase {
+                TouchPhase::Started => {
+                    if let Some(button) = button_at(touch.position).filter(|_| buttons_shown) {
+                        dir = Some(button);
+                    } else if self.swipe_start.is_none() {
+                        self.swipe_start = Some((touch.id, touch.position, get_time()));
+                        self.swiped = false;
+                    }
+                }
+                TouchPhase::Moved | TouchPhase::Stationary => {
+                    if let Some((id, start, time)) = self.swipe_start {
+                        let delta = touch.position - start;
+                        if id == touch.id && delta.length() >= sensitivity.min_distance() {
+                            dir = Some(direction_of(delta));
+                            self.swiped = true;
+                            // Measure the next swipe from here so one finger can keep steering
+                            self.swipe_start = Some((id, touch.position, time));
+                        }
+                    }
+                }
+                TouchPhase::Ended | TouchPhase::Cancelled => {
+                    if let Some((id, start, time)) = self.swipe_start {
+                        if id == touch.id {
+                            let short = get_time() - time <= TAP_TIME;
+                            let still = (touch.position - start).length() < TAP_DISTANCE;
+                            if touch.phase == TouchPhase::Ended && !self.swiped && short && still {
+                                tap = Some(touch.position);
+                            }
+                            self.swipe_start = None;
+                        }
+                    }
+                }
+            }
+        }
+
+        *TAP.lock().unwrap() = tap;
+        dir
+    }
+
+    // Directional pad in the bottom right corner
+    pub fn draw_buttons(&self) {
+        if !self.active {
+            return;
+        }
+
+        for (rect, dir) in button_rects() {
+            draw_rectangle(rect.x, rect.y, rect.w, rect.h, Color::new(1.0, 1.0, 1.0, 0.15));
+            draw_rectangle_lines(rect.x, rect.y, rect.w, rect.h, 2.0, Color::new(1.0, 1.0, 1.0, 0.4));
+
+            let center = rect.center();
+            let (dx, dy) = dir.delta();
+            let forward = vec2(dx as f32, dy as f32) * BUTTON_SIZE * 0.25;
+            let side = vec2(-forward.y, forward.x);
+            draw_triangle(
+                center + forward,
+                center - forward + side,
+                center - forward - side,
+                Color::new(1.0, 1.0, 1.0, 0.6),
+            );
+        }
+    }
+}
+
+fn button_rects() -> [(Rect, Direction); 4] {
+    let center = vec2(
+        screen_width() - BUTTON_MARGIN - BUTTON_SIZE * 1.5,
+        screen_height() - BUTTON_MARGIN - BUTTON_SIZE * 1.5,
+    );
+    let at = |x: f32, y: f32| {
+        Rect::new(
+            center.x + (x - 0.5) * BUTTON_SIZE,
+            center.y + (y - 0.5) * BUTTON_SIZE,
+            BUTTON_SIZE,
+            BUTTON_SIZE,
+        )
+    };
+    [
+        (at(0.0, -1.0), Direction::Up),
+        (at(0.0, 1.0), Direction::Down),
+        (at(-1.0, 0.0), Direction::Left),
+        (at(1.0, 0.0), Direction::Right),
+    ]
+}
+
+fn button_at(position: Vec2) -> Option<Direction> {
+    button_rects()
+        .iter()
+        .find(|(rect, _)| rect.contains(position))
+        .map(|(_, dir)| *dir)
+}
+
+fn direction_of(delta: Vec2) -> Direction {
+    if delta.x.abs() > delta.y.abs() {
+        if delta.x > 0.0 { Direction::Right } else { Direction::Left }
+    } else if delta.y > 0.0 {
+        Direction::Down
+    } else {
+        Direction::Up
+    }
+}
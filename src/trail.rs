@@ -0,0 +1,83 @@
+use macroquad::prelude::*;
+use std::collections::VecDeque;
+use crate::grid::{CELL_SIZE, get_offset};
+use crate::level::{special_mechanics, SpecialMechanic};
+use crate::snake::{Segment, Snake};
+use crate::themes::Theme;
+
+// Seconds a patch of ice lasts after the tail leaves it
+const TRAIL_LIFETIME: f32 = 4.0;
+const TRAIL_MULTIPLIER: f32 = 0.6;
+
+// On ice levels the snake leaves frost behind its tail, which slows it down if it
+// doubles back over it
+pub struct IceTrail {
+    cells: VecDeque<(Segment, f32)>, // Cell and how long it has left, oldest first
+    last_tail: Option<Segment>,
+    active: bool,
+}
+
+impl IceTrail {
+    pub fn new() -> Self {
+        Self {
+            cells: VecDeque::new(),
+            last_tail: None,
+            active: false,
+        }
+    }
+
+    pub fn setup(&mut self, level: usize) {
+        self.cells.clear();
+        self.last_tail = None;
+        self.active = special_mechanics(level).contains(&SpecialMechanic::Trail);
+    }
+
+    pub fn update(&mut self, delta_time: f32, snake: &Snake) {
+        if !self.active {
+            return;
+        }
+
+        for (_, life) in &mut self.cells {
+            *life -= delta_time;
+        }
+        while self.cells.front().is_some_and(|(_, life)| *life <= 0.0) {
+            self.cells.pop_front();
+        }
+
+        // Frost the cell the tail just moved off, unless the head has already taken it
+        let tail = snake.body.back().copied();
+        if let Some(left) = self.last_tail.filter(|last| Some(*last) != tail) {
+            if !snake.is_at(left) {
+                self.cells.retain(|(cell, _)| *cell != left);
+                self.cells.push_back((left, TRAIL_LIFETIME));
+            }
+        }
+        self.last_tail = tail;
+    }
+
+    pub fn is_at(&self, position: Segment) -> bool {
+        self.cells.iter().any(|(cell, _)| *cell == position)
+    }
+
+    pub fn multiplier_at(&self, position: Segment) -> f32 {
+        if self.is_at(position) { TRAIL_MULTIPLIER } else { 1.0 }
+    }
+
+    pub fn draw(&self, theme: &Theme) {
+        let offset = get_offset();
+
+        for (cell, life) in &self.cells {
+            // Frosted version of the snake's own color, melting away as it ages
+            let fade = (life / TRAIL_LIFETIME).clamp(0.0, 1.0);
+            let color = Color::new(
+                (theme.snake_body.r + 1.0) / 2.0,
+                (theme.snake_body.g + 1.0) / 2.0,
+                (theme.snake_body.b + 1.0) / 2.0,
+                0.45 * fade,
+            );
+            let x = offset.x + cell.x as f32 * CELL_SIZE;
+            let y = offset.y + cell.y as f32 * CELL_SIZE;
+            draw_rectangle(x + 2.0, y + 2.0, CELL_SIZE - 4.0, CELL_SIZE - 4.0, color);
+        }
+    }
+}
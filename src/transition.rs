@@ -0,0 +1,84 @@
+use macroquad::prelude::*;
+use crate::level::special_mechanics;
+use crate::motion::reduced_motion;
+use crate::themes::Theme;
+
+// Seconds the card stays up before play picks up again
+const INTRO_TIME: f32 = 2.0;
+// Seconds the heading takes to slide in, and before SPACE can skip the card
+const SLIDE_TIME: f32 = 0.35;
+
+// Short card between levels naming the level ahead and what's new on it,
+// shown once the level breakdown or the credits are closed
+pub struct LevelIntro {
+    pub showing: bool,
+    level: usize,
+    mechanics: Vec<String>,
+    timer: f32,
+}
+
+impl LevelIntro {
+    pub fn new() -> Self {
+        Self {
+            showing: false,
+            level: 1,
+            mechanics: Vec::new(),
+            timer: 0.0,
+        }
+    }
+
+    pub fn start(&mut self, level: usize) {
+        self.showing = true;
+        self.level = level;
+        self.mechanics = special_mechanics(level)
+            .iter()
+            .map(|mechanic| t!(&format!("mechanic-{}", mechanic.name())))
+            .collect();
+        self.timer = 0.0;
+    }
+
+    pub fn update(&mut self, delta_time: f32) {
+        if !self.showing {
+            return;
+        }
+        self.timer += delta_time;
+        let skipped = self.timer >= SLIDE_TIME && (is_key_pressed(KeyCode::Space) || is_key_pressed(KeyCode::Enter));
+        if skipped || self.timer >= INTRO_TIME {
+            self.showing = false;
+        }
+    }
+
+    pub fn draw(&self, theme: &Theme) {
+        if !self.showing {
+            return;
+        }
+
+        let center_y = screen_height() / 2.0;
+        draw_rectangle(0.0, center_y - 70.0, screen_width(), 130.0, Color::new(0.0, 0.0, 0.0, 0.75));
+
+        // Heading slides in from the left edge and eases to a stop in the middle
+        let heading = t!("hud-level", level = self.level);
+        let width = measure_text(&heading, None, 64, 1.0).width;
+        let rest_x = (screen_width() - width) / 2.0;
+        let x = if reduced_motion() {
+            rest_x
+        } else {
+            let progress = (self.timer / SLIDE_TIME).min(1.0);
+            let eased = 1.0 - (1.0 - progress).powi(3);
+            -width + (rest_x + width) * eased
+        };
+        draw_text(&heading, x, center_y, 64.0, theme.snake_head);
+
+        let details = if self.mechanics.is_empty() {
+            t!("intro-get-ready")
+        } else {
+            self.mechanics.join("  -  ")
+        };
+        draw_centered(&details, center_y + 40.0, 24.0, theme.ui_text);
+    }
+}
+
+fn draw_centered(text: &str, y: f32, size: f32, color: Color) {
+    let width = measure_text(text, None, size as u16, 1.0).width;
+    draw_text(text, (screen_width() - width) / 2.0, y, size, color);
+}
@@ -0,0 +1,95 @@
+use macroquad::prelude::*;
+use crate::controls::{ControlScheme, KeyPreset};
+use crate::snake::{Direction, Snake};
+
+// How long the last tip stays up, since there's nothing for the player to do to clear it
+const LAST_TIP_TIME: f32 = 4.0;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Tip {
+    Steer,
+    EatFood,
+    AvoidTail,
+}
+
+// Tips on level 1 of the first run, each one cleared by doing what it says or by ENTER
+pub struct Tutorial {
+    tip: Option<Tip>,
+    shown_for: f32,
+    start_dir: Direction,
+}
+
+impl Tutorial {
+    pub fn new() -> Self {
+        Self {
+            tip: None,
+            shown_for: 0.0,
+            start_dir: Direction::Right,
+        }
+    }
+
+    pub fn start(&mut self, snake: &Snake) {
+        self.tip = Some(Tip::Steer);
+        self.shown_for = 0.0;
+        self.start_dir = snake.dir;
+    }
+
+    pub fn stop(&mut self) {
+        self.tip = None;
+    }
+
+    // Returns true once the last tip is cleared, so the caller can record it
+    pub fn update(&mut self, delta_time: f32, snake: &Snake, foods_eaten: u32) -> bool {
+        let Some(tip) = self.tip else {
+            return false;
+        };
+        self.shown_for += delta_time;
+
+        let done = is_key_pressed(KeyCode::Enter)
+            || match tip {
+                Tip::Steer => snake.dir != self.start_dir,
+                Tip::EatFood => foods_eaten > 0,
+                Tip::AvoidTail => self.shown_for >= LAST_TIP_TIME,
+            };
+        if !done {
+            return false;
+        }
+
+        self.shown_for = 0.0;
+        self.tip = match tip {
+            Tip::Steer => Some(Tip::EatFood),
+            Tip::EatFood => Some(Tip::AvoidTail),
+            Tip::AvoidTail => None,
+        };
+        self.tip.is_none()
+    }
+
+    pub fn draw(&self, controls: ControlScheme) {
+        let Some(tip) = self.tip else {
+            return;
+        };
+        let text = match (tip, controls) {
+            (Tip::Steer, ControlScheme::Keys(KeyPreset::ArrowsAndWasd)) => t!("tutorial-steer-arrows-wasd"),
+            (Tip::Steer, ControlScheme::Keys(KeyPreset::Arrows)) => t!("tutorial-steer-arrows"),
+            (Tip::Steer, ControlScheme::Keys(KeyPreset::Wasd)) => t!("tutorial-steer-wasd"),
+            (Tip::Steer, ControlScheme::Keys(KeyPreset::Vim)) => t!("tutorial-steer-vim"),
+            (Tip::Steer, ControlScheme::OneButton { .. }) => t!("tutorial-steer-one-button"),
+            (Tip::EatFood, _) => t!("tutorial-eat"),
+            (Tip::AvoidTail, _) => t!("tutorial-tail"),
+        };
+        let dismiss = t!("tutorial-dismiss");
+
+        let width = measure_text(&text, None, 28, 1.0).width.max(measure_text(&dismiss, None, 18, 1.0).width) + 40.0;
+        let x = (screen_width() - width) / 2.0;
+        let y = screen_height() - 130.0;
+        draw_rectangle(x, y, width, 66.0, Color::new(0.05, 0.1, 0.05, 0.9));
+        draw_rectangle_lines(x, y, width, 66.0, 2.0, GREEN);
+        draw_centered(&text, y + 32.0, 28.0, WHITE);
+        draw_centered(&dismiss, y + 54.0, 18.0, LIGHTGRAY);
+    }
+}
+
+fn draw_centered(text: &str, y: f32, size: f32, color: Color) {
+    let width = measure_text(text, None, size as u16, 1.0).width;
+    draw_text(text, (screen_width() - width) / 2.0, y, size, color);
+}
@@ -0,0 +1,682 @@
+use macroquad::prelude::*;
+use ::rand::rngs::StdRng;
+use ::rand::seq::SliceRandom;
+use ::rand::{Rng, SeedableRng};
+use std::collections::{HashMap, VecDeque};
+use std::net::{SocketAddr, UdpSocket};
+use crate::grid::{draw_grid, get_offset, CELL_SIZE, GRID_HEIGHT, GRID_WIDTH};
+use crate::input::{KeyboardInput, PlayerCommand};
+use crate::snake::{Direction, Segment, Snake};
+use crate::controls::{ControlScheme, KeyPreset};
+use crate::timestep::FixedTimestep;
+
+const DEFAULT_PORT: u16 = 7878;
+// Ticks between state hash comparisons
+const HASH_INTERVAL: u32 = 60;
+// Input delay is picked in milliseconds and turned into ticks when the match starts
+const MAX_INPUT_DELAY_MS: u32 = 300;
+const INPUT_DELAY_STEP_MS: u32 = 10;
+// Most inputs resent in a single packet
+const MAX_INPUTS_PER_PACKET: usize = 128;
+// Presses waiting for a tick of their own, like the snake's own input buffer
+const MAX_PENDING: usize = 3;
+// Seconds without a packet before the opponent counts as gone
+const PEER_TIMEOUT: f64 = 5.0;
+const START_LENGTH: usize = 3;
+const PLAYER_COLORS: [Color; 2] = [GREEN, SKYBLUE];
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum VersusResult {
+    Winner(usize),
+    Draw,
+}
+
+// Two snakes on one board, stepped one fixed tick at a time from both players' commands.
+// They're the same snakes as the solo game, so both play by the same rules.
+pub struct VersusSim {
+    pub snakes: [Snake; 2],
+    pub food: Segment,
+    rng: StdRng, // Seeded by the host so both peers place food in the same spots
+    tick_length: f32, // Seconds per tick, the host's so both peers step the same amount
+    pub tick: u32,
+    pub result: Option<VersusResult>,
+}
+
+impl VersusSim {
+    pub fn new(seed: u64, tick_length: f32) -> Self {
+        let y = GRID_HEIGHT / 2;
+        let mut snakes = [Snake::new(), Snake::new()];
+        snakes[0].reset_at(Segment { x: 10, y }, Direction::Right);
+        snakes[1].reset_at(Segment { x: GRID_WIDTH - 11, y }, Direction::Left);
+        for snake in &mut snakes {
+            snake.grow_tail = START_LENGTH - 1;
+        }
+
+        let mut sim = Self {
+            snakes,
+            food: Segment { x: 0, y: 0 },
+            rng: StdRng::seed_from_u64(seed),
+            tick_length,
+            tick: 0,
+            result: None,
+        };
+        sim.place_food();
+        sim
+    }
+
+    fn place_food(&mut self) {
+        let free: Vec<Segment> = (0..GRID_HEIGHT)
+            .flat_map(|y| (0..GRID_WIDTH).map(move |x| Segment { x, y }))
+            .filter(|&cell| !self.snakes.iter().any(|snake| snake.is_at(cell)))
+            .collect();
+        // A board with no room left keeps the old spot, nobody can get there anyway
+        if let Some(&cell) = free.choose(&mut self.rng) {
+            self.food = cell;
+        }
+    }
+
+    pub fn step(&mut self, commands: [Option<PlayerCommand>; 2]) {
+        if self.result.is_some() {
+            return;
+        }
+
+        for (snake, command) in self.snakes.iter_mut().zip(commands) {
+            if let Some(command) = command {
+                snake.buffer(command);
+            }
+            snake.update(self.tick_length, None);
+        }
+
+        let mut ate = false;
+        for snake in &mut self.snakes {
+            if snake.head() == self.food {
+                snake.grow();
+                ate = true;
+            }
+        }
+
+        let dead: Vec<bool> = (0..2)
+            .map(|player| {
+                let snake = &self.snakes[player];
+                snake.is_dead() || self.snakes[1 - player].is_at(snake.head())
+            })
+            .collect();
+
+        self.result = match dead[..] {
+            [true, true] => Some(VersusResult::Draw),
+            [true, false] => Some(VersusResult::Winner(1)),
+            [false, true] => Some(VersusResult::Winner(0)),
+            _ => None,
+        };
+
+        if ate && self.result.is_none() {
+            self.place_food();
+        }
+        self.tick += 1;
+    }
+
+    // FNV-1a over everything that affects the next tick
+    pub fn hash(&self) -> u64 {
+        let mut hash: u64 = 0xcbf29ce484222325;
+        let mut feed = |value: i64| {
+            for byte in value.to_le_bytes() {
+                hash ^= byte as u64;
+                hash = hash.wrapping_mul(0x100000001b3);
+            }
+        };
+
+        feed(self.tick as i64);
+        // The next number it would draw stands in for the generator's state
+        feed(self.rng.clone().gen::<i64>());
+        feed(self.food.x as i64);
+        feed(self.food.y as i64);
+        for snake in &self.snakes {
+            feed(snake.dir as i64);
+            feed(snake.move_timer.to_bits() as i64);
+            feed(snake.grow_tail as i64);
+            for cell in &snake.body {
+                feed(cell.x as i64);
+                feed(cell.y as i64);
+            }
+        }
+        hash
+    }
+
+    pub fn draw(&self) {
+        let offset = get_offset();
+        draw_grid(Color::new(0.15, 0.15, 0.2, 1.0), false);
+
+        draw_rectangle(
+            offset.x + self.food.x as f32 * CELL_SIZE,
+            offset.y + self.food.y as f32 * CELL_SIZE,
+            CELL_SIZE,
+            CELL_SIZE,
+            RED,
+        );
+
+        for (player, snake) in self.snakes.iter().enumerate() {
+            for (i, cell) in snake.body.iter().enumerate() {
+                let color = if i == 0 { WHITE } else { PLAYER_COLORS[player] };
+                draw_rectangle(
+                    offset.x + cell.x as f32 * CELL_SIZE + 1.0,
+                    offset.y + cell.y as f32 * CELL_SIZE + 1.0,
+                    CELL_SIZE - 2.0,
+                    CELL_SIZE - 2.0,
+                    color,
+                );
+            }
+        }
+    }
+}
+
+// One character per tick: a direction for SetDirection, lower case for a sidestep,
+// arrows for a whip from that heading, and . for no command
+const COMMAND_CODES: [(char, PlayerCommand); 14] = [
+    ('U', PlayerCommand::SetDirection(Direction::Up)),
+    ('D', PlayerCommand::SetDirection(Direction::Down)),
+    ('L', PlayerCommand::SetDirection(Direction::Left)),
+    ('R', PlayerCommand::SetDirection(Direction::Right)),
+    ('u', PlayerCommand::Sidestep(Direction::Up)),
+    ('d', PlayerCommand::Sidestep(Direction::Down)),
+    ('l', PlayerCommand::Sidestep(Direction::Left)),
+    ('r', PlayerCommand::Sidestep(Direction::Right)),
+    ('^', PlayerCommand::Whip(Direction::Up)),
+    ('v', PlayerCommand::Whip(Direction::Down)),
+    ('{', PlayerCommand::Whip(Direction::Left)),
+    ('}', PlayerCommand::Whip(Direction::Right)),
+    ('<', PlayerCommand::TurnLeft),
+    ('>', PlayerCommand::TurnRight),
+];
+
+fn encode_command(command: Option<PlayerCommand>) -> char {
+    COMMAND_CODES
+        .iter()
+        .find(|(_, known)| Some(*known) == command)
+        .map_or('.', |(code, _)| *code)
+}
+
+fn decode_command(c: char) -> Option<PlayerCommand> {
+    COMMAND_CODES.iter().find(|(code, _)| *code == c).map(|(_, command)| *command)
+}
+
+// Deterministic lockstep over UDP. Every packet repeats all inputs the peer hasn't
+// confirmed yet, so lost packets just mean a short stall instead of a desync.
+struct Session {
+    socket: UdpSocket,
+    peer: SocketAddr,
+    local_player: usize,
+    local_inputs: Vec<Option<PlayerCommand>>,
+    remote_inputs: Vec<Option<PlayerCommand>>,
+    peer_has: usize, // How many of our inputs the peer has confirmed
+    local_hashes: HashMap<u32, u64>,
+    remote_hashes: HashMap<u32, u64>,
+    last_hash: Option<(u32, u64)>,
+    start_packet: Option<String>, // Host only, resent if the joiner missed it
+    desynced: bool,
+    last_heard: f64, // When the last packet from the peer arrived
+    peer_left: bool, // The peer said BYE
+}
+
+impl Session {
+    fn new(socket: UdpSocket, peer: SocketAddr, local_player: usize, delay: u32) -> Self {
+        Self {
+            socket,
+            peer,
+            local_player,
+            // The first `delay` ticks have no input from either side
+            local_inputs: vec![None; delay as usize],
+            remote_inputs: vec![None; delay as usize],
+            peer_has: 0,
+            local_hashes: HashMap::new(),
+            remote_hashes: HashMap::new(),
+            last_hash: None,
+            start_packet: None,
+            desynced: false,
+            last_heard: get_time(),
+            peer_left: false,
+        }
+    }
+
+    fn send(&self) {
+        let start = self.peer_has.min(self.local_inputs.len());
+        let end = self.local_inputs.len().min(start + MAX_INPUTS_PER_PACKET);
+        let inputs: String = self.local_inputs[start..end].iter().map(|c| encode_command(*c)).collect();
+        let packet = format!("IN {} {} {}", self.remote_inputs.len(), start, inputs);
+        let _ = self.socket.send_to(packet.as_bytes(), self.peer);
+
+        if let Some((tick, hash)) = self.last_hash {
+            let _ = self.socket.send_to(format!("HASH {} {:016x}", tick, hash).as_bytes(), self.peer);
+        }
+    }
+
+    // Lets the peer know we've gone rather than leaving it to time out.
+    // A few copies, since nothing comes back to say it arrived.
+    fn say_bye(&self) {
+        for _ in 0..3 {
+            let _ = self.socket.send_to(b"BYE", self.peer);
+        }
+    }
+
+    fn receive(&mut self) {
+        let mut buffer = [0u8; 512];
+        while let Ok((size, from)) = self.socket.recv_from(&mut buffer) {
+            if from != self.peer {
+                continue;
+            }
+            self.last_heard = get_time();
+            let text = String::from_utf8_lossy(&buffer[..size]).to_string();
+            let mut parts = text.split_whitespace();
+            match parts.next() {
+                Some("IN") => {
+                    let peer_has = parts.next().and_then(|v| v.parse().ok());
+                    let start = parts.next().and_then(|v| v.parse::<usize>().ok());
+                    if let (Some(peer_has), Some(start)) = (peer_has, start) {
+                        self.peer_has = self.peer_has.max(peer_has);
+                        let inputs = parts.next().unwrap_or("");
+                        for (i, c) in inputs.chars().enumerate() {
+                            // Only append in order, anything past a gap will be resent
+                            if start + i == self.remote_inputs.len() {
+                                self.remote_inputs.push(decode_command(c));
+                            }
+                        }
+                    }
+                }
+                Some("HELLO") => {
+                    if let Some(start) = &self.start_packet {
+                        let _ = self.socket.send_to(start.as_bytes(), self.peer);
+                    }
+                }
+                Some("HASH") => {
+                    let tick = parts.next().and_then(|v| v.parse().ok());
+                    let hash = parts.next().and_then(|v| u64::from_str_radix(v, 16).ok());
+                    if let (Some(tick), Some(hash)) = (tick, hash) {
+                        self.remote_hashes.insert(tick, hash);
+                    }
+                }
+                Some("BYE") => self.peer_left = true,
+                _ => {}
+            }
+        }
+    }
+
+    fn record_hash(&mut self, tick: u32, hash: u64) {
+        self.local_hashes.insert(tick, hash);
+        self.last_hash = Some((tick, hash));
+    }
+
+    fn check_desync(&mut self) {
+        let mut newest_compared = None;
+        for (tick, hash) in &self.remote_hashes {
+            if let Some(local) = self.local_hashes.get(tick) {
+                if local != hash {
+                    println!("Warning: Versus desync at tick {}", tick);
+                    self.desynced = true;
+                }
+                newest_compared = newest_compared.max(Some(*tick));
+            }
+        }
+        // The peer only ever sends its newest hash, so nothing at or before one we've
+        // both had a look at can be compared again
+        if let Some(newest) = newest_compared {
+            self.local_hashes.retain(|tick, _| *tick > newest);
+            self.remote_hashes.retain(|tick, _| *tick > newest);
+        }
+    }
+
+    // Why the match can't go on without the peer, if it can't
+    fn lost_peer(&self) -> Option<&'static str> {
+        if self.peer_left {
+            Some("Your opponent left the match")
+        } else if get_time() - self.last_heard > PEER_TIMEOUT {
+            Some("Lost contact with your opponent")
+        } else {
+            None
+        }
+    }
+}
+
+// A match in progress, boxed in the lobby state since it's much bigger than the rest
+struct Match {
+    session: Session,
+    sim: VersusSim,
+    input: KeyboardInput,
+    pending: VecDeque<PlayerCommand>, // Our presses waiting for a tick to go out on
+    timestep: FixedTimestep,
+    delay: u32, // Ticks between pressing a key and the snakes acting on it
+}
+
+enum LobbyState {
+    Menu,
+    EnteringCode(String),
+    Hosting { socket: UdpSocket, code: String },
+    Joining { socket: UdpSocket, host: SocketAddr, timer: f32 },
+    Playing(Box<Match>),
+    Error(String),
+}
+
+// Versus lobby and match, opened from the title screen
+pub struct Versus {
+    pub showing: bool,
+    state: LobbyState,
+    input_delay_ms: u32,
+    controls: ControlScheme, // The player's own, from their profile
+}
+
+impl Versus {
+    pub fn new() -> Self {
+        Self {
+            showing: false,
+            state: LobbyState::Menu,
+            input_delay_ms: 50,
+            controls: ControlScheme::Keys(KeyPreset::ArrowsAndWasd),
+        }
+    }
+
+    pub fn open(&mut self, controls: ControlScheme) {
+        self.showing = true;
+        self.controls = controls;
+        self.state = LobbyState::Menu;
+    }
+
+    // Also tells the opponent when that ends a match
+    fn set_state(&mut self, state: LobbyState) {
+        if let LobbyState::Playing(game) = &self.state {
+            game.session.say_bye();
+        }
+        self.state = state;
+    }
+
+    pub fn update(&mut self, delta_time: f32) {
+        if is_key_pressed(KeyCode::Escape) {
+            // Dropping the state closes any open socket
+            match self.state {
+                LobbyState::Menu => self.showing = false,
+                _ => self.set_state(LobbyState::Menu),
+            }
+            return;
+        }
+
+        let next = match &mut self.state {
+            LobbyState::Menu => {
+                if is_key_pressed(KeyCode::H) {
+                    Some(host())
+                } else if is_key_pressed(KeyCode::J) {
+                    while get_char_pressed().is_some() {}
+                    Some(LobbyState::EnteringCode(String::new()))
+                } else if is_key_pressed(KeyCode::Up) {
+                    self.input_delay_ms = (self.input_delay_ms + INPUT_DELAY_STEP_MS).min(MAX_INPUT_DELAY_MS);
+                    None
+                } else if is_key_pressed(KeyCode::Down) {
+                    self.input_delay_ms = self.input_delay_ms.saturating_sub(INPUT_DELAY_STEP_MS);
+                    None
+                } else {
+                    None
+                }
+            }
+            LobbyState::EnteringCode(code) => {
+                while let Some(c) = get_char_pressed() {
+                    if (c.is_ascii_digit() || c == '.' || c == ':') && code.len() < 21 {
+                        code.push(c);
+                    }
+                }
+                if is_key_pressed(KeyCode::Backspace) {
+                    code.pop();
+                }
+                if is_key_pressed(KeyCode::Enter) {
+                    Some(join(code))
+                } else {
+                    None
+                }
+            }
+            LobbyState::Hosting { socket, .. } => {
+                // The first HELLO picks the opponent, the host decides seed, delay and tick
+                let mut buffer = [0u8; 64];
+                match socket.recv_from(&mut buffer) {
+                    Ok((size, from)) if &buffer[..size] == b"HELLO" => {
+                        let seed = (get_time() * 1_000_000.0) as u64 ^ from.port() as u64;
+                        let tick_length = FixedTimestep::new().tick;
+                        let delay = (self.input_delay_ms as f32 / 1000.0 / tick_length).round() as u32;
+                        let start = format!("START {} {} {}", seed, delay, tick_length);
+                        // Send a few copies, the joiner also keeps saying hello until it hears back
+                        for _ in 0..3 {
+                            let _ = socket.send_to(start.as_bytes(), from);
+                        }
+                        socket.try_clone().ok().map(|socket| {
+                            let mut state = start_match(socket, from, 0, seed, delay, tick_length, self.controls);
+                            if let LobbyState::Playing(game) = &mut state {
+                                game.session.start_packet = Some(start);
+                            }
+                            state
+                        })
+                    }
+                    _ => None,
+                }
+            }
+            LobbyState::Joining { socket, host, timer } => {
+                *timer -= delta_time;
+                if *timer <= 0.0 {
+                    *timer = 0.5;
+                    let _ = socket.send_to(b"HELLO", *host);
+                }
+
+                let mut buffer = [0u8; 64];
+                match socket.recv_from(&mut buffer) {
+                    Ok((size, from)) if from == *host => {
+                        let text = String::from_utf8_lossy(&buffer[..size]).to_string();
+                        let mut parts = text.split_whitespace();
+                        let seed = parts.nth(1).and_then(|v| v.parse().ok());
+                        let delay = parts.next().and_then(|v| v.parse().ok());
+                        let tick_length = parts.next().and_then(|v| v.parse().ok());
+                        match (text.starts_with("START"), seed, delay, tick_length) {
+                            (true, Some(seed), Some(delay), Some(tick_length)) => socket
+                                .try_clone()
+                                .ok()
+                                .map(|socket| start_match(socket, *host, 1, seed, delay, tick_length, self.controls)),
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                }
+            }
+            LobbyState::Playing(game) => {
+                let Match { session, sim, input, pending, timestep, delay } = game.as_mut();
+                let heading = sim.snakes[session.local_player].queued_heading();
+                for command in input.poll(heading) {
+                    if pending.len() < MAX_PENDING {
+                        pending.push_back(command);
+                    }
+                }
+
+                session.receive();
+
+                timestep.advance(delta_time);
+                loop {
+                    // Step every tick both sides have inputs for, once its slot in our own queue has passed
+                    while sim.result.is_none()
+                        && !session.desynced
+                        && ((sim.tick + *delay) as usize) < session.local_inputs.len()
+                        && (sim.tick as usize) < session.remote_inputs.len()
+                    {
+                        let tick = sim.tick as usize;
+                        let mut commands = [None; 2];
+                        commands[session.local_player] = session.local_inputs[tick];
+                        commands[1 - session.local_player] = session.remote_inputs[tick];
+                        sim.step(commands);
+
+                        if sim.tick % HASH_INTERVAL == 0 {
+                            session.record_hash(sim.tick, sim.hash());
+                        }
+                    }
+
+                    // Queue our input for a future tick, never running more than the delay ahead
+                    if session.local_inputs.len() > (sim.tick + *delay) as usize {
+                        // Stalled on the opponent, don't bank up ticks to rush through afterwards
+                        timestep.hold();
+                        break;
+                    }
+                    if !timestep.step() {
+                        break;
+                    }
+                    session.local_inputs.push(pending.pop_front());
+                }
+
+                session.check_desync();
+                session.send();
+
+                let lost = session.lost_peer().filter(|_| sim.result.is_none() && !session.desynced);
+                if let Some(reason) = lost {
+                    Some(LobbyState::Error(reason.to_string()))
+                } else if sim.result.is_some() && is_key_pressed(KeyCode::Space) {
+                    Some(LobbyState::Menu)
+                } else {
+                    None
+                }
+            }
+            LobbyState::Error(_) => {
+                if is_key_pressed(KeyCode::Space) {
+                    Some(LobbyState::Menu)
+                } else {
+                    None
+                }
+            }
+        };
+
+        if let Some(state) = next {
+            self.set_state(state);
+        }
+    }
+
+    pub fn draw(&self) {
+        clear_background(BLACK);
+
+        match &self.state {
+            LobbyState::Menu => {
+                draw_centered("VERSUS", 120.0, 64.0, GREEN);
+                draw_centered("H - Host a match", 220.0, 30.0, WHITE);
+                draw_centered("J - Join with a code", 260.0, 30.0, WHITE);
+                let delay = format!("Input delay: {} ms  (UP / DOWN)", self.input_delay_ms);
+                draw_centered(&delay, 320.0, 26.0, LIGHTGRAY);
+                draw_centered("Higher delay hides lag, lower feels snappier", 350.0, 20.0, GRAY);
+                draw_centered("ESC - Back", screen_height() - 30.0, 24.0, LIGHTGRAY);
+            }
+            LobbyState::EnteringCode(code) => {
+                draw_centered("JOIN MATCH", 120.0, 48.0, GREEN);
+                draw_centered("Type the host's code (address:port)", 200.0, 26.0, LIGHTGRAY);
+                draw_centered(&format!("{}_", code), 260.0, 36.0, WHITE);
+                draw_centered("ENTER - Join   ESC - Back", screen_height() - 30.0, 24.0, LIGHTGRAY);
+            }
+            LobbyState::Hosting { code, .. } => {
+                draw_centered("HOSTING", 120.0, 48.0, GREEN);
+                draw_centered("Give your opponent this code:", 200.0, 26.0, LIGHTGRAY);
+                draw_centered(code, 250.0, 40.0, YELLOW);
+                draw_centered("Waiting for a player to join...", 320.0, 26.0, WHITE);
+                draw_centered("ESC - Cancel", screen_height() - 30.0, 24.0, LIGHTGRAY);
+            }
+            LobbyState::Joining { host, .. } => {
+                draw_centered("JOINING", 120.0, 48.0, GREEN);
+                draw_centered(&format!("Contacting {}...", host), 220.0, 26.0, WHITE);
+                draw_centered("ESC - Cancel", screen_height() - 30.0, 24.0, LIGHTGRAY);
+            }
+            LobbyState::Playing(game) => {
+                let Match { session, sim, .. } = game.as_ref();
+                sim.draw();
+
+                let you = format!("You are {}", if session.local_player == 0 { "GREEN" } else { "BLUE" });
+                draw_text(&you, 20.0, 30.0, 24.0, PLAYER_COLORS[session.local_player]);
+
+                let waiting = sim.result.is_none() && sim.tick as usize >= session.remote_inputs.len();
+                if session.desynced {
+                    draw_centered("DESYNC - the match can't continue", screen_height() / 2.0, 36.0, RED);
+                    draw_centered("ESC - Back to lobby", screen_height() / 2.0 + 40.0, 24.0, LIGHTGRAY);
+                } else if let Some(result) = sim.result {
+                    let text = match result {
+                        VersusResult::Draw => "DRAW!",
+                        VersusResult::Winner(player) if player == session.local_player => "YOU WIN!",
+                        VersusResult::Winner(_) => "YOU LOSE",
+                    };
+                    draw_centered(text, screen_height() / 2.0, 64.0, YELLOW);
+                    draw_centered("Press SPACE to return to the lobby", screen_height() / 2.0 + 50.0, 24.0, LIGHTGRAY);
+                } else if waiting && sim.tick > 0 {
+                    draw_centered("Waiting for opponent...", 30.0, 24.0, ORANGE);
+                }
+            }
+            LobbyState::Error(message) => {
+                draw_centered("CONNECTION PROBLEM", 120.0, 48.0, RED);
+                draw_centered(message, 200.0, 26.0, WHITE);
+                draw_centered("Press SPACE to go back", screen_height() - 30.0, 24.0, LIGHTGRAY);
+            }
+        }
+    }
+}
+
+fn host() -> LobbyState {
+    let socket = match UdpSocket::bind(("0.0.0.0", DEFAULT_PORT)) {
+        Ok(socket) => socket,
+        Err(e) => return LobbyState::Error(format!("Could not open port {}: {}", DEFAULT_PORT, e)),
+    };
+    if let Err(e) = socket.set_nonblocking(true) {
+        return LobbyState::Error(format!("Could not set up the connection: {}", e));
+    }
+
+    let port = socket.local_addr().map(|addr| addr.port()).unwrap_or(DEFAULT_PORT);
+    let code = format!("{}:{}", local_ip(), port);
+    LobbyState::Hosting { socket, code }
+}
+
+fn join(code: &str) -> LobbyState {
+    let host: SocketAddr = match code.parse() {
+        Ok(addr) => addr,
+        Err(_) => match format!("{}:{}", code, DEFAULT_PORT).parse() {
+            Ok(addr) => addr,
+            Err(_) => return LobbyState::Error(format!("\"{}\" isn't a valid code", code)),
+        },
+    };
+
+    let socket = match UdpSocket::bind(("0.0.0.0", 0)) {
+        Ok(socket) => socket,
+        Err(e) => return LobbyState::Error(format!("Could not open a port: {}", e)),
+    };
+    if let Err(e) = socket.set_nonblocking(true) {
+        return LobbyState::Error(format!("Could not set up the connection: {}", e));
+    }
+
+    LobbyState::Joining { socket, host, timer: 0.0 }
+}
+
+fn start_match(
+    socket: UdpSocket,
+    peer: SocketAddr,
+    local_player: usize,
+    seed: u64,
+    delay: u32,
+    tick_length: f32,
+    controls: ControlScheme,
+) -> LobbyState {
+    let mut timestep = FixedTimestep::new();
+    timestep.tick = tick_length;
+    LobbyState::Playing(Box::new(Match {
+        session: Session::new(socket, peer, local_player, delay),
+        sim: VersusSim::new(seed, tick_length),
+        input: KeyboardInput::new(controls),
+        pending: VecDeque::new(),
+        timestep,
+        delay,
+    }))
+}
+
+// Finds the address other machines on the network would use to reach us.
+// Connecting a UDP socket doesn't send anything, it just picks a route.
+fn local_ip() -> String {
+    UdpSocket::bind(("0.0.0.0", 0))
+        .and_then(|socket| {
+            socket.connect(("8.8.8.8", 80))?;
+            socket.local_addr()
+        })
+        .map(|addr| addr.ip().to_string())
+        .unwrap_or_else(|_| "127.0.0.1".to_string())
+}
+
+fn draw_centered(text: &str, y: f32, size: f32, color: Color) {
+    let width = measure_text(text, None, size as u16, 1.0).width;
+    draw_text(text, (screen_width() - width) / 2.0, y, size, color);
+}
@@ -0,0 +1,188 @@
+use macroquad::prelude::*;
+use ::rand::Rng;
+use crate::grid::{GRID_WIDTH, GRID_HEIGHT, CELL_SIZE, get_offset};
+use crate::level::{special_mechanics, SpecialMechanic};
+use crate::snake::{Segment, Snake};
+use crate::rng;
+
+const WALL_COUNT: usize = 3;
+const WALL_LENGTH: i32 = 3;
+const STEP_DELAY: f32 = 0.4;
+const MIN_TRAVEL: i32 = 4;
+const MAX_TRAVEL: i32 = 10;
+
+// A short bar that slides back and forth between two cells.
+// Vertical bars travel sideways, horizontal bars travel up and down.
+pub struct MovingWall {
+    pub start: Segment,
+    pub target: Segment,
+    pub position: Segment,
+    pub vertical: bool,
+    pub forward: bool,
+    pub timer: f32,
+}
+
+impl MovingWall {
+    pub fn cells(&self) -> Vec<Segment> {
+        cells_at(self.position, self.vertical)
+    }
+
+    // The end it's heading for, turning around once it gets there
+    fn heading_forward(&self) -> bool {
+        let goal = if self.forward { self.target } else { self.start };
+        if self.position == goal { !self.forward } else { self.forward }
+    }
+
+    // Where the next step will take it
+    fn next_position(&self) -> Segment {
+        let goal = if self.heading_forward() { self.target } else { self.start };
+        Segment {
+            x: self.position.x + (goal.x - self.position.x).signum(),
+            y: self.position.y + (goal.y - self.position.y).signum(),
+        }
+    }
+
+    fn step(&mut self) {
+        // Grid position only changes here, so collisions never see a half-moved wall
+        self.forward = self.heading_forward();
+        self.position = self.next_position();
+    }
+
+    // Every cell the wall can ever cover on its route
+    fn swept_cells(&self) -> Vec<Segment> {
+        let mut cells = Vec::new();
+        let mut position = self.start;
+        loop {
+            cells.extend(cells_at(position, self.vertical));
+            if position == self.target {
+                break;
+            }
+            position.x += (self.target.x - position.x).signum();
+            position.y += (self.target.y - position.y).signum();
+        }
+        cells
+    }
+}
+
+fn cells_at(position: Segment, vertical: bool) -> Vec<Segment> {
+    (0..WALL_LENGTH)
+        .map(|i| {
+            if vertical {
+                Segment { x: position.x, y: position.y + i }
+            } else {
+                Segment { x: position.x + i, y: position.y }
+            }
+        })
+        .collect()
+}
+
+pub struct MovingWalls {
+    pub walls: Vec<MovingWall>,
+}
+
+impl MovingWalls {
+    pub fn new() -> Self {
+        Self { walls: Vec::new() }
+    }
+
+    pub fn setup(&mut self, level: usize, snake: &Snake) {
+        self.walls.clear();
+
+        if !special_mechanics(level).contains(&SpecialMechanic::MovingWalls) {
+            return;
+        }
+
+        let mut rng = rng::gameplay();
+        let mut attempts = 0;
+
+        while self.walls.len() < WALL_COUNT && attempts < 100 {
+            attempts += 1;
+
+            let vertical = rng.gen_bool(0.5);
+            let travel = rng.gen_range(MIN_TRAVEL..=MAX_TRAVEL);
+            let (start, target) = if vertical {
+                let x = rng.gen_range(0..GRID_WIDTH - travel);
+                let y = rng.gen_range(0..GRID_HEIGHT - WALL_LENGTH);
+                (Segment { x, y }, Segment { x: x + travel, y })
+            } else {
+                let x = rng.gen_range(0..GRID_WIDTH - WALL_LENGTH);
+                let y = rng.gen_range(0..GRID_HEIGHT - travel);
+                (Segment { x, y }, Segment { x, y: y + travel })
+            };
+
+            let wall = MovingWall {
+                start,
+                target,
+                position: start,
+                vertical,
+                forward: true,
+                timer: 0.0,
+            };
+
+            // Don't drop a wall's route on top of the snake or another wall
+            let swept = wall.swept_cells();
+            let overlaps_snake = swept.iter().any(|&cell| snake.is_at(cell));
+            let overlaps_wall = self
+                .walls
+                .iter()
+                .any(|other| other.swept_cells().iter().any(|cell| swept.contains(cell)));
+
+            if !overlaps_snake && !overlaps_wall {
+                self.walls.push(wall);
+            }
+        }
+    }
+
+    pub fn update(&mut self, delta_time: f32) {
+        for wall in &mut self.walls {
+            wall.timer += delta_time;
+            if wall.timer >= STEP_DELAY {
+                wall.timer = 0.0;
+                wall.step();
+            }
+        }
+    }
+
+    pub fn is_occupied(&self, position: Segment) -> bool {
+        self.walls.iter().any(|wall| wall.cells().contains(&position))
+    }
+
+    // True if any wall will pass over this cell at some point
+    pub fn is_on_route(&self, position: Segment) -> bool {
+        self.walls.iter().any(|wall| wall.swept_cells().contains(&position))
+    }
+
+    pub fn draw(&self) {
+        let offset = get_offset();
+
+        for wall in &self.walls {
+            // Slide from the current cell toward the next, so the wall is never drawn behind
+            // where it collides and lands there just as the step happens
+            let t = (wall.timer / STEP_DELAY).min(1.0);
+            let next = wall.next_position();
+            let x = wall.position.x as f32 + (next.x - wall.position.x) as f32 * t;
+            let y = wall.position.y as f32 + (next.y - wall.position.y) as f32 * t;
+            let (width, height) = if wall.vertical {
+                (1.0, WALL_LENGTH as f32)
+            } else {
+                (WALL_LENGTH as f32, 1.0)
+            };
+
+            draw_rectangle(
+                offset.x + x * CELL_SIZE,
+                offset.y + y * CELL_SIZE,
+                width * CELL_SIZE,
+                height * CELL_SIZE,
+                LIGHTGRAY,
+            );
+            draw_rectangle_lines(
+                offset.x + x * CELL_SIZE,
+                offset.y + y * CELL_SIZE,
+                width * CELL_SIZE,
+                height * CELL_SIZE,
+                2.0,
+                GRAY,
+            );
+        }
+    }
+}
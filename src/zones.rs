@@ -0,0 +1,94 @@
+use macroquad::prelude::*;
+use ::rand::Rng;
+use crate::grid::{GRID_WIDTH, GRID_HEIGHT, CELL_SIZE, get_offset};
+use crate::level::{special_mechanics, SpecialMechanic};
+use crate::snake::Segment;
+use crate::rng;
+
+const FAST_MULTIPLIER: f32 = 1.6;
+const SLOW_MULTIPLIER: f32 = 0.6;
+
+pub struct SpeedZone {
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+    pub multiplier: f32,
+}
+
+impl SpeedZone {
+    fn contains(&self, position: Segment) -> bool {
+        position.x >= self.x
+            && position.x < self.x + self.width
+            && position.y >= self.y
+            && position.y < self.y + self.height
+    }
+}
+
+pub struct SpeedZones {
+    pub zones: Vec<SpeedZone>,
+}
+
+impl SpeedZones {
+    pub fn new() -> Self {
+        Self { zones: Vec::new() }
+    }
+
+    // One fast and one slow zone on levels that have them
+    pub fn setup(&mut self, level: usize) {
+        self.zones.clear();
+
+        if !special_mechanics(level).contains(&SpecialMechanic::SpeedZones) {
+            return;
+        }
+
+        let mut rng = rng::gameplay();
+        for multiplier in [FAST_MULTIPLIER, SLOW_MULTIPLIER] {
+            let width = rng.gen_range(5..=8);
+            let height = rng.gen_range(4..=6);
+            self.zones.push(SpeedZone {
+                x: rng.gen_range(0..GRID_WIDTH - width),
+                y: rng.gen_range(0..GRID_HEIGHT - height),
+                width,
+                height,
+                multiplier,
+            });
+        }
+    }
+
+    pub fn multiplier_at(&self, position: Segment) -> f32 {
+        self.zones
+            .iter()
+            .find(|zone| zone.contains(position))
+            .map_or(1.0, |zone| zone.multiplier)
+    }
+
+    pub fn draw(&self) {
+        let offset = get_offset();
+
+        for zone in &self.zones {
+            let color = if zone.multiplier > 1.0 {
+                Color::new(1.0, 0.4, 0.0, 1.0)
+            } else {
+                Color::new(0.2, 0.5, 1.0, 1.0)
+            };
+            let x = offset.x + zone.x as f32 * CELL_SIZE;
+            let y = offset.y + zone.y as f32 * CELL_SIZE;
+            let width = zone.width as f32 * CELL_SIZE;
+            let height = zone.height as f32 * CELL_SIZE;
+
+            draw_rectangle(x, y, width, height, Color::new(color.r, color.g, color.b, 0.15));
+            draw_rectangle_lines(x, y, width, height, 2.0, Color::new(color.r, color.g, color.b, 0.6));
+
+            let label = if zone.multiplier > 1.0 { ">>" } else { "<<" };
+            let label_width = measure_text(label, None, 24, 1.0).width;
+            draw_text(
+                label,
+                x + (width - label_width) / 2.0,
+                y + height / 2.0 + 8.0,
+                24.0,
+                Color::new(color.r, color.g, color.b, 0.5),
+            );
+        }
+    }
+}